@@ -0,0 +1,17 @@
+use eframe::egui;
+use egui::accesskit;
+
+/// Gives a widget the name screen readers read out, for buttons whose text is a symbol.
+pub fn set_name(response: &egui::Response, name: &str) {
+    response
+        .ctx
+        .accesskit_node_builder(response.id, |node| node.set_label(name));
+}
+
+/// Has screen readers read out the widget's text whenever it changes, without moving the
+/// focus to it.
+pub fn announce_changes(response: &egui::Response) {
+    response
+        .ctx
+        .accesskit_node_builder(response.id, |node| node.set_live(accesskit::Live::Polite));
+}
@@ -0,0 +1,403 @@
+use crate::deck::DeckInfo;
+use crate::{ConjugationForm, DanishVerbsApp, Verb, grading};
+use eframe::egui;
+use rusqlite::{Connection, DatabaseName, params};
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::io::{self, Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+/// Id of the note type, which stays the same so later exports update the same notes.
+const MODEL_ID: i64 = 1_729_000_000_000;
+
+/// The tables and indexes of an Anki collection, in the schema version 11 that packages
+/// are still read in.
+const SCHEMA: &str = "
+create table col (
+    id integer primary key, crt integer not null, mod integer not null,
+    scm integer not null, ver integer not null, dty integer not null,
+    usn integer not null, ls integer not null, conf text not null,
+    models text not null, decks text not null, dconf text not null, tags text not null
+);
+create table notes (
+    id integer primary key, guid text not null, mid integer not null,
+    mod integer not null, usn integer not null, tags text not null,
+    flds text not null, sfld integer not null, csum integer not null,
+    flags integer not null, data text not null
+);
+create table cards (
+    id integer primary key, nid integer not null, did integer not null,
+    ord integer not null, mod integer not null, usn integer not null,
+    type integer not null, queue integer not null, due integer not null,
+    ivl integer not null, factor integer not null, reps integer not null,
+    lapses integer not null, left integer not null, odue integer not null,
+    odid integer not null, flags integer not null, data text not null
+);
+create table revlog (
+    id integer primary key, cid integer not null, usn integer not null,
+    ease integer not null, ivl integer not null, lastIvl integer not null,
+    factor integer not null, time integer not null, type integer not null
+);
+create table graves (usn integer not null, oid integer not null, type integer not null);
+create index ix_notes_usn on notes (usn);
+create index ix_cards_usn on cards (usn);
+create index ix_revlog_usn on revlog (usn);
+create index ix_cards_nid on cards (nid);
+create index ix_cards_sched on cards (did, queue, due);
+create index ix_revlog_cid on revlog (cid);
+create index ix_notes_csum on notes (csum);
+";
+
+/// Packs verbs into an Anki package (`.apkg`), for File → Import in Anki.
+///
+/// Each verb is a note with a field for the infinitive, each form and the meaning, and a
+/// card asking for the forms and meaning from the infinitive. The guid comes from the
+/// infinitive, so exporting again and importing updates the notes instead of duplicating
+/// them. `tags` gives each verb's tags, which is how its results travel along.
+pub fn anki_package(
+    verbs: &[&Verb],
+    info: &DeckInfo,
+    tags: impl Fn(&Verb) -> Vec<String>,
+    now: u64,
+) -> io::Result<Vec<u8>> {
+    let collection = collection(verbs, info, tags, now).map_err(io::Error::other)?;
+    let mut package = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    package.start_file("collection.anki2", options)?;
+    package.write_all(&collection)?;
+    // The package has no pictures or sounds
+    package.start_file("media", options)?;
+    package.write_all(b"{}")?;
+    Ok(package.finish()?.into_inner())
+}
+
+/// The Anki collection holding the notes, as the bytes of its SQLite database.
+fn collection(
+    verbs: &[&Verb],
+    info: &DeckInfo,
+    tags: impl Fn(&Verb) -> Vec<String>,
+    now: u64,
+) -> rusqlite::Result<Vec<u8>> {
+    let now = i64::try_from(now).unwrap_or_default();
+    let now_ms = now * 1_000;
+    let deck_name = format!("{} verbs", info.language);
+    let deck_id = deck_id(&deck_name);
+
+    let db = Connection::open_in_memory()?;
+    db.execute_batch(SCHEMA)?;
+    db.execute(
+        "insert into col values (1, ?1, ?2, ?2, 11, 0, 0, 0, ?3, ?4, ?5, ?6, '{}')",
+        params![
+            now,
+            now_ms,
+            serde_json::json!({"curDeck": deck_id, "activeDecks": [deck_id], "nextPos": verbs.len() + 1})
+                .to_string(),
+            serde_json::json!({ MODEL_ID.to_string(): note_type(info, deck_id, now) }).to_string(),
+            serde_json::json!({
+                "1": deck(1, "Default", now),
+                deck_id.to_string(): deck(deck_id, &deck_name, now),
+            })
+            .to_string(),
+            serde_json::json!({ "1": deck_options(now) }).to_string(),
+        ],
+    )?;
+
+    for (index, verb) in verbs.iter().enumerate() {
+        let id = now_ms + index as i64;
+        let mut fields = vec![escape(&verb.infinitive)];
+        fields.extend(
+            ConjugationForm::ALL
+                .into_iter()
+                .map(|form| escape(&grading::format_variants(verb.form(form)))),
+        );
+        fields.push(escape(&verb.english));
+        let tags: Vec<String> = tags(verb)
+            .iter()
+            .map(|tag| tag.replace(char::is_whitespace, "_"))
+            .collect();
+        db.execute(
+            "insert into notes values (?1, ?2, ?3, ?4, -1, ?5, ?6, ?7, ?8, 0, '')",
+            params![
+                id,
+                guid(&verb.infinitive),
+                MODEL_ID,
+                now,
+                format!(" {} ", tags.join(" ")),
+                fields.join("\x1f"),
+                fields[0],
+                checksum(&fields[0]),
+            ],
+        )?;
+        // New cards, in deck order
+        db.execute(
+            "insert into cards values (?1, ?1, ?2, 0, ?3, -1, 0, 0, ?4, 0, 0, 0, 0, 0, 0, 0, 0, '')",
+            params![id, deck_id, now, index as i64 + 1],
+        )?;
+    }
+
+    Ok(db.serialize(DatabaseName::Main)?.to_vec())
+}
+
+/// The note type, with a field for the infinitive, each form and the meaning.
+fn note_type(info: &DeckInfo, deck_id: i64, now: i64) -> serde_json::Value {
+    let mut names = vec!["Infinitive".to_string()];
+    names.extend(ConjugationForm::ALL.map(|form| capitalize(form.label())));
+    names.push("English".to_string());
+    let fields: Vec<serde_json::Value> = names
+        .iter()
+        .enumerate()
+        .map(|(ord, name)| {
+            serde_json::json!({
+                "name": name, "ord": ord, "sticky": false, "rtl": false,
+                "font": "Arial", "size": 20, "media": [],
+            })
+        })
+        .collect();
+    // Forms the verb doesn't have are left out of the back
+    let mut back = "{{FrontSide}}<hr id=answer>".to_string();
+    for (form, name) in ConjugationForm::ALL.into_iter().zip(&names[1..]) {
+        back += &format!(
+            "{{{{#{name}}}}}{}: {{{{{name}}}}}<br>{{{{/{name}}}}}",
+            escape(info.form_name(form))
+        );
+    }
+    back += "<br>{{English}}";
+    serde_json::json!({
+        "id": MODEL_ID,
+        "name": "Danish verb",
+        "type": 0,
+        "mod": now,
+        "usn": -1,
+        "sortf": 0,
+        "did": deck_id,
+        "flds": fields,
+        "tmpls": [{
+            "name": "Forms", "ord": 0, "qfmt": "{{Infinitive}}", "afmt": back,
+            "did": null, "bqfmt": "", "bafmt": "",
+        }],
+        "css": ".card { font-family: arial; font-size: 20px; text-align: center; }",
+        "latexPre": "\\documentclass[12pt]{article}\n\\begin{document}\n",
+        "latexPost": "\\end{document}",
+        "req": [[0, "any", [0]]],
+        "tags": [],
+        "vers": [],
+    })
+}
+
+fn deck(id: i64, name: &str, now: i64) -> serde_json::Value {
+    serde_json::json!({
+        "id": id, "name": name, "desc": "", "mod": now, "usn": -1, "conf": 1, "dyn": 0,
+        "collapsed": false, "browserCollapsed": false, "extendNew": 0, "extendRev": 0,
+        "newToday": [0, 0], "revToday": [0, 0], "lrnToday": [0, 0], "timeToday": [0, 0],
+    })
+}
+
+/// Anki's default options, which every deck in the package uses.
+fn deck_options(now: i64) -> serde_json::Value {
+    serde_json::json!({
+        "id": 1, "name": "Default", "mod": now, "usn": -1, "maxTaken": 60, "autoplay": true,
+        "timer": 0, "replayq": true, "dyn": false,
+        "new": {
+            "delays": [1, 10], "ints": [1, 4, 7], "initialFactor": 2500, "order": 1,
+            "perDay": 20, "bury": true, "separate": true,
+        },
+        "rev": {
+            "perDay": 200, "ease4": 1.3, "fuzz": 0.05, "ivlFct": 1, "maxIvl": 36500,
+            "bury": true, "minSpace": 1,
+        },
+        "lapse": {
+            "delays": [10], "mult": 0, "minInt": 1, "leechFails": 8, "leechAction": 0,
+        },
+    })
+}
+
+/// A deck id that stays the same for a deck name between exports, kept within the
+/// integers JavaScript can hold as Anki's JSON needs.
+fn deck_id(name: &str) -> i64 {
+    (fnv(name) & 0x000f_ffff_ffff_ffff) as i64
+}
+
+/// The first 32 bits of the SHA-1 of a note's first field, which Anki finds duplicates by.
+fn checksum(field: &str) -> i64 {
+    let digest = Sha1::digest(field.as_bytes());
+    i64::from(u32::from_be_bytes([
+        digest[0], digest[1], digest[2], digest[3],
+    ]))
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// Makes text safe for an HTML field.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\n', "<br>")
+}
+
+/// The FNV-1a hash of some text.
+fn fnv(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// A note id that stays the same for an infinitive between exports.
+fn guid(infinitive: &str) -> String {
+    format!("verb-{:016x}", fnv(infinitive))
+}
+
+impl DanishVerbsApp {
+    /// The part of the File menu exporting the deck for Anki.
+    pub(crate) fn show_anki_export(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .button("Export deck for Anki…")
+            .on_hover_text(
+                "Write the verbs as an Anki package, tagged with how you're doing on each. \
+                 Import the package in Anki with File → Import",
+            )
+            .clicked()
+            && let Some(path) = rfd::FileDialog::new()
+                .set_file_name(format!("{} verbs.apkg", self.deck_info.language))
+                .add_filter("Anki package", &["apkg"])
+                .save_file()
+        {
+            let by_position = self.deck_cache().by_position.clone();
+            let verbs: Vec<&Verb> = by_position
+                .iter()
+                .map(|&index| &self.verbs[index])
+                .collect();
+            let package = anki_package(
+                &verbs,
+                &self.deck_info,
+                |verb| self.anki_tags(verb),
+                crate::stats::now(),
+            );
+            self.anki_message = Some(
+                match package.and_then(|package| fs::write(&path, package)) {
+                    Ok(()) => format!("Exported {} notes to {}", verbs.len(), path.display()),
+                    Err(e) => format!("Couldn't write the file: {}", e),
+                },
+            );
+        }
+        if let Some(anki_message) = &self.anki_message {
+            ui.label(egui::RichText::new(anki_message).small());
+        }
+    }
+
+    /// Tags carrying a verb's marks and all-time accuracy, rounded down to tens, into Anki.
+    fn anki_tags(&self, verb: &Verb) -> Vec<String> {
+        let infinitive = &verb.infinitive;
+        let mut tags = Vec::new();
+        for (marked, tag) in [
+            (self.starred_verbs.contains(infinitive), "starred"),
+            (self.learned_verbs.contains(infinitive), "learned"),
+            (
+                self.stats
+                    .is_leech(infinitive, self.settings.leech_threshold),
+                "leech",
+            ),
+        ] {
+            if marked {
+                tags.push(tag.to_string());
+            }
+        }
+        match self
+            .stats
+            .verbs
+            .get(infinitive)
+            .filter(|verb_stat| verb_stat.attempts > 0)
+        {
+            Some(verb_stat) => tags.push(format!(
+                "accuracy::{}",
+                verb_stat.correct * 10 / verb_stat.attempts * 10
+            )),
+            None => tags.push("unseen".to_string()),
+        }
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn packs_one_note_per_verb() {
+        let verb: Verb = serde_json::from_value(serde_json::json!({
+            "infinitive": "at hænge",
+            "present": "hænger",
+            "past": "hang/hængte",
+            "past_participle": "hængt",
+            "english": "to hang <sth>",
+        }))
+        .unwrap();
+        let package = anki_package(
+            &[&verb],
+            &DeckInfo::default(),
+            |_| vec!["starred".to_string(), "accuracy 80".to_string()],
+            1_791_987_000,
+        )
+        .unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(package)).unwrap();
+        let mut media = String::new();
+        archive
+            .by_name("media")
+            .unwrap()
+            .read_to_string(&mut media)
+            .unwrap();
+        assert_eq!(media, "{}");
+        let mut collection = Vec::new();
+        archive
+            .by_name("collection.anki2")
+            .unwrap()
+            .read_to_end(&mut collection)
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("anki-{}.anki2", std::process::id()));
+        fs::write(&path, collection).unwrap();
+        let db = Connection::open(&path).unwrap();
+
+        let (guid_field, tags, fields, sort_field): (String, String, String, String) = db
+            .query_row("select guid, tags, flds, sfld from notes", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .unwrap();
+        assert_eq!(guid_field, guid("at hænge"));
+        assert_ne!(guid_field, guid("at hænger"));
+        assert_eq!(tags, " starred accuracy_80 ");
+        assert_eq!(
+            fields.split('\x1f').collect::<Vec<_>>(),
+            [
+                "at hænge",
+                "hænger",
+                "hang / hængte",
+                "hængt",
+                "",
+                "",
+                "to hang &lt;sth&gt;"
+            ]
+        );
+        assert_eq!(sort_field, "at hænge");
+        let cards: i64 = db
+            .query_row("select count(*) from cards", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(cards, 1);
+        let models: String = db
+            .query_row("select models from col", [], |row| row.get(0))
+            .unwrap();
+        let models: serde_json::Value = serde_json::from_str(&models).unwrap();
+        assert_eq!(
+            models[MODEL_ID.to_string()]["flds"][1]["name"],
+            "Present tense"
+        );
+        drop(db);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{DanishVerbsApp, grading};
+use eframe::egui;
+use std::cell::Ref;
+
+/// How many search matches are listed below the search box
+const MAX_MATCHES: usize = 8;
+
+impl DanishVerbsApp {
+    /// Study view showing every form of one verb at a time, without any grading.
+    pub(crate) fn show_browser(
+        &mut self,
+        ui: &mut egui::Ui,
+        body_font: &egui::FontId,
+        heading_font: &egui::FontId,
+        accent_color: egui::Color32,
+    ) {
+        if self.verbs.is_empty() {
+            return;
+        }
+        self.browse_index = self.browse_index.min(self.verbs.len() - 1);
+
+        // Search to jump straight to a verb
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Find:")
+                    .font(body_font.clone())
+                    .color(self.theme.text),
+            );
+            ui.add_sized(
+                [ui.available_width(), 36.0],
+                egui::TextEdit::singleline(&mut self.browse_search)
+                    .font(body_font.clone())
+                    .hint_text("Danish or English"),
+            );
+        });
+
+        let query = self.browse_search.trim().to_lowercase();
+        if !query.is_empty() {
+            let matches: Vec<usize> = self
+                .verbs
+                .iter()
+                .enumerate()
+                .filter(|(_, verb)| {
+                    verb.infinitive.to_lowercase().contains(&query)
+                        || verb.english.to_lowercase().contains(&query)
+                })
+                .map(|(index, _)| index)
+                .take(MAX_MATCHES)
+                .collect();
+
+            if matches.is_empty() {
+                ui.label("No matching verbs.");
+            }
+            for index in matches {
+                let verb = &self.verbs[index];
+                if ui
+                    .link(format!("{} — {}", verb.infinitive, verb.english))
+                    .clicked()
+                {
+                    self.browse_index = index;
+                    self.browse_search.clear();
+                }
+            }
+        }
+
+        ui.add_space(20.0);
+
+        let verb = &self.verbs[self.browse_index];
+        egui::Frame::new()
+            .fill(self.panel_color())
+            .stroke(egui::Stroke::new(1.0, accent_color))
+            .corner_radius(8.0)
+            .inner_margin(24.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.vertical_centered(|ui| {
+                    ui.label(
+                        egui::RichText::new(&verb.infinitive)
+                            .font(heading_font.clone())
+                            .color(accent_color)
+                            .strong(),
+                    );
+                    ui.label(egui::RichText::new(&verb.english).font(body_font.clone()));
+                });
+
+                ui.add_space(16.0);
+
+                egui::Grid::new("browse_card")
+                    .num_columns(2)
+                    .spacing([24.0, 8.0])
+                    .show(ui, |ui| {
+                        for form in crate::ConjugationForm::ALL {
+                            if !verb.has_form(form) {
+                                continue;
+                            }
+                            let value = verb.form(form);
+                            ui.label(
+                                egui::RichText::new(self.deck_info.form_name(form))
+                                    .font(body_font.clone()),
+                            );
+                            ui.label(
+                                egui::RichText::new(grading::format_variants(value))
+                                    .font(body_font.clone())
+                                    .strong(),
+                            );
+                            ui.end_row();
+                        }
+                    });
+
+                if !verb.ipa.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label(
+                        egui::RichText::new(format!("[{}]", verb.ipa))
+                            .text_style(egui::TextStyle::Monospace),
+                    );
+                }
+            });
+
+        ui.add_space(20.0);
+
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 20.0;
+
+            let previous = ui.add_sized(
+                [150.0, 50.0],
+                egui::Button::new(
+                    egui::RichText::new("Previous")
+                        .font(body_font.clone())
+                        .color(egui::Color32::WHITE),
+                )
+                .fill(accent_color)
+                .corner_radius(8.0),
+            );
+            if previous.clicked() {
+                self.browse_index = self
+                    .browse_index
+                    .checked_sub(1)
+                    .unwrap_or(self.verbs.len() - 1);
+            }
+
+            let next = ui.add_sized(
+                [150.0, 50.0],
+                egui::Button::new(
+                    egui::RichText::new("Next")
+                        .font(body_font.clone())
+                        .color(egui::Color32::WHITE),
+                )
+                .fill(accent_color)
+                .corner_radius(8.0),
+            );
+            if next.clicked() {
+                self.browse_index = (self.browse_index + 1) % self.verbs.len();
+            }
+
+            ui.label(format!("{} / {}", self.browse_index + 1, self.verbs.len()));
+
+            // Verbs of a temporary session have no deck file to save to
+            if ui
+                .add_enabled(self.saved_deck.is_none(), egui::Button::new("✏ Edit"))
+                .on_hover_text("Fix or delete this verb in the deck")
+                .clicked()
+            {
+                self.edit_verb(self.browse_index);
+            }
+        });
+
+        ui.add_space(20.0);
+        self.show_deck_order(ui, accent_color);
+        self.show_verb_editor(ui, accent_color);
+        self.show_deck_merge(ui);
+    }
+
+    /// The verbs in deck file order, rearranged by dragging them.
+    fn show_deck_order(&mut self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        let mut moved = None;
+
+        egui::CollapsingHeader::new("Deck order")
+            .id_salt("deck_order")
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "Drag a verb to move it. \"Preserve deck order\" asks them in this order.",
+                    )
+                    .small(),
+                );
+                let order = Ref::map(self.deck_cache(), |cache| cache.by_position.as_slice());
+                let row_height = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+                egui::ScrollArea::vertical().max_height(300.0).show_rows(
+                    ui,
+                    row_height,
+                    order.len(),
+                    |ui, visible| {
+                        for &index in &order[visible] {
+                            let verb = &self.verbs[index];
+                            let position = verb.position;
+                            let response = ui
+                                .dnd_drag_source(
+                                    egui::Id::new(("deck_order", position)),
+                                    position,
+                                    |ui| {
+                                        ui.label(format!(
+                                            "☰ {}. {} — {}",
+                                            position + 1,
+                                            verb.infinitive,
+                                            verb.english
+                                        ));
+                                    },
+                                )
+                                .response;
+                            // Show where the verb being dragged will go
+                            if response.dnd_hover_payload::<usize>().is_some() {
+                                ui.painter().hline(
+                                    response.rect.x_range(),
+                                    response.rect.top(),
+                                    egui::Stroke::new(2.0, accent_color),
+                                );
+                            }
+                            if let Some(from) = response.dnd_release_payload::<usize>() {
+                                moved = Some((*from, position));
+                            }
+                        }
+                    },
+                );
+                drop(order);
+
+                ui.horizontal(|ui| {
+                    // Verbs of a temporary session have no deck file to save to
+                    if ui
+                        .add_enabled(self.saved_deck.is_none(), egui::Button::new("Save order"))
+                        .on_hover_text("Write the verbs back to the deck file in this order")
+                        .clicked()
+                    {
+                        self.save_deck_order();
+                    }
+                    if let Some(order_message) = &self.order_message {
+                        ui.label(egui::RichText::new(order_message).small());
+                    }
+                });
+            });
+
+        if let Some((from, to)) = moved
+            && from != to
+        {
+            self.move_verb(from, to);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::conjugator::Group;
+use crate::stats::MasteryRule;
+use crate::{DanishVerbsApp, Verb, VerbSort, confusion, grading};
+use std::cell::Ref;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+/// What the cached figures depend on. Any change means they're worked out again.
+#[derive(Debug)]
+pub struct CacheKey {
+    deck_revision: u64,
+    disabled: HashSet<String>,
+    learned: HashSet<String>,
+    mastery: MasteryRule,
+    range: Option<RangeInclusive<usize>>,
+    group: Option<Group>,
+    /// Every answer, or answer taken back, also moves the verb's review schedule
+    stats_revision: u64,
+}
+
+/// Figures that take a pass over the whole deck, kept between frames so large decks
+/// don't cost a full scan every time the window is drawn.
+#[derive(Debug, Default)]
+pub struct DeckCache {
+    key: Option<CacheKey>,
+    pub active: usize,
+    pub unseen: usize,
+    /// Active verbs that meet the mastery rule or are marked as learned
+    pub mastered: usize,
+    /// When each scheduled active verb is due, soonest first, so the number due at any
+    /// time is a search rather than a scan
+    pub due_times: Vec<u64>,
+    pub confusable_groups: Vec<Vec<String>>,
+    /// Indices of the verbs in deck file order
+    pub by_position: Vec<usize>,
+    /// Indices of the active verbs in the order of the statistics table or the verb list
+    table: Option<(VerbSort, Vec<usize>)>,
+}
+
+impl DanishVerbsApp {
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            deck_revision: self.deck_revision,
+            disabled: self.disabled_verbs.clone(),
+            learned: self.learned_verbs.clone(),
+            mastery: self.settings.mastery,
+            range: self.deck_range(),
+            group: self.settings.only_group,
+            stats_revision: self.stats.revision(),
+        }
+    }
+
+    /// Whether the figures cached under `key` still hold, compared without copying the sets.
+    fn is_cache_current(&self, key: &CacheKey) -> bool {
+        key.deck_revision == self.deck_revision
+            && key.stats_revision == self.stats.revision()
+            && key.mastery == self.settings.mastery
+            && key.range == self.deck_range()
+            && key.group == self.settings.only_group
+            && key.disabled == self.disabled_verbs
+            && key.learned == self.learned_verbs
+    }
+
+    /// The cached deck figures, worked out again first if anything they depend on changed.
+    pub(crate) fn deck_cache(&self) -> Ref<'_, DeckCache> {
+        let current = self
+            .deck_cache
+            .borrow()
+            .key
+            .as_ref()
+            .is_some_and(|key| self.is_cache_current(key));
+        if !current {
+            let key = self.cache_key();
+            let active: Vec<&Verb> = self
+                .verbs
+                .iter()
+                .filter(|verb| self.is_active(verb))
+                .collect();
+            let unseen = active.iter().filter(|verb| self.is_new(verb)).count();
+            let mastered = active
+                .iter()
+                .filter(|verb| {
+                    self.learned_verbs.contains(&verb.infinitive)
+                        || self
+                            .stats
+                            .is_mastered(&verb.infinitive, &self.settings.mastery)
+                })
+                .count();
+            let mut due_times: Vec<u64> = active
+                .iter()
+                .filter_map(|verb| self.schedule.get(&verb.infinitive))
+                .map(|review| review.due)
+                .collect();
+            due_times.sort_unstable();
+            let mut by_position: Vec<usize> = (0..self.verbs.len()).collect();
+            by_position.sort_by_key(|&index| self.verbs[index].position);
+
+            *self.deck_cache.borrow_mut() = DeckCache {
+                key: Some(key),
+                active: active.len(),
+                unseen,
+                mastered,
+                due_times,
+                confusable_groups: confusion::confusable_groups(&active),
+                by_position,
+                table: None,
+            };
+        }
+        self.deck_cache.borrow()
+    }
+
+    /// Indices of the active verbs sorted by `sort`.
+    pub(crate) fn verb_table_order(&self, sort: VerbSort) -> Ref<'_, [usize]> {
+        let sorted = self
+            .deck_cache()
+            .table
+            .as_ref()
+            .is_some_and(|(table_sort, _)| *table_sort == sort);
+        if !sorted {
+            let mut rows: Vec<usize> = (0..self.verbs.len())
+                .filter(|&index| self.is_active(&self.verbs[index]))
+                .collect();
+            let stat = |index: usize| self.stats.verbs.get(&self.verbs[index].infinitive);
+            let accuracy = |index: usize| {
+                stat(index)
+                    .filter(|verb_stat| verb_stat.attempts > 0)
+                    .map_or(-1.0, |verb_stat| {
+                        verb_stat.correct as f32 / verb_stat.attempts as f32
+                    })
+            };
+            match sort {
+                VerbSort::Name => {
+                    rows.sort_by(|&a, &b| self.verbs[a].infinitive.cmp(&self.verbs[b].infinitive))
+                }
+                VerbSort::English => rows.sort_by_key(|&index| {
+                    grading::normalize(self.verbs[index].english.trim_start_matches("to "))
+                }),
+                VerbSort::Accuracy => rows.sort_by(|&a, &b| accuracy(a).total_cmp(&accuracy(b))),
+                VerbSort::LastSeen => {
+                    rows.sort_by_key(|&index| stat(index).and_then(|v| v.last_seen))
+                }
+            }
+            self.deck_cache.borrow_mut().table = Some((sort, rows));
+        }
+        Ref::map(self.deck_cache.borrow(), |cache| {
+            cache
+                .table
+                .as_ref()
+                .map_or(&[][..], |(_, rows)| rows.as_slice())
+        })
+    }
+}
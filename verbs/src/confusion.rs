@@ -0,0 +1,195 @@
+use crate::grading;
+use crate::{ConjugationForm, Verb};
+use std::collections::{BTreeSet, HashMap};
+
+/// The part of a verb an answer is compared against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnswerField {
+    English,
+    Infinitive,
+    Form(ConjugationForm),
+}
+
+impl AnswerField {
+    fn values(self, verb: &Verb) -> Vec<&str> {
+        match self {
+            AnswerField::English => grading::meaning_variants(&verb.english),
+            AnswerField::Infinitive => vec![verb.infinitive.as_str()],
+            AnswerField::Form(form) => grading::variants(verb.form(form)),
+        }
+    }
+}
+
+const FIELDS: [AnswerField; 7] = [
+    AnswerField::English,
+    AnswerField::Infinitive,
+    AnswerField::Form(ConjugationForm::Present),
+    AnswerField::Form(ConjugationForm::Past),
+    AnswerField::Form(ConjugationForm::PastParticiple),
+    AnswerField::Form(ConjugationForm::Imperative),
+    AnswerField::Form(ConjugationForm::PresentParticiple),
+];
+
+/// Finds the verb a wrong answer belongs to, such as "at ligge" when asked for "at lægge".
+///
+/// Built once per deck so grading only does a hash lookup.
+#[derive(Debug, Default)]
+pub struct ConfusionIndex {
+    /// Infinitives of the verbs with each answer, keyed by field and normalized answer
+    verbs: HashMap<(AnswerField, String), Vec<String>>,
+}
+
+impl ConfusionIndex {
+    pub fn new(verbs: &[Verb]) -> Self {
+        let mut index: HashMap<(AnswerField, String), Vec<String>> = HashMap::new();
+        for verb in verbs {
+            for field in FIELDS {
+                for value in field.values(verb) {
+                    let infinitives = index.entry((field, key(field, value))).or_default();
+                    if !infinitives.contains(&verb.infinitive) {
+                        infinitives.push(verb.infinitive.clone());
+                    }
+                }
+            }
+        }
+        Self { verbs: index }
+    }
+
+    /// The infinitive of another verb whose `field` is exactly `answer`.
+    pub fn other_verb(&self, field: AnswerField, answer: &str, infinitive: &str) -> Option<&str> {
+        self.verbs
+            .get(&(field, key(field, answer)))?
+            .iter()
+            .find(|other| *other != infinitive)
+            .map(String::as_str)
+    }
+}
+
+/// Verbs that are declared confusable, directly or through each other, as groups of
+/// at least two infinitives in alphabetical order.
+///
+/// Confusables that aren't in `verbs` are left out.
+pub fn confusable_groups(verbs: &[&Verb]) -> Vec<Vec<String>> {
+    let mut groups: Vec<BTreeSet<&str>> = Vec::new();
+    for verb in verbs {
+        let mut group: BTreeSet<&str> = verb
+            .confusables
+            .iter()
+            .filter(|other| verbs.iter().any(|verb| verb.infinitive == **other))
+            .map(String::as_str)
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+        group.insert(&verb.infinitive);
+        // Merge every group this one shares a verb with
+        groups.retain(|existing| {
+            if existing.is_disjoint(&group) {
+                true
+            } else {
+                group.extend(existing);
+                false
+            }
+        });
+        groups.push(group);
+    }
+
+    let mut groups: Vec<Vec<String>> = groups
+        .into_iter()
+        .map(|group| group.into_iter().map(str::to_string).collect())
+        .collect();
+    groups.sort();
+    groups
+}
+
+/// Answers are compared without case, and without the infinitive markers "at" and "to".
+fn key(field: AnswerField, text: &str) -> String {
+    let text = grading::normalize(text);
+    let marker = match field {
+        AnswerField::English => "to ",
+        AnswerField::Infinitive => "at ",
+        AnswerField::Form(_) => return text,
+    };
+    match text.strip_prefix(marker) {
+        Some(rest) => rest.trim_start().to_string(),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::AnswerPatterns;
+
+    fn verb(infinitive: &str, present: &str, past: &str, english: &str) -> Verb {
+        Verb {
+            infinitive: infinitive.to_string(),
+            present: present.to_string(),
+            past: past.to_string(),
+            past_participle: String::new(),
+            imperative: String::new(),
+            present_participle: String::new(),
+            auxiliary: String::new(),
+            english: english.to_string(),
+            ipa: String::new(),
+            present_phrase: String::new(),
+            note: String::new(),
+            frequency: 0.0,
+            confusables: Vec::new(),
+            answer_pattern: AnswerPatterns::default(),
+            group: None,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn finds_the_verb_an_answer_belongs_to() {
+        let verbs = [
+            verb("at lægge", "lægger", "lagde", "to lay"),
+            verb("at ligge", "ligger", "lå", "to lie"),
+        ];
+        let index = ConfusionIndex::new(&verbs);
+
+        assert_eq!(
+            index.other_verb(AnswerField::English, "Lie", "at lægge"),
+            Some("at ligge")
+        );
+        assert_eq!(
+            index.other_verb(AnswerField::Infinitive, "ligge", "at lægge"),
+            Some("at ligge")
+        );
+        assert_eq!(
+            index.other_verb(AnswerField::Form(ConjugationForm::Past), "lå", "at lægge"),
+            Some("at ligge")
+        );
+        // The right verb's own answer, or an answer in another field, isn't a mix-up
+        assert_eq!(
+            index.other_verb(AnswerField::English, "to lay", "at lægge"),
+            None
+        );
+        assert_eq!(
+            index.other_verb(
+                AnswerField::Form(ConjugationForm::Present),
+                "lå",
+                "at lægge"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn groups_confusables_declared_on_either_verb() {
+        let mut lægge = verb("at lægge", "lægger", "lagde", "to lay");
+        lægge.confusables = vec!["at ligge".to_string(), "at mangle".to_string()];
+        let ligge = verb("at ligge", "ligger", "lå", "to lie");
+        let mut sætte = verb("at sætte", "sætter", "satte", "to set");
+        let mut sidde = verb("at sidde", "sidder", "sad", "to sit");
+        sætte.confusables = vec!["at sidde".to_string()];
+        sidde.confusables = vec!["at sætte".to_string()];
+        let være = verb("at være", "er", "var", "to be");
+
+        let groups = confusable_groups(&[&lægge, &ligge, &sætte, &sidde, &være]);
+        // "at mangle" isn't in the deck, so it's left out
+        assert_eq!(groups, [["at ligge", "at lægge"], ["at sidde", "at sætte"]]);
+    }
+}
@@ -0,0 +1,277 @@
+use crate::editor::infinitive_key;
+use crate::{Verb, grading};
+use serde::{Deserialize, Serialize};
+
+/// Danish modal verbs, which are irregular in their own way and are often practiced apart
+const MODAL_VERBS: [&str; 7] = [
+    "kunne", "skulle", "ville", "måtte", "burde", "turde", "gide",
+];
+
+/// The kind of conjugation a verb has: one of the two regular groups, named by their past
+/// tense endings, or irregular or modal. A deck gives it as `"group"` for verbs the rules
+/// would put in the wrong one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Group {
+    /// Past in -ede, past participle in -et, e.g. "lave, lavede, lavet"
+    #[serde(rename = "1")]
+    First,
+    /// Past in -te, past participle in -t, e.g. "spise, spiste, spist"
+    #[serde(rename = "2")]
+    Second,
+    #[serde(rename = "irregular")]
+    Irregular,
+    #[serde(rename = "modal")]
+    Modal,
+}
+
+impl Group {
+    pub const ALL: [Group; 4] = [Group::First, Group::Second, Group::Irregular, Group::Modal];
+    /// The groups with rules to conjugate by
+    pub const REGULAR: [Group; 2] = [Group::First, Group::Second];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Group::First => "group 1 (-ede, -et)",
+            Group::Second => "group 2 (-te, -t)",
+            Group::Irregular => "irregular",
+            Group::Modal => "modal",
+        }
+    }
+}
+
+/// A verb's group as the deck gives it, or else as its forms show: modal verbs by their
+/// infinitive, regular ones by following the rules, and irregular otherwise.
+pub fn classify(verb: &Verb) -> Group {
+    if let Some(group) = verb.group {
+        return group;
+    }
+    if MODAL_VERBS.contains(&infinitive_key(&verb.infinitive).as_str()) {
+        return Group::Modal;
+    }
+    group_of(verb).unwrap_or(Group::Irregular)
+}
+
+/// The forms of a regular verb.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forms {
+    pub present: String,
+    pub past: String,
+    pub past_participle: String,
+    pub imperative: String,
+    pub present_participle: String,
+}
+
+/// Conjugates a regular verb from its infinitive, with or without the leading "at".
+///
+/// The present adds -r to the infinitive. The past and past participle add their endings to
+/// the stem, which is the infinitive without its final e. A stem ending in a doubled
+/// consonant keeps just one before a consonant ending, as in "glemme, glemte". Irregular
+/// and modal verbs have no rule, so there are no forms for them.
+pub fn conjugate(infinitive: &str, group: Group) -> Option<Forms> {
+    let infinitive = infinitive.trim();
+    let infinitive = infinitive.strip_prefix("at ").unwrap_or(infinitive).trim();
+    let stem = infinitive.strip_suffix('e').unwrap_or(infinitive);
+    let (past, past_participle) = match group {
+        Group::First => (format!("{stem}ede"), format!("{stem}et")),
+        Group::Second => {
+            let stem = single_final_consonant(stem);
+            (format!("{stem}te"), format!("{stem}t"))
+        }
+        Group::Irregular | Group::Modal => return None,
+    };
+    Some(Forms {
+        present: format!("{infinitive}r"),
+        past,
+        past_participle,
+        imperative: imperative(infinitive),
+        present_participle: present_participle(infinitive),
+    })
+}
+
+/// The imperative, which is the infinitive without its final e, as in "spis!". Short
+/// infinitives that are all stem keep it ("se!"), and a doubled consonant is left single
+/// ("kom!"). Irregular verbs follow this rule too.
+pub fn imperative(infinitive: &str) -> String {
+    let infinitive = infinitive.trim();
+    let infinitive = infinitive.strip_prefix("at ").unwrap_or(infinitive).trim();
+    match infinitive.strip_suffix('e') {
+        Some(stem) if ends_in_consonant(stem) && stem.contains(is_vowel) => {
+            single_final_consonant(stem).to_string()
+        }
+        _ => infinitive.to_string(),
+    }
+}
+
+/// The present participle, which adds -nde to an infinitive in -e and -ende to any other,
+/// as in "spisende", "gående" and "seende".
+pub fn present_participle(infinitive: &str) -> String {
+    let infinitive = infinitive.trim();
+    let infinitive = infinitive.strip_prefix("at ").unwrap_or(infinitive).trim();
+    if imperative(infinitive) == infinitive {
+        format!("{infinitive}ende")
+    } else {
+        format!("{infinitive}nde")
+    }
+}
+
+fn is_vowel(letter: char) -> bool {
+    "aeiouyæøå".contains(letter)
+}
+
+fn ends_in_consonant(stem: &str) -> bool {
+    stem.chars().last().is_some_and(|last| !is_vowel(last))
+}
+
+/// Drops the last letter of a stem that ends in the same consonant twice.
+fn single_final_consonant(stem: &str) -> &str {
+    let mut letters = stem.chars().rev();
+    match (letters.next(), letters.next()) {
+        (Some(last), Some(before)) if last == before && !is_vowel(last) => {
+            &stem[..stem.len() - last.len_utf8()]
+        }
+        _ => stem,
+    }
+}
+
+/// The regular group a verb's forms follow, or none for an irregular verb.
+///
+/// A form with variants counts as regular when its first variant is.
+pub fn group_of(verb: &Verb) -> Option<Group> {
+    let first = |field: &str| grading::variants(field).first().map(|v| v.to_lowercase());
+    let stored = (
+        first(&verb.present),
+        first(&verb.past),
+        first(&verb.past_participle),
+    );
+    Group::REGULAR.into_iter().find(|&group| {
+        conjugate(&verb.infinitive.to_lowercase(), group).is_some_and(|forms| {
+            stored
+                == (
+                    Some(forms.present),
+                    Some(forms.past),
+                    Some(forms.past_participle),
+                )
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conjugates_both_groups() {
+        let lave = conjugate("at lave", Group::First).unwrap();
+        assert_eq!(
+            (lave.present, lave.past, lave.past_participle),
+            ("laver".into(), "lavede".into(), "lavet".into())
+        );
+        let bo = conjugate("bo", Group::First).unwrap();
+        assert_eq!(
+            (bo.past, bo.past_participle),
+            ("boede".into(), "boet".into())
+        );
+        let spise = conjugate("at spise", Group::Second).unwrap();
+        assert_eq!(
+            (spise.present, spise.past, spise.past_participle),
+            ("spiser".into(), "spiste".into(), "spist".into())
+        );
+        let glemme = conjugate("at glemme", Group::Second).unwrap();
+        assert_eq!(
+            (glemme.past, glemme.past_participle),
+            ("glemte".into(), "glemt".into())
+        );
+    }
+
+    #[test]
+    fn forms_the_imperative_and_present_participle() {
+        let forms = |infinitive| (imperative(infinitive), present_participle(infinitive));
+        assert_eq!(forms("at spise"), ("spis".into(), "spisende".into()));
+        assert_eq!(forms("komme"), ("kom".into(), "kommende".into()));
+        assert_eq!(forms("at gå"), ("gå".into(), "gående".into()));
+        assert_eq!(forms("at se"), ("se".into(), "seende".into()));
+        assert_eq!(forms("at åbne"), ("åbn".into(), "åbnende".into()));
+
+        // The built-in deck only differs where it adds a spelling
+        let deck = crate::deck::parse_deck(crate::BUILT_IN_DECK).unwrap();
+        for verb in &deck.verbs {
+            if let Some(&first) = grading::variants(&verb.imperative).first() {
+                assert_eq!(first, imperative(&verb.infinitive), "{}", verb.infinitive);
+            }
+            if !verb.present_participle.is_empty() {
+                assert_eq!(
+                    verb.present_participle,
+                    present_participle(&verb.infinitive),
+                    "{}",
+                    verb.infinitive
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sorts_the_built_in_verbs_into_groups() {
+        let deck = crate::deck::parse_deck(crate::BUILT_IN_DECK).unwrap();
+        let group = |infinitive: &str| {
+            deck.verbs
+                .iter()
+                .find(|verb| verb.infinitive == infinitive)
+                .and_then(group_of)
+        };
+        assert_eq!(group("at arbejde"), Some(Group::First));
+        assert_eq!(group("at bo"), Some(Group::First));
+        assert_eq!(group("at spise"), Some(Group::Second));
+        // The doubled consonant is left single before -te, as in "glemte"
+        assert_eq!(group("at glemme"), Some(Group::Second));
+        assert_eq!(group("at bestille"), Some(Group::Second));
+
+        // The built-in verbs the rules can't conjugate, which are all irregular
+        let irregular: Vec<&str> = deck
+            .verbs
+            .iter()
+            .filter(|verb| group_of(verb).is_none())
+            .map(|verb| verb.infinitive.as_str())
+            .collect();
+        assert_eq!(
+            irregular.join(", "),
+            "at være, at have, at gå, at drikke, at skrive, at se, at sælge, at give, \
+             at tage, at finde, at forstå, at bede, at blive, at flyve, at fortælle, \
+             at hjælpe, at komme, at kunne, at ligge, at løbe, at sidde, at sige, at sove, \
+             at spørge, at synge, at vide, at vinde, at lykkes, at lide, at bryde, at le, \
+             at undgå"
+        );
+        let count = |wanted| {
+            deck.verbs
+                .iter()
+                .filter(|verb| group_of(verb) == Some(wanted))
+                .count()
+        };
+        assert_eq!((count(Group::First), count(Group::Second)), (30, 25));
+    }
+
+    #[test]
+    fn classifies_verbs_unless_the_deck_says() {
+        let deck = crate::deck::parse_deck(crate::BUILT_IN_DECK).unwrap();
+        let group = |infinitive: &str| {
+            deck.verbs
+                .iter()
+                .find(|verb| verb.infinitive == infinitive)
+                .map(classify)
+        };
+        assert_eq!(group("at kunne"), Some(Group::Modal));
+        assert_eq!(group("at spise"), Some(Group::Second));
+        assert_eq!(group("at være"), Some(Group::Irregular));
+
+        let mut verb: Verb = serde_json::from_value(serde_json::json!({
+            "infinitive": "at lave",
+            "present": "laver",
+            "past": "lavede",
+            "past_participle": "lavet",
+            "english": "to make",
+        }))
+        .unwrap();
+        assert_eq!(classify(&verb), Group::First);
+        verb.group = serde_json::from_value(serde_json::json!("irregular")).unwrap();
+        assert_eq!(classify(&verb), Group::Irregular);
+    }
+}
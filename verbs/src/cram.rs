@@ -0,0 +1,84 @@
+use crate::stats::Stats;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Pause between grading and moving on to the next verb while cramming
+pub const ADVANCE_DELAY: Duration = Duration::from_millis(1200);
+
+/// A small set of verbs cycled rapidly until every one is answered right in a round.
+#[derive(Debug)]
+pub struct CramSession {
+    /// Infinitives in the set
+    pub verbs: Vec<String>,
+    /// Verbs answered right in the current round
+    pub nailed: HashSet<String>,
+    pub round: u32,
+    /// When to move on automatically after the current answer
+    pub advance_at: Option<Instant>,
+    next: usize,
+}
+
+impl CramSession {
+    pub fn new(verbs: Vec<String>) -> Self {
+        Self {
+            verbs,
+            nailed: HashSet::new(),
+            round: 1,
+            advance_at: None,
+            next: 0,
+        }
+    }
+
+    /// The next verb not yet nailed this round, starting a new round once all are.
+    pub fn next_verb(&mut self) -> Option<&str> {
+        if self.verbs.is_empty() {
+            return None;
+        }
+        if self.nailed.len() >= self.verbs.len() {
+            self.nailed.clear();
+            self.round += 1;
+        }
+
+        for _ in 0..self.verbs.len() {
+            let index = self.next % self.verbs.len();
+            self.next = index + 1;
+            if !self.nailed.contains(&self.verbs[index]) {
+                return Some(&self.verbs[index]);
+            }
+        }
+        None
+    }
+
+    pub fn record(&mut self, infinitive: &str, correct: bool) {
+        if correct {
+            self.nailed.insert(infinitive.to_string());
+        }
+        self.advance_at = Some(Instant::now() + ADVANCE_DELAY);
+    }
+}
+
+/// Picks the `count` verbs with the lowest accuracy.
+///
+/// Accuracy is smoothed so unseen verbs rank as 50% rather than 0% or 100%.
+pub fn pick_weakest<'a>(
+    infinitives: impl Iterator<Item = &'a str>,
+    stats: &Stats,
+    count: usize,
+) -> Vec<String> {
+    let mut ranked: Vec<(f32, &str)> = infinitives
+        .map(|infinitive| {
+            let (correct, attempts) = stats
+                .verbs
+                .get(infinitive)
+                .map_or((0, 0), |verb_stat| (verb_stat.correct, verb_stat.attempts));
+            ((correct as f32 + 1.0) / (attempts as f32 + 2.0), infinitive)
+        })
+        .collect();
+    ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    ranked
+        .into_iter()
+        .take(count)
+        .map(|(_, infinitive)| infinitive.to_string())
+        .collect()
+}
@@ -0,0 +1,569 @@
+use crate::{ConjugationForm, Verb, state};
+use serde::Deserialize;
+use std::path::Path;
+use std::{fmt, io};
+
+/// Language and conjugation form names of a deck.
+#[derive(Debug, Clone)]
+pub struct DeckInfo {
+    pub language: String,
+    /// Names of the conjugation forms, in the order of `ConjugationForm::ALL`
+    pub form_names: Vec<String>,
+    /// Whether the file had a header, so it is written back the same way
+    pub has_header: bool,
+}
+
+impl Default for DeckInfo {
+    fn default() -> Self {
+        Self {
+            language: "Danish".to_string(),
+            form_names: ConjugationForm::ALL
+                .iter()
+                .map(|form| form.label().to_string())
+                .collect(),
+            has_header: false,
+        }
+    }
+}
+
+impl DeckInfo {
+    /// The deck's name for a form, falling back to the Danish one if it declares fewer forms.
+    pub fn form_name(&self, form: ConjugationForm) -> &str {
+        let index = ConjugationForm::ALL
+            .iter()
+            .position(|other| *other == form)
+            .unwrap_or(0);
+        self.form_names
+            .get(index)
+            .map_or(form.label(), String::as_str)
+    }
+}
+
+#[derive(Debug)]
+pub struct Deck {
+    pub info: DeckInfo,
+    pub verbs: Vec<Verb>,
+    /// Something the user should know about the file, even though it loaded
+    pub warning: Option<String>,
+}
+
+/// A deck with a header, e.g. `{"language": "German", "forms": [...], "verbs": [...]}`.
+#[derive(Deserialize)]
+struct DeckFile {
+    language: Option<String>,
+    #[serde(default)]
+    forms: Vec<String>,
+    verbs: Vec<Verb>,
+}
+
+/// Reads the English meanings of a verb, given as one string or as a list, e.g.
+/// `["to make", "to do"]`. A list is kept as its entries joined with " / ".
+pub fn deserialize_meanings<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Meanings {
+        One(String),
+        List(Vec<String>),
+    }
+    Ok(match Meanings::deserialize(deserializer)? {
+        Meanings::One(english) => english,
+        Meanings::List(list) => list.join(" / "),
+    })
+}
+
+/// A problem found while parsing a deck file, pointing at the offending entry and field.
+#[derive(Debug)]
+pub struct DeckError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    /// Zero-based index of the verb entry the error is in
+    pub entry: Option<usize>,
+    /// Infinitive of that entry, if it could be read
+    pub infinitive: Option<String>,
+    pub field: Option<String>,
+}
+
+impl fmt::Display for DeckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(entry) = self.entry {
+            write!(f, "verb #{}", entry + 1)?;
+            if let Some(infinitive) = &self.infinitive {
+                write!(f, " (\"{}\")", infinitive)?;
+            }
+            if let Some(field) = &self.field {
+                write!(f, ", field \"{}\"", field)?;
+            }
+            write!(f, ": ")?;
+        }
+        write!(
+            f,
+            "{} (line {}, column {})",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+/// Parses a deck, turning serde errors into messages that name the entry and field.
+///
+/// A deck is either a bare array of verbs, which is taken to be Danish, or an object
+/// with a header declaring the language and form names next to the verbs.
+pub fn parse_deck(data: &str) -> Result<Deck, DeckError> {
+    // Some editors start UTF-8 files with a byte order mark, which serde rejects
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+    let has_header = data.trim_start().starts_with('{');
+
+    let result = if has_header {
+        serde_json::from_str::<DeckFile>(data).map(|file| {
+            let mut info = DeckInfo {
+                has_header: true,
+                ..DeckInfo::default()
+            };
+            if let Some(language) = file.language {
+                info.language = language;
+            }
+            if !file.forms.is_empty() {
+                info.form_names = file.forms;
+            }
+            Deck {
+                info,
+                verbs: file.verbs,
+                warning: None,
+            }
+        })
+    } else {
+        serde_json::from_str::<Vec<Verb>>(data).map(|verbs| Deck {
+            info: DeckInfo::default(),
+            verbs,
+            warning: None,
+        })
+    };
+
+    result.map_err(|e| {
+        // Verb entries sit one level deeper when wrapped in a header object
+        let entry_depth = if has_header { 3 } else { 2 };
+        let offset = byte_offset(data, e.line(), e.column());
+        let location = locate(data, offset, entry_depth);
+        let message = strip_position(&e.to_string());
+
+        // serde names missing fields itself, for other errors use the key being parsed
+        let field = missing_field(&message).or(location.key);
+        let infinitive = location
+            .entry
+            .and_then(|entry| entry_infinitive(data, entry, has_header));
+
+        DeckError {
+            message,
+            line: e.line(),
+            column: e.column(),
+            entry: location.entry,
+            infinitive,
+            field,
+        }
+    })
+}
+
+/// Decodes the bytes of a deck file.
+///
+/// Files that aren't valid UTF-8 are assumed to be Windows-1252, which is what most
+/// editors on Windows save as. The second value is true when that fallback was used.
+pub fn decode_deck(bytes: Vec<u8>) -> (String, bool) {
+    match String::from_utf8(bytes) {
+        Ok(text) => (text, false),
+        Err(e) => (
+            e.as_bytes().iter().map(|&b| windows_1252(b)).collect(),
+            true,
+        ),
+    }
+}
+
+/// Windows-1252 matches Latin-1 except for the 0x80..=0x9F range.
+fn windows_1252(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž',
+        '\u{8f}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}',
+        'ž', 'Ÿ',
+    ];
+    match byte {
+        0x80..=0x9f => HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+/// Serializes verbs back into a deck, in the same layout they were read from.
+pub fn serialize_deck(info: &DeckInfo, verbs: &[&Verb]) -> serde_json::Result<String> {
+    if info.has_header {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "language": info.language,
+            "forms": info.form_names,
+            "verbs": verbs,
+        }))
+    } else {
+        serde_json::to_string_pretty(verbs)
+    }
+}
+
+/// Writes the verbs back to the deck file, in deck file order.
+///
+/// Like the state file, the deck is written atomically, so a failed save leaves the old
+/// deck whole.
+pub fn save_deck(path: &Path, info: &DeckInfo, verbs: &[Verb]) -> io::Result<()> {
+    let mut ordered: Vec<&Verb> = verbs.iter().collect();
+    ordered.sort_by_key(|verb| verb.position);
+    let data = serialize_deck(info, &ordered).map_err(io::Error::other)?;
+    state::write_atomically(path, &(data + "\n"))
+}
+
+/// Moves the verb at deck file position `from` to position `to`, shifting the verbs
+/// in between along by one.
+pub fn move_verb(verbs: &mut [Verb], from: usize, to: usize) {
+    for verb in verbs.iter_mut() {
+        verb.position = if verb.position == from {
+            to
+        } else if from < to && (from + 1..=to).contains(&verb.position) {
+            verb.position - 1
+        } else if to < from && (to..from).contains(&verb.position) {
+            verb.position + 1
+        } else {
+            verb.position
+        };
+    }
+}
+
+/// serde_json appends " at line X column Y" to its messages, which is reported separately.
+fn strip_position(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message.to_string(),
+    }
+}
+
+fn missing_field(message: &str) -> Option<String> {
+    let rest = message.strip_prefix("missing field `")?;
+    rest.split('`').next().map(str::to_string)
+}
+
+/// Converts serde_json's one-based line and column into a byte offset.
+fn byte_offset(data: &str, line: usize, column: usize) -> usize {
+    let line_start: usize = data
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    (line_start + column).min(data.len())
+}
+
+#[derive(Debug, Default)]
+struct Location {
+    entry: Option<usize>,
+    key: Option<String>,
+}
+
+/// Scans the JSON up to `offset` to find which verb entry and object key it falls in.
+///
+/// Verb entries are the objects opened at `entry_depth`.
+fn locate(data: &str, offset: usize, entry_depth: usize) -> Location {
+    let mut location = Location::default();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string_start = 0;
+    let mut last_string: Option<&str> = None;
+
+    for (index, ch) in data.char_indices() {
+        if index >= offset {
+            break;
+        }
+
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+                last_string = Some(&data[string_start..index]);
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => {
+                in_string = true;
+                string_start = index + 1;
+            }
+            ':' if depth == entry_depth => location.key = last_string.map(str::to_string),
+            '{' | '[' => {
+                depth += 1;
+                if ch == '{' && depth == entry_depth {
+                    location.entry = Some(location.entry.map_or(0, |entry| entry + 1));
+                    location.key = None;
+                }
+            }
+            '}' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    location
+}
+
+/// Reads the infinitive of an entry, if the file is at least valid JSON.
+fn entry_infinitive(data: &str, entry: usize, has_header: bool) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(data).ok()?;
+    let verbs = if has_header {
+        value.get("verbs")?
+    } else {
+        &value
+    };
+    verbs
+        .get(entry)?
+        .get("infinitive")?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_valid_deck() {
+        let data = r#"[
+  {
+    "infinitive": "at være",
+    "present": "er",
+    "past": "var",
+    "past_participle": "været",
+    "english": "to be"
+  }
+]"#;
+        let deck = parse_deck(data).unwrap();
+        assert_eq!(deck.verbs.len(), 1);
+        assert_eq!(deck.verbs[0].past, "var");
+        assert_eq!(deck.info.language, "Danish");
+    }
+
+    #[test]
+    fn parses_a_deck_with_a_byte_order_mark() {
+        let mut bytes = vec![0xef, 0xbb, 0xbf];
+        bytes.extend_from_slice(
+            r#"[{"infinitive": "at være", "present": "er", "past": "var", "past_participle": "været", "english": "to be"}]"#
+                .as_bytes(),
+        );
+        let (data, fallback) = decode_deck(bytes);
+        assert!(!fallback);
+        let deck = parse_deck(&data).unwrap();
+        assert_eq!(deck.verbs[0].infinitive, "at være");
+    }
+
+    #[test]
+    fn decodes_windows_1252_decks() {
+        // "at være" with æ as the single Windows-1252 byte 0xe6, and a curly quote
+        let mut bytes = br#"[{"infinitive": "at v"#.to_vec();
+        bytes.push(0xe6);
+        bytes.extend_from_slice(br#"re", "present": "er", "past": "var", "past_participle": "v"#);
+        bytes.push(0xe6);
+        bytes.extend_from_slice(br#"ret", "english": "to be "#);
+        bytes.extend_from_slice(&[0x93, b'x', 0x94]);
+        bytes.extend_from_slice(br#""}]"#);
+
+        let (data, fallback) = decode_deck(bytes);
+        assert!(fallback);
+        let deck = parse_deck(&data).unwrap();
+        assert_eq!(deck.verbs[0].infinitive, "at være");
+        assert_eq!(deck.verbs[0].english, "to be “x”");
+    }
+
+    #[test]
+    fn parses_a_deck_with_a_header() {
+        let data = r#"{
+  "language": "German",
+  "forms": ["Präsens", "Präteritum", "Partizip"],
+  "verbs": [
+    {
+      "infinitive": "sein",
+      "present": "ist",
+      "past": "war",
+      "past_participle": "gewesen",
+      "english": "to be"
+    }
+  ]
+}"#;
+        let deck = parse_deck(data).unwrap();
+        assert_eq!(deck.verbs.len(), 1);
+        assert_eq!(deck.info.language, "German");
+        assert_eq!(deck.info.form_name(ConjugationForm::Past), "Präteritum");
+    }
+
+    #[test]
+    fn writes_a_deck_back_in_the_same_layout() {
+        let data = r#"{
+  "language": "German",
+  "forms": ["Präsens", "Präteritum", "Partizip"],
+  "verbs": [
+    {
+      "infinitive": "sein",
+      "present": "ist",
+      "past": "war",
+      "past_participle": "gewesen",
+      "english": "to be",
+      "note": "irregular"
+    }
+  ]
+}"#;
+        let deck = parse_deck(data).unwrap();
+        let verbs: Vec<&Verb> = deck.verbs.iter().collect();
+        let written = serialize_deck(&deck.info, &verbs).unwrap();
+
+        // Empty optional fields are left out
+        assert!(!written.contains("\"ipa\""));
+        let reread = parse_deck(&written).unwrap();
+        assert_eq!(reread.info.language, "German");
+        assert_eq!(reread.info.form_names, deck.info.form_names);
+        assert_eq!(reread.verbs[0].note, "irregular");
+
+        let bare = DeckInfo::default();
+        assert!(serialize_deck(&bare, &verbs).unwrap().starts_with('['));
+    }
+
+    #[test]
+    fn reports_errors_inside_a_header_deck() {
+        let data = r#"{
+  "language": "German",
+  "verbs": [
+    {
+      "infinitive": "sein",
+      "present": "ist",
+      "past": true,
+      "past_participle": "gewesen",
+      "english": "to be"
+    }
+  ]
+}"#;
+        let error = parse_deck(data).unwrap_err();
+        assert_eq!(error.entry, Some(0));
+        assert_eq!(error.infinitive.as_deref(), Some("sein"));
+        assert_eq!(error.field.as_deref(), Some("past"));
+    }
+
+    #[test]
+    fn reports_missing_field() {
+        let data = r#"[
+  {
+    "infinitive": "at være",
+    "present": "er",
+    "past": "var",
+    "past_participle": "været",
+    "english": "to be"
+  },
+  {
+    "infinitive": "at have",
+    "present": "har",
+    "pastt": "havde",
+    "past_participle": "haft",
+    "english": "to have"
+  }
+]"#;
+        let error = parse_deck(data).unwrap_err();
+        assert_eq!(error.entry, Some(1));
+        assert_eq!(error.infinitive.as_deref(), Some("at have"));
+        assert_eq!(error.field.as_deref(), Some("past"));
+        assert_eq!(error.line, 15);
+        assert_eq!(
+            error.to_string(),
+            "verb #2 (\"at have\"), field \"past\": missing field `past` (line 15, column 3)"
+        );
+    }
+
+    #[test]
+    fn reports_wrong_type() {
+        let data = r#"[
+  {
+    "infinitive": "at gå",
+    "present": "går",
+    "past": 42,
+    "past_participle": "gået",
+    "english": "to go"
+  }
+]"#;
+        let error = parse_deck(data).unwrap_err();
+        assert_eq!(error.entry, Some(0));
+        assert_eq!(error.infinitive.as_deref(), Some("at gå"));
+        assert_eq!(error.field.as_deref(), Some("past"));
+        assert_eq!(error.line, 5);
+        assert!(error.message.starts_with("invalid type: integer `42`"));
+    }
+
+    #[test]
+    fn reports_invalid_answer_patterns() {
+        let data = r#"[
+  {
+    "infinitive": "at ligge",
+    "present": "ligger",
+    "past": "lå",
+    "past_participle": "ligget",
+    "english": "to lie",
+    "answer_pattern": {"english": "to (lie|be lying"}
+  }
+]"#;
+        let error = parse_deck(data).unwrap_err();
+        assert_eq!(error.entry, Some(0));
+        assert_eq!(error.field.as_deref(), Some("answer_pattern"));
+        assert!(
+            error
+                .message
+                .starts_with("invalid answer pattern \"to (lie|be lying\": unclosed group")
+        );
+    }
+
+    #[test]
+    fn reads_a_list_of_meanings() {
+        let deck = parse_deck(
+            r#"[{"infinitive": "at lave", "present": "laver", "past": "lavede", "past_participle": "lavet", "english": ["to make", "to do"]}]"#,
+        )
+        .unwrap();
+        assert_eq!(deck.verbs[0].english, "to make / to do");
+    }
+
+    #[test]
+    fn moves_a_verb_within_the_deck_order() {
+        let data = r#"[
+  {"infinitive": "at være", "present": "er", "past": "var", "past_participle": "været", "english": "to be"},
+  {"infinitive": "at have", "present": "har", "past": "havde", "past_participle": "haft", "english": "to have"},
+  {"infinitive": "at gå", "present": "går", "past": "gik", "past_participle": "gået", "english": "to go"},
+  {"infinitive": "at se", "present": "ser", "past": "så", "past_participle": "set", "english": "to see"}
+]"#;
+        let mut verbs = parse_deck(data).unwrap().verbs;
+        for (position, verb) in verbs.iter_mut().enumerate() {
+            verb.position = position;
+        }
+        let order = |verbs: &[Verb]| {
+            let mut ordered: Vec<&Verb> = verbs.iter().collect();
+            ordered.sort_by_key(|verb| verb.position);
+            ordered
+                .iter()
+                .map(|verb| verb.infinitive.clone())
+                .collect::<Vec<_>>()
+        };
+
+        move_verb(&mut verbs, 0, 2);
+        assert_eq!(order(&verbs), ["at have", "at gå", "at være", "at se"]);
+        move_verb(&mut verbs, 3, 0);
+        assert_eq!(order(&verbs), ["at se", "at have", "at gå", "at være"]);
+        move_verb(&mut verbs, 1, 1);
+        assert_eq!(order(&verbs), ["at se", "at have", "at gå", "at være"]);
+    }
+
+    #[test]
+    fn reports_syntax_errors_without_an_entry_name() {
+        let data = "[\n  {\n    \"infinitive\": \"at gå\",\n  }\n]";
+        let error = parse_deck(data).unwrap_err();
+        assert_eq!(error.entry, Some(0));
+        assert_eq!(error.infinitive, None);
+        assert_eq!(error.line, 4);
+    }
+}
@@ -0,0 +1,508 @@
+use crate::conjugator::{self, Group};
+use crate::pattern::AnswerPatterns;
+use crate::{ConjugationForm, DanishVerbsApp, Verb, confusion, deck, grading};
+use eframe::egui;
+use std::io;
+
+/// A verb being typed into the editor, before it's added to the deck or saved over a verb
+/// already in it.
+#[derive(Debug, Default, Clone)]
+pub struct VerbDraft {
+    pub infinitive: String,
+    pub present: String,
+    pub past: String,
+    pub past_participle: String,
+    /// Optional, as not every verb has one
+    pub imperative: String,
+    /// Optional, as not every verb has one
+    pub present_participle: String,
+    /// Auxiliary of the perfect tense, "har" when left empty
+    pub auxiliary: String,
+    pub english: String,
+    /// Infinitive of the deck verb being edited, or none for a new verb
+    pub editing: Option<String>,
+}
+
+impl VerbDraft {
+    /// A draft to edit the fields of a verb in the deck.
+    pub fn from_verb(verb: &Verb) -> Self {
+        Self {
+            infinitive: verb.infinitive.clone(),
+            present: verb.present.clone(),
+            past: verb.past.clone(),
+            past_participle: verb.past_participle.clone(),
+            imperative: verb.imperative.clone(),
+            present_participle: verb.present_participle.clone(),
+            auxiliary: verb.auxiliary.clone(),
+            english: verb.english.clone(),
+            editing: Some(verb.infinitive.clone()),
+        }
+    }
+
+    /// Names of the fields that are still empty, in the order they're shown.
+    pub fn missing(&self) -> Vec<&'static str> {
+        [
+            ("infinitive", &self.infinitive),
+            ("present", &self.present),
+            ("past", &self.past),
+            ("past participle", &self.past_participle),
+            ("English", &self.english),
+        ]
+        .into_iter()
+        .filter(|(_, value)| value.trim().is_empty())
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    /// The verb in the deck with the same infinitive, ignoring case and a leading "at". The
+    /// verb being edited doesn't count.
+    pub fn duplicate_of<'a>(&self, verbs: &'a [Verb]) -> Option<&'a Verb> {
+        let infinitive = infinitive_key(&self.infinitive);
+        if infinitive.is_empty() {
+            return None;
+        }
+        verbs.iter().find(|verb| {
+            infinitive_key(&verb.infinitive) == infinitive
+                && self.editing.as_ref() != Some(&verb.infinitive)
+        })
+    }
+
+    /// Whether the draft can be added without leaving a blank or ambiguous question.
+    pub fn is_valid(&self, verbs: &[Verb]) -> bool {
+        self.missing().is_empty() && self.duplicate_of(verbs).is_none()
+    }
+
+    /// Fills in the forms of a regular verb of `group` from the infinitive.
+    pub fn conjugate(&mut self, group: Group) {
+        let Some(forms) = conjugator::conjugate(&self.infinitive, group) else {
+            return;
+        };
+        self.present = forms.present;
+        self.past = forms.past;
+        self.past_participle = forms.past_participle;
+        self.imperative = forms.imperative;
+        self.present_participle = forms.present_participle;
+    }
+
+    /// Copies the draft's fields over the verb's, leaving the ones the editor doesn't show.
+    fn apply_to(&self, verb: &mut Verb) {
+        verb.infinitive = self.infinitive.trim().to_string();
+        verb.present = self.present.trim().to_string();
+        verb.past = self.past.trim().to_string();
+        verb.past_participle = self.past_participle.trim().to_string();
+        verb.imperative = self.imperative.trim().to_string();
+        verb.present_participle = self.present_participle.trim().to_string();
+        verb.auxiliary = self.auxiliary.trim().to_string();
+        verb.english = self.english.trim().to_string();
+    }
+
+    fn to_verb(&self, position: usize) -> Verb {
+        Verb {
+            infinitive: self.infinitive.trim().to_string(),
+            present: self.present.trim().to_string(),
+            past: self.past.trim().to_string(),
+            past_participle: self.past_participle.trim().to_string(),
+            imperative: self.imperative.trim().to_string(),
+            present_participle: self.present_participle.trim().to_string(),
+            auxiliary: self.auxiliary.trim().to_string(),
+            english: self.english.trim().to_string(),
+            ipa: String::new(),
+            present_phrase: String::new(),
+            note: String::new(),
+            frequency: 0.0,
+            confusables: Vec::new(),
+            answer_pattern: AnswerPatterns::default(),
+            group: None,
+            position,
+        }
+    }
+}
+
+/// An infinitive as compared for duplicates, ignoring case and a leading "at".
+pub(crate) fn infinitive_key(infinitive: &str) -> String {
+    let infinitive = grading::normalize(infinitive);
+    match infinitive.strip_prefix("at ") {
+        Some(rest) => rest.trim_start().to_string(),
+        None => infinitive,
+    }
+}
+
+impl DanishVerbsApp {
+    /// Form to add a verb to the deck or edit one in it, which can only be saved once every
+    /// field is filled in and the infinitive isn't taken by another verb.
+    pub(crate) fn show_verb_editor(&mut self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        let mut save = false;
+        let mut cancel = false;
+        let mut delete = false;
+
+        let title = match &self.verb_draft.editing {
+            Some(infinitive) => format!("Edit '{}'", infinitive),
+            None => "Add a verb".to_string(),
+        };
+        egui::CollapsingHeader::new(title)
+            .id_salt("verb_editor")
+            .open(std::mem::take(&mut self.open_verb_editor).then_some(true))
+            .show(ui, |ui| {
+                let error = self.theme.wrong;
+                let labels = [
+                    "Infinitive".to_string(),
+                    self.deck_info
+                        .form_name(ConjugationForm::Present)
+                        .to_string(),
+                    self.deck_info.form_name(ConjugationForm::Past).to_string(),
+                    self.deck_info
+                        .form_name(ConjugationForm::PastParticiple)
+                        .to_string(),
+                    self.deck_info
+                        .form_name(ConjugationForm::Imperative)
+                        .to_string(),
+                    self.deck_info
+                        .form_name(ConjugationForm::PresentParticiple)
+                        .to_string(),
+                    "Perfect with".to_string(),
+                    "English".to_string(),
+                ];
+                let hints = [
+                    "at være", "er", "var", "været", "vær", "værende", "har", "to be",
+                ];
+                let optional = [false, false, false, false, true, true, true, false];
+
+                egui::Grid::new("verb_editor_fields")
+                    .num_columns(2)
+                    .spacing([16.0, 6.0])
+                    .show(ui, |ui| {
+                        let draft = &mut self.verb_draft;
+                        let fields = [
+                            &mut draft.infinitive,
+                            &mut draft.present,
+                            &mut draft.past,
+                            &mut draft.past_participle,
+                            &mut draft.imperative,
+                            &mut draft.present_participle,
+                            &mut draft.auxiliary,
+                            &mut draft.english,
+                        ];
+                        for (((label, hint), optional), value) in
+                            labels.iter().zip(hints).zip(optional).zip(fields)
+                        {
+                            ui.label(label);
+                            // Empty fields are marked, as the verb can't be saved without them
+                            let empty = !optional && value.trim().is_empty();
+                            let mut field = egui::TextEdit::singleline(value).hint_text(hint);
+                            if empty {
+                                field = field.background_color(error.gamma_multiply(0.25));
+                            }
+                            ui.add(field);
+                            ui.end_row();
+                        }
+                    });
+
+                // Regular verbs only need their infinitive, the forms follow from it
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Fill in the forms of");
+                    let has_infinitive = !self.verb_draft.infinitive.trim().is_empty();
+                    for group in Group::REGULAR {
+                        if ui
+                            .add_enabled(has_infinitive, egui::Button::new(group.label()).small())
+                            .on_disabled_hover_text("Type the infinitive first")
+                            .clicked()
+                        {
+                            self.verb_draft.conjugate(group);
+                        }
+                    }
+                });
+                let draft = &self.verb_draft;
+                if [&draft.present, &draft.past, &draft.past_participle]
+                    .iter()
+                    .all(|form| !form.trim().is_empty())
+                {
+                    let kind = match conjugator::group_of(&draft.to_verb(0)) {
+                        Some(group) => format!("Regular, {}", group.label()),
+                        None => "Irregular".to_string(),
+                    };
+                    ui.label(egui::RichText::new(kind).small());
+                }
+
+                let missing = self.verb_draft.missing();
+                if !missing.is_empty() {
+                    ui.label(
+                        egui::RichText::new(format!("Still needed: {}", missing.join(", ")))
+                            .small()
+                            .color(error),
+                    );
+                }
+                if let Some(existing) = self.verb_draft.duplicate_of(&self.verbs) {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "'{}' is already in the deck as \"{}\"",
+                            existing.infinitive, existing.english
+                        ))
+                        .small()
+                        .color(error),
+                    );
+                }
+
+                // How the new verb will be asked
+                ui.add_space(6.0);
+                let or_dots = |text: &str| {
+                    if text.trim().is_empty() {
+                        "…".to_string()
+                    } else {
+                        text.trim().to_string()
+                    }
+                };
+                ui.label(egui::RichText::new("Preview").small().color(accent_color));
+                ui.label(format!(
+                    "Translate to English: {}",
+                    or_dots(&self.verb_draft.infinitive)
+                ));
+                ui.label(format!(
+                    "Translate to {}: {}",
+                    self.deck_info.language,
+                    or_dots(&self.verb_draft.english)
+                ));
+
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    // Verbs of a temporary session have no deck file to save to
+                    let valid = self.verb_draft.is_valid(&self.verbs) && self.saved_deck.is_none();
+                    let editing = self.verb_draft.editing.is_some();
+                    if ui
+                        .add_enabled(valid, egui::Button::new("Save"))
+                        .on_hover_text(if editing {
+                            "Save the changes to the deck file"
+                        } else {
+                            "Add the verb to the deck file"
+                        })
+                        .on_disabled_hover_text(
+                            "Fill in every field with a verb that's new to the deck",
+                        )
+                        .clicked()
+                    {
+                        save = true;
+                    }
+                    if editing {
+                        if ui.button("Cancel").clicked() {
+                            cancel = true;
+                        }
+                        if self.confirm_delete {
+                            ui.label("Delete it from the deck?");
+                            if ui.button("Yes, delete").clicked() {
+                                delete = true;
+                            }
+                            if ui.button("Keep it").clicked() {
+                                self.confirm_delete = false;
+                            }
+                        } else if ui
+                            .add_enabled(self.saved_deck.is_none(), egui::Button::new("🗑 Delete"))
+                            .clicked()
+                        {
+                            self.confirm_delete = true;
+                        }
+                    }
+                    if let Some(editor_message) = &self.editor_message {
+                        ui.label(egui::RichText::new(editor_message).small());
+                    }
+                });
+            });
+
+        if save {
+            match self.verb_draft.editing.clone() {
+                Some(infinitive) => self.save_edited_verb(&infinitive),
+                None => self.add_drafted_verb(),
+            }
+        } else if delete {
+            if let Some(infinitive) = self.verb_draft.editing.clone() {
+                self.delete_verb(&infinitive);
+            }
+        } else if cancel {
+            self.verb_draft = VerbDraft::default();
+            self.confirm_delete = false;
+        }
+    }
+
+    /// Fills the editor with the verb at `index` to change it or delete it.
+    pub(crate) fn edit_verb(&mut self, index: usize) {
+        self.verb_draft = VerbDraft::from_verb(&self.verbs[index]);
+        self.open_verb_editor = true;
+        self.confirm_delete = false;
+        self.editor_message = None;
+    }
+
+    /// Saves the editor's fields over the verb with the given infinitive and writes the deck.
+    fn save_edited_verb(&mut self, infinitive: &str) {
+        let Some(verb) = self
+            .verbs
+            .iter_mut()
+            .find(|verb| verb.infinitive == infinitive)
+        else {
+            return;
+        };
+        self.verb_draft.apply_to(verb);
+        let renamed = verb.infinitive.clone();
+        if renamed != infinitive {
+            self.rename_verb(infinitive, &renamed);
+        }
+        self.confusion = confusion::ConfusionIndex::new(&self.verbs);
+        self.deck_revision += 1;
+        self.verb_draft = VerbDraft::default();
+
+        self.editor_message = Some(match self.write_deck() {
+            Ok(()) => format!("Saved the changes to '{}'", renamed),
+            Err(e) => format!("Changed '{}', but couldn't save the deck: {}", renamed, e),
+        });
+    }
+
+    /// Keeps a verb's results, stars and schedule when its infinitive is edited.
+    fn rename_verb(&mut self, from: &str, to: &str) {
+        for infinitives in [
+            &mut self.starred_verbs,
+            &mut self.learned_verbs,
+            &mut self.disabled_verbs,
+            &mut self.pass_seen,
+        ] {
+            if infinitives.remove(from) {
+                infinitives.insert(to.to_string());
+            }
+        }
+        if let Some(review) = self.schedule.remove(from) {
+            self.schedule.insert(to.to_string(), review);
+        }
+        if let Some(box_number) = self.leitner_boxes.remove(from) {
+            self.leitner_boxes.insert(to.to_string(), box_number);
+        }
+        self.stats.rename(from, to);
+        self.save_state();
+    }
+
+    /// Takes the verb with the given infinitive out of the deck and writes the deck.
+    ///
+    /// Its results stay in the saved state, so they come back if the verb is added again.
+    fn delete_verb(&mut self, infinitive: &str) {
+        let Some(index) = self
+            .verbs
+            .iter()
+            .position(|verb| verb.infinitive == infinitive)
+        else {
+            return;
+        };
+        self.verbs.remove(index);
+        self.confusion = confusion::ConfusionIndex::new(&self.verbs);
+        self.deck_revision += 1;
+        self.verb_draft = VerbDraft::default();
+        self.confirm_delete = false;
+        if self.browse_index > index {
+            self.browse_index -= 1;
+        }
+        self.browse_index = self.browse_index.min(self.verbs.len().saturating_sub(1));
+        if self.current_verb_index > index {
+            self.current_verb_index -= 1;
+        } else if self.current_verb_index == index && !self.verbs.is_empty() {
+            // The question was about the deleted verb, so move on from the verb before it
+            self.current_verb_index = index.saturating_sub(1);
+            self.next_verb();
+        }
+
+        self.editor_message = Some(match self.write_deck() {
+            Ok(()) => format!("Deleted '{}' from the deck", infinitive),
+            Err(e) => format!(
+                "Deleted '{}', but couldn't save the deck: {}",
+                infinitive, e
+            ),
+        });
+    }
+
+    /// Writes the verbs to the deck file, unless the file didn't load and would be lost.
+    pub(crate) fn write_deck(&self) -> io::Result<()> {
+        if self.load_error.is_some() {
+            return Err(io::Error::other(
+                "the deck file didn't load, so it's left as it is",
+            ));
+        }
+        deck::save_deck(&self.deck_file(), &self.deck_info, &self.verbs)
+    }
+
+    /// Adds the verb in the editor to the end of the deck and writes the deck file.
+    fn add_drafted_verb(&mut self) {
+        let position = self
+            .verbs
+            .iter()
+            .map(|verb| verb.position + 1)
+            .max()
+            .unwrap_or(0);
+        let verb = self.verb_draft.to_verb(position);
+        let infinitive = verb.infinitive.clone();
+        self.verbs.push(verb);
+        self.confusion = confusion::ConfusionIndex::new(&self.verbs);
+        self.deck_revision += 1;
+        self.browse_index = self.verbs.len() - 1;
+        self.verb_draft = VerbDraft::default();
+
+        self.editor_message = Some(match self.write_deck() {
+            Ok(()) => format!("Added '{}' to the deck", infinitive),
+            Err(e) => format!("Added '{}', but couldn't save the deck: {}", infinitive, e),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(infinitive: &str, english: &str) -> VerbDraft {
+        VerbDraft {
+            infinitive: infinitive.to_string(),
+            present: "ligger".to_string(),
+            past: "lå".to_string(),
+            past_participle: "ligget".to_string(),
+            imperative: String::new(),
+            present_participle: String::new(),
+            auxiliary: String::new(),
+            english: english.to_string(),
+            editing: None,
+        }
+    }
+
+    #[test]
+    fn lists_the_empty_fields() {
+        let mut incomplete = draft("at ligge", " ");
+        incomplete.past.clear();
+        assert_eq!(incomplete.missing(), ["past", "English"]);
+        assert!(!incomplete.is_valid(&[]));
+        assert!(draft("at ligge", "to lie").is_valid(&[]));
+    }
+
+    #[test]
+    fn spots_an_infinitive_already_in_the_deck() {
+        let deck = [draft("at ligge", "to lie").to_verb(0)];
+        assert!(draft("Ligge", "to lie down").duplicate_of(&deck).is_some());
+        assert!(!draft("at ligge ", "to lie").is_valid(&deck));
+        assert!(draft("at lægge", "to lay").duplicate_of(&deck).is_none());
+    }
+
+    #[test]
+    fn fills_in_regular_forms() {
+        let mut snakke = draft("at snakke", "to talk");
+        snakke.conjugate(Group::First);
+        assert_eq!(snakke.past, "snakkede");
+        assert_eq!(snakke.imperative, "snak");
+        assert_eq!(snakke.present_participle, "snakkende");
+        assert_eq!(conjugator::group_of(&snakke.to_verb(0)), Some(Group::First));
+        let ligge = draft("at ligge", "to lie");
+        assert_eq!(conjugator::group_of(&ligge.to_verb(0)), None);
+    }
+
+    #[test]
+    fn an_edited_verb_keeps_its_infinitive_and_other_fields() {
+        let mut verb = draft("at ligge", "to lie").to_verb(3);
+        verb.note = "lå like a lie".to_string();
+        let deck = [verb.clone()];
+        let mut edit = VerbDraft::from_verb(&verb);
+        assert!(edit.is_valid(&deck));
+        edit.english = " to lie down ".to_string();
+        edit.apply_to(&mut verb);
+        assert_eq!(verb.english, "to lie down");
+        assert_eq!(verb.note, "lå like a lie");
+        assert_eq!(verb.position, 3);
+    }
+}
@@ -0,0 +1,759 @@
+use crate::pattern::AnswerPattern;
+use serde::{Deserialize, Serialize};
+
+/// Splits a stored conjugation into its accepted variants.
+///
+/// Forms with more than one valid spelling are written with a "/" separator,
+/// e.g. `"hang/hængte"`.
+pub fn variants(field: &str) -> Vec<&str> {
+    field
+        .split('/')
+        .map(str::trim)
+        .filter(|variant| !variant.is_empty())
+        .collect()
+}
+
+/// Splits a stored English meaning into its alternatives, separated by "/" or "|".
+pub fn meaning_variants(english: &str) -> Vec<&str> {
+    english
+        .split(['/', '|'])
+        .map(str::trim)
+        .filter(|meaning| !meaning.is_empty())
+        .collect()
+}
+
+/// The English meanings an answer is graded against.
+///
+/// An alternative written without "to" after one with it is a verb too, so "to learn/teach"
+/// accepts "to learn" and "to teach".
+pub fn meanings(english: &str) -> Vec<String> {
+    let variants = meaning_variants(english);
+    let carry_to = variants
+        .first()
+        .is_some_and(|first| first.to_lowercase().starts_with("to "));
+    variants
+        .into_iter()
+        .map(|meaning| {
+            if carry_to && !meaning.to_lowercase().starts_with("to ") {
+                format!("to {meaning}")
+            } else {
+                meaning.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Formats a stored conjugation for display, listing every variant.
+pub fn format_variants(field: &str) -> String {
+    variants(field).join(" / ")
+}
+
+/// Switches for how leniently answers are compared.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GradingOptions {
+    /// Tell "Spiser" and "spiser" apart
+    pub case_sensitive: bool,
+    /// Leave out punctuation such as full stops and apostrophes when comparing
+    pub ignore_punctuation: bool,
+    /// Compare letters without their accents, and æ, ø and å as "ae", "oe" and "aa"
+    pub fold_diacritics: bool,
+    /// Accept a single wrong, missing or extra letter in answers of five letters or more
+    pub typo_tolerance: bool,
+    /// Accept infinitives with or without the leading "at"
+    pub optional_infinitive_marker: bool,
+    /// Accept English meanings with or without the leading "to"
+    pub optional_english_to: bool,
+    /// Accept reflexive verbs with or without a trailing "sig"
+    pub ignore_reflexive_sig: bool,
+    /// Accept conjugations typed with a subject pronoun in front, e.g. "han spiser"
+    pub accept_subject_pronoun: bool,
+    /// How letters are lowercased, which follows the deck's language rather than a setting
+    #[serde(skip)]
+    pub case_folding: CaseFolding,
+}
+
+/// Language rules for comparing answers without case.
+///
+/// Most languages, Danish included, are fine with Unicode lowercasing, which is the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaseFolding {
+    #[default]
+    Unicode,
+    /// "ß" matches "ss", so "Straße" and "STRASSE" are the same answer
+    German,
+    /// "I" lowercases to the dotless "ı" and "İ" to "i"
+    Turkish,
+}
+
+impl CaseFolding {
+    /// The folding for a deck's `language`, given in English or in the language itself.
+    pub fn for_language(language: &str) -> Self {
+        match language.trim().to_lowercase().as_str() {
+            "german" | "deutsch" | "de" => CaseFolding::German,
+            "turkish" | "türkçe" | "tr" => CaseFolding::Turkish,
+            _ => CaseFolding::Unicode,
+        }
+    }
+
+    pub fn fold(self, text: &str) -> String {
+        match self {
+            CaseFolding::Unicode => text.to_lowercase(),
+            CaseFolding::German => text.to_lowercase().replace('ß', "ss"),
+            CaseFolding::Turkish => text
+                .chars()
+                .map(|ch| match ch {
+                    'I' => "ı".to_string(),
+                    'İ' => "i".to_string(),
+                    _ => ch.to_lowercase().collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for GradingOptions {
+    fn default() -> Self {
+        Self {
+            case_sensitive: false,
+            ignore_punctuation: false,
+            fold_diacritics: false,
+            typo_tolerance: false,
+            optional_infinitive_marker: true,
+            optional_english_to: false,
+            ignore_reflexive_sig: false,
+            accept_subject_pronoun: false,
+            case_folding: CaseFolding::default(),
+        }
+    }
+}
+
+/// Named combinations of the grading options.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum GradingPreset {
+    /// Exactly as stored, apart from surrounding spaces
+    Strict,
+    #[default]
+    Normal,
+    /// Everything that is close enough to show the verb is known
+    Lenient,
+    /// Options that don't match any preset
+    Custom,
+}
+
+impl GradingPreset {
+    pub const ALL: [GradingPreset; 4] = [
+        GradingPreset::Strict,
+        GradingPreset::Normal,
+        GradingPreset::Lenient,
+        GradingPreset::Custom,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GradingPreset::Strict => "Strict",
+            GradingPreset::Normal => "Normal",
+            GradingPreset::Lenient => "Lenient",
+            GradingPreset::Custom => "Custom",
+        }
+    }
+
+    /// The options the preset sets, or none for `Custom`.
+    pub fn options(self) -> Option<GradingOptions> {
+        match self {
+            GradingPreset::Strict => Some(GradingOptions {
+                case_sensitive: true,
+                optional_infinitive_marker: false,
+                ..GradingOptions::default()
+            }),
+            GradingPreset::Normal => Some(GradingOptions::default()),
+            GradingPreset::Lenient => Some(GradingOptions {
+                case_sensitive: false,
+                ignore_punctuation: true,
+                fold_diacritics: true,
+                typo_tolerance: true,
+                optional_infinitive_marker: true,
+                optional_english_to: true,
+                ignore_reflexive_sig: true,
+                accept_subject_pronoun: true,
+                case_folding: CaseFolding::default(),
+            }),
+            GradingPreset::Custom => None,
+        }
+    }
+
+    /// The preset that sets exactly these options, or `Custom`.
+    ///
+    /// The case folding comes from the deck, so it's left out of the comparison.
+    pub fn matching(options: &GradingOptions) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|preset| {
+                preset.options().is_some_and(|preset| {
+                    GradingOptions {
+                        case_folding: options.case_folding,
+                        ..preset
+                    } == *options
+                })
+            })
+            .unwrap_or(GradingPreset::Custom)
+    }
+}
+
+/// Danish subject pronouns, lowercased. "I" is the plural "you".
+const SUBJECT_PRONOUNS: [&str; 7] = ["jeg", "du", "han", "hun", "vi", "i", "de"];
+
+/// Trims and lowercases an answer, the comparison used when no options apply.
+pub fn normalize(text: &str) -> String {
+    text.trim().to_lowercase()
+}
+
+/// Normalizes an answer or an expected answer according to the grading options.
+fn normalize_with(text: &str, options: &GradingOptions) -> String {
+    let mut text = if options.case_sensitive {
+        text.trim().to_string()
+    } else {
+        options.case_folding.fold(text.trim())
+    };
+    if options.ignore_punctuation {
+        text = text
+            .chars()
+            .filter(|ch| !ch.is_ascii_punctuation())
+            .collect::<String>()
+            .trim()
+            .to_string();
+    }
+    if options.fold_diacritics {
+        text = fold_diacritics(&text);
+    }
+    if options.ignore_reflexive_sig {
+        text = strip_reflexive_sig(&text).to_string();
+    }
+    text
+}
+
+/// Spells out æ, ø and å and drops accents, e.g. "blåbær" becomes "blaabaer".
+fn fold_diacritics(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            'æ' => folded.push_str("ae"),
+            'Æ' => folded.push_str("Ae"),
+            'ø' => folded.push_str("oe"),
+            'Ø' => folded.push_str("Oe"),
+            'å' => folded.push_str("aa"),
+            'Å' => folded.push_str("Aa"),
+            'á' | 'à' | 'â' | 'ä' => folded.push('a'),
+            'é' | 'è' | 'ê' | 'ë' => folded.push('e'),
+            'í' | 'ì' | 'î' | 'ï' => folded.push('i'),
+            'ó' | 'ò' | 'ô' | 'ö' => folded.push('o'),
+            'ú' | 'ù' | 'û' | 'ü' => folded.push('u'),
+            _ => folded.push(ch),
+        }
+    }
+    folded
+}
+
+/// Compares two normalized answers, allowing one typo in longer words when enabled.
+fn same_answer(answer: &str, expected: &str, options: &GradingOptions) -> bool {
+    answer == expected
+        || (options.typo_tolerance
+            && expected.chars().count() >= 5
+            && levenshtein(answer, expected) <= 1)
+}
+
+/// Returns true if the answer matches any of the accepted answers.
+pub fn matches_any(answer: &str, accepted: &[&str], options: &GradingOptions) -> bool {
+    let answer = normalize_with(answer, options);
+    accepted
+        .iter()
+        .any(|expected| same_answer(&answer, &normalize_with(expected, options), options))
+}
+
+/// Returns true if the normalized answer matches the deck's pattern for the field, if it
+/// has one.
+pub fn matches_pattern(
+    answer: &str,
+    pattern: Option<&AnswerPattern>,
+    options: &GradingOptions,
+) -> bool {
+    pattern.is_some_and(|pattern| pattern.is_match(&normalize_with(answer, options)))
+}
+
+/// The accepted full phrases for the present tense.
+///
+/// A stored phrase wins, otherwise each present tense variant gets the pronoun in
+/// front. Returns nothing when there is neither, so the bare form is graded instead.
+pub fn present_phrases(present_phrase: &str, present: &str, pronoun: &str) -> Vec<String> {
+    if !variants(present_phrase).is_empty() {
+        return variants(present_phrase)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+    }
+    let pronoun = pronoun.trim();
+    if pronoun.is_empty() {
+        return Vec::new();
+    }
+    variants(present)
+        .into_iter()
+        .map(|variant| format!("{} {}", pronoun, variant))
+        .collect()
+}
+
+/// The accepted perfect tense phrases, one for each auxiliary and past participle
+/// variant, e.g. "jeg har spist". Without a pronoun they start with the auxiliary.
+pub fn perfect_phrases(auxiliary: &str, past_participle: &str, pronoun: &str) -> Vec<String> {
+    let pronoun = pronoun.trim();
+    let mut phrases = Vec::new();
+    for auxiliary in variants(auxiliary) {
+        for participle in variants(past_participle) {
+            phrases.push(if pronoun.is_empty() {
+                format!("{} {}", auxiliary, participle)
+            } else {
+                format!("{} {} {}", pronoun, auxiliary, participle)
+            });
+        }
+    }
+    phrases
+}
+
+/// The auxiliary a perfect tense answer starts with, after any pronoun, if it's "har" or
+/// "er" but not one the verb takes, as in "jeg har gået".
+pub fn wrong_auxiliary(answer: &str, auxiliary: &str) -> Option<&'static str> {
+    let first = strip_subject_pronoun(answer)
+        .split_whitespace()
+        .next()?
+        .to_lowercase();
+    let used = ["har", "er"].into_iter().find(|&word| word == first)?;
+    (!variants(auxiliary).contains(&used)).then_some(used)
+}
+
+/// Returns true if a conjugated answer matches any of the accepted forms.
+pub fn matches_conjugation(answer: &str, accepted: &[&str], options: &GradingOptions) -> bool {
+    let answer = if options.accept_subject_pronoun {
+        strip_subject_pronoun(answer)
+    } else {
+        answer
+    };
+    matches_any(answer, accepted, options)
+}
+
+/// Returns true if the answer matches an English meaning, with or without the leading
+/// "to" if the options allow it.
+pub fn matches_english(answer: &str, accepted: &[&str], options: &GradingOptions) -> bool {
+    if !options.optional_english_to {
+        return matches_any(answer, accepted, options);
+    }
+    let answer = normalize_with(answer, options);
+    accepted.iter().any(|expected| {
+        let expected = normalize_with(expected, options);
+        same_answer(strip_to(&answer), strip_to(&expected), options)
+    })
+}
+
+/// Drops the English infinitive marker, so "to eat" becomes "eat".
+fn strip_to(text: &str) -> &str {
+    text.strip_prefix("to ")
+        .map(str::trim_start)
+        .unwrap_or(text)
+}
+
+/// Returns true if the answer is the infinitive, with or without the leading "at" if
+/// the options allow it.
+pub fn matches_infinitive(answer: &str, infinitive: &str, options: &GradingOptions) -> bool {
+    let answer = normalize_with(answer, options);
+    let infinitive = normalize_with(infinitive, options);
+    if options.optional_infinitive_marker {
+        same_answer(
+            strip_infinitive_marker(&answer),
+            strip_infinitive_marker(&infinitive),
+            options,
+        )
+    } else {
+        same_answer(&answer, &infinitive, options)
+    }
+}
+
+/// Drops a trailing reflexive pronoun, so "skynde sig" becomes "skynde".
+fn strip_reflexive_sig(text: &str) -> &str {
+    match text.strip_suffix("sig") {
+        Some(rest) if rest.ends_with(char::is_whitespace) => rest.trim_end(),
+        _ => text,
+    }
+}
+
+/// Drops a leading subject pronoun, so "han spiser" becomes "spiser".
+fn strip_subject_pronoun(text: &str) -> &str {
+    match text.trim_start().split_once(char::is_whitespace) {
+        Some((first, rest))
+            if SUBJECT_PRONOUNS.contains(&first.to_lowercase().as_str())
+                && !rest.trim().is_empty() =>
+        {
+            rest.trim_start()
+        }
+        _ => text,
+    }
+}
+
+fn strip_infinitive_marker(text: &str) -> &str {
+    text.strip_prefix("at ")
+        .map(str::trim_start)
+        .unwrap_or(text)
+}
+
+/// Number of single-character insertions, deletions and substitutions between two strings.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// The character is in both the answer and the expected answer
+    Same,
+    /// The character is in the expected answer but not in the answer
+    Missing,
+    /// The character was typed but is not in the expected answer
+    Extra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffChar {
+    pub ch: char,
+    pub kind: DiffKind,
+}
+
+/// Aligns the normalized answer with the expected answer character by character,
+/// using the longest common subsequence of the two.
+pub fn diff_chars(answer: &str, expected: &str) -> Vec<DiffChar> {
+    let answer: Vec<char> = normalize(answer).chars().collect();
+    let expected: Vec<char> = normalize(expected).chars().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of answer[i..] and expected[j..]
+    let mut lcs = vec![vec![0usize; expected.len() + 1]; answer.len() + 1];
+    for i in (0..answer.len()).rev() {
+        for j in (0..expected.len()).rev() {
+            lcs[i][j] = if answer[i] == expected[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(answer.len().max(expected.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < answer.len() || j < expected.len() {
+        if i < answer.len() && j < expected.len() && answer[i] == expected[j] {
+            diff.push(DiffChar {
+                ch: answer[i],
+                kind: DiffKind::Same,
+            });
+            i += 1;
+            j += 1;
+        } else if j < expected.len() && (i == answer.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push(DiffChar {
+                ch: expected[j],
+                kind: DiffKind::Missing,
+            });
+            j += 1;
+        } else {
+            diff.push(DiffChar {
+                ch: answer[i],
+                kind: DiffKind::Extra,
+            });
+            i += 1;
+        }
+    }
+    diff
+}
+
+/// The accepted answer a wrong answer misses by a few letters at most, if any.
+///
+/// Both are normalized with the grading options and compared without a leading "at", so
+/// "at lææse" is a near miss of "læse" with a `max_distance` of 2.
+pub fn near_miss<'a>(
+    answer: &str,
+    accepted: &[&'a str],
+    max_distance: usize,
+    options: &GradingOptions,
+) -> Option<&'a str> {
+    let answer = normalize_with(answer, options);
+    let answer = strip_infinitive_marker(&answer);
+    if answer.is_empty() || max_distance == 0 {
+        return None;
+    }
+    accepted
+        .iter()
+        .map(|expected| {
+            let normalized = normalize_with(expected, options);
+            (
+                *expected,
+                levenshtein(answer, strip_infinitive_marker(&normalized)),
+            )
+        })
+        .filter(|&(_, distance)| distance > 0 && distance <= max_distance)
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(expected, _)| expected)
+}
+
+/// Diffs the answer against whichever accepted answer it is closest to.
+pub fn closest_diff(answer: &str, accepted: &[&str]) -> Vec<DiffChar> {
+    accepted
+        .iter()
+        .map(|expected| diff_chars(answer, expected))
+        .min_by_key(|diff| diff.iter().filter(|c| c.kind != DiffKind::Same).count())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_variants_on_slash() {
+        assert_eq!(variants("hang/hængte"), vec!["hang", "hængte"]);
+        assert_eq!(variants(" hang / hængte "), vec!["hang", "hængte"]);
+        assert_eq!(variants("talte"), vec!["talte"]);
+    }
+
+    #[test]
+    fn folds_german_sharp_s() {
+        let options = GradingOptions {
+            case_folding: CaseFolding::for_language("German"),
+            ..GradingOptions::default()
+        };
+        assert!(matches_any("STRASSE", &["Straße"], &options));
+        assert!(matches_any("Straße", &["strasse"], &options));
+        assert!(matches_any("gießen", &["GIESSEN"], &options));
+        // Other decks keep "ß" and "ss" apart
+        assert!(!matches_any(
+            "STRASSE",
+            &["Straße"],
+            &GradingOptions::default()
+        ));
+    }
+
+    #[test]
+    fn folds_turkish_dotted_and_dotless_i() {
+        let options = GradingOptions {
+            case_folding: CaseFolding::Turkish,
+            ..GradingOptions::default()
+        };
+        assert!(matches_any("İSTEMEK", &["istemek"], &options));
+        assert!(matches_any("KALMAK", &["kalmak"], &options));
+        assert!(!matches_any("ISTEMEK", &["istemek"], &options));
+    }
+
+    #[test]
+    fn presets_ignore_the_case_folding() {
+        let options = GradingOptions {
+            case_folding: CaseFolding::German,
+            ..GradingOptions::default()
+        };
+        assert_eq!(GradingPreset::matching(&options), GradingPreset::Normal);
+    }
+
+    #[test]
+    fn accepts_either_past_form() {
+        let past = variants("hang/hængte");
+        let options = GradingOptions::default();
+        assert!(matches_any("hang", &past, &options));
+        assert!(matches_any("Hængte ", &past, &options));
+        assert!(!matches_any("hænger", &past, &options));
+        assert!(!matches_any("hang/hængte", &past, &options));
+    }
+
+    #[test]
+    fn ignores_reflexive_sig_when_enabled() {
+        let options = GradingOptions {
+            ignore_reflexive_sig: true,
+            ..Default::default()
+        };
+        assert!(matches_infinitive("skynde", "at skynde sig", &options));
+        assert!(matches_infinitive(
+            "at skynde sig",
+            "at skynde sig",
+            &options
+        ));
+        assert!(matches_any("skynder", &["skynder sig"], &options));
+        assert!(matches_any("skynder  sig", &["skynder"], &options));
+
+        let strict = GradingOptions::default();
+        assert!(!matches_infinitive("skynde", "at skynde sig", &strict));
+    }
+
+    #[test]
+    fn leaves_verbs_ending_in_sig_letters_alone() {
+        let options = GradingOptions {
+            ignore_reflexive_sig: true,
+            ..Default::default()
+        };
+        // "sige" and "sig" as part of a longer word are not the reflexive pronoun
+        assert!(!matches_infinitive("si", "at sige", &options));
+        assert!(!matches_any("for", &["forsig"], &options));
+        assert!(matches_any("sig", &["sig"], &options));
+        assert!(!matches_any("se", &["se sig om"], &options));
+    }
+
+    #[test]
+    fn accepts_english_with_or_without_to_when_enabled() {
+        let options = GradingOptions {
+            optional_english_to: true,
+            ..Default::default()
+        };
+        assert!(matches_english("run", &["to run"], &options));
+        assert!(matches_english("to run", &["run"], &options));
+        assert!(matches_english("look after", &["to look after"], &options));
+        assert!(matches_english(
+            "To look after",
+            &["to look after"],
+            &options
+        ));
+        assert!(!matches_english("look", &["to look after"], &options));
+        assert!(!matches_english("tomato", &["mato"], &options));
+
+        let normal = GradingOptions::default();
+        assert!(!matches_english("run", &["to run"], &normal));
+        assert!(matches_english("to run", &["to run"], &normal));
+    }
+
+    #[test]
+    fn presets_set_their_options() {
+        let strict = GradingPreset::Strict.options().unwrap();
+        assert!(!matches_any("Spiser", &["spiser"], &strict));
+        assert!(!matches_infinitive("spise", "at spise", &strict));
+        assert!(matches_infinitive("at spise", "at spise", &strict));
+
+        let normal = GradingPreset::Normal.options().unwrap();
+        assert!(matches_any("Spiser", &["spiser"], &normal));
+        assert!(matches_infinitive("spise", "at spise", &normal));
+        assert!(!matches_any("spisr", &["spiser"], &normal));
+
+        let lenient = GradingPreset::Lenient.options().unwrap();
+        assert!(matches_any("spisr", &["spiser"], &lenient));
+        assert!(!matches_any("blaa", &["blå"], &normal));
+        assert!(matches_any("blaa", &["blå"], &lenient));
+        assert!(matches_any("cafe", &["café"], &lenient));
+        assert!(matches_any("to be.", &["to be"], &lenient));
+        // Short words don't get a typo allowance
+        assert!(!matches_any("ar", &["er"], &lenient));
+    }
+
+    #[test]
+    fn recognizes_presets_and_custom_options() {
+        for preset in [
+            GradingPreset::Strict,
+            GradingPreset::Normal,
+            GradingPreset::Lenient,
+        ] {
+            assert_eq!(GradingPreset::matching(&preset.options().unwrap()), preset);
+        }
+        let custom = GradingOptions {
+            typo_tolerance: true,
+            ..Default::default()
+        };
+        assert_eq!(GradingPreset::matching(&custom), GradingPreset::Custom);
+        assert_eq!(GradingPreset::Custom.options(), None);
+    }
+
+    #[test]
+    fn builds_perfect_phrases_and_spots_the_wrong_auxiliary() {
+        assert_eq!(perfect_phrases("har", "spist", "jeg"), ["jeg har spist"]);
+        assert_eq!(
+            perfect_phrases("er/har", "løbet", ""),
+            ["er løbet", "har løbet"]
+        );
+        assert_eq!(wrong_auxiliary("jeg har gået", "er"), Some("har"));
+        assert_eq!(wrong_auxiliary("Er gået", "har"), Some("er"));
+        assert_eq!(wrong_auxiliary("jeg er gået", "er"), None);
+        assert_eq!(wrong_auxiliary("jeg har løbet", "er/har"), None);
+        assert_eq!(wrong_auxiliary("jeg gik", "er"), None);
+    }
+
+    #[test]
+    fn builds_present_phrases() {
+        assert_eq!(
+            present_phrases("", "spiser", "jeg"),
+            vec!["jeg spiser".to_string()]
+        );
+        assert_eq!(
+            present_phrases("vi spiser", "spiser", "jeg"),
+            vec!["vi spiser".to_string()]
+        );
+        assert!(present_phrases("", "spiser", " ").is_empty());
+
+        let phrases = present_phrases("", "hænger", "han");
+        let accepted: Vec<&str> = phrases.iter().map(String::as_str).collect();
+        assert!(matches_any(
+            "Han hænger",
+            &accepted,
+            &GradingOptions::default()
+        ));
+        assert!(!matches_any(
+            "hænger",
+            &accepted,
+            &GradingOptions::default()
+        ));
+    }
+
+    #[test]
+    fn strips_a_leading_subject_pronoun_when_enabled() {
+        let options = GradingOptions {
+            accept_subject_pronoun: true,
+            ..Default::default()
+        };
+        assert!(matches_conjugation("han spiser", &["spiser"], &options));
+        assert!(matches_conjugation("I spiser", &["spiser"], &options));
+        assert!(matches_conjugation("spiser", &["spiser"], &options));
+        // Only a leading pronoun is stripped, and only a recognized one
+        assert!(!matches_conjugation("spiser han", &["spiser"], &options));
+        assert!(!matches_conjugation("hund spiser", &["spiser"], &options));
+        assert!(!matches_conjugation("man spiser", &["spiser"], &options));
+
+        let strict = GradingOptions::default();
+        assert!(!matches_conjugation("han spiser", &["spiser"], &strict));
+    }
+
+    #[test]
+    fn splits_english_meanings() {
+        assert_eq!(meanings("to learn/teach"), ["to learn", "to teach"]);
+        assert_eq!(meanings("to make | to do"), ["to make", "to do"]);
+        assert_eq!(meanings("eat"), ["eat"]);
+        let options = GradingOptions::default();
+        let accepted = meanings("to borrow/lend");
+        let accepted: Vec<&str> = accepted.iter().map(String::as_str).collect();
+        assert!(matches_english("to lend", &accepted, &options));
+        assert!(matches_english("To borrow", &accepted, &options));
+        assert!(!matches_english("to borrow/lend", &accepted, &options));
+    }
+
+    #[test]
+    fn finds_answers_a_letter_or_two_off() {
+        let options = GradingOptions::default();
+        assert_eq!(near_miss("laese", &["læse"], 1, &options), None);
+        assert_eq!(near_miss("lase", &["læse"], 1, &options), Some("læse"));
+        assert_eq!(near_miss("at laase", &["læse"], 2, &options), Some("læse"));
+        assert_eq!(
+            near_miss("spis", &["spiser", "spis"], 1, &options),
+            None,
+            "an exact match isn't a miss"
+        );
+        assert_eq!(near_miss("gik", &["gå"], 1, &options), None);
+        assert_eq!(near_miss("lase", &["læse"], 0, &options), None);
+        assert_eq!(near_miss("", &["e"], 1, &options), None);
+    }
+}
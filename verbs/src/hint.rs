@@ -0,0 +1,46 @@
+/// Number of hint levels. Each asks for one more press of the hint button.
+pub const LEVELS: u8 = 3;
+
+/// How much of the answer a hint of `level` gives away: the first letter, then the
+/// length of each word as underscores, then every other letter. Level 0 gives nothing.
+pub fn reveal(answer: &str, level: u8) -> Option<String> {
+    match level {
+        0 => None,
+        1 => answer.chars().next().map(|first| format!("{}…", first)),
+        _ => {
+            let mut letter = 0;
+            let spaced: Vec<String> = answer
+                .chars()
+                .map(|ch| {
+                    // Spaces and punctuation stay, so the words can be told apart
+                    if !ch.is_alphabetic() {
+                        return ch.to_string();
+                    }
+                    let shown = letter == 0 || (level >= 3 && letter % 2 == 0);
+                    letter += 1;
+                    if shown {
+                        ch.to_string()
+                    } else {
+                        "_".to_string()
+                    }
+                })
+                .collect();
+            Some(spaced.join(" "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gives_away_more_of_the_answer_at_each_level() {
+        assert_eq!(reveal("spise", 0), None);
+        assert_eq!(reveal("spise", 1).as_deref(), Some("s…"));
+        assert_eq!(reveal("spise", 2).as_deref(), Some("s _ _ _ _"));
+        assert_eq!(reveal("spise", 3).as_deref(), Some("s _ i _ e"));
+        assert_eq!(reveal("er gået", 2).as_deref(), Some("e _   _ _ _ _"));
+        assert_eq!(reveal("er gået", 3).as_deref(), Some("e _   g _ e _"));
+    }
+}
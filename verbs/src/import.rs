@@ -0,0 +1,543 @@
+use crate::editor::infinitive_key;
+use crate::pattern::AnswerPatterns;
+use crate::{DanishVerbsApp, Verb};
+use eframe::egui;
+use std::collections::HashSet;
+use std::{fs, io};
+
+/// Verbs read from pasted rows, and the rows that couldn't be used.
+#[derive(Debug, Default)]
+pub struct Import {
+    pub verbs: Vec<Verb>,
+    /// One-based line numbers of skipped rows, with the reason
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// Parses rows of `infinitive, present, past, past participle, English`, as copied from a
+/// spreadsheet or a table on a web page.
+///
+/// Rows are split on tabs when they have any, otherwise on commas. Blank lines and a header
+/// row starting with "infinitive" are ignored.
+pub fn parse_rows(text: &str) -> Import {
+    let mut import = Import::default();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let separator = if line.contains('\t') { '\t' } else { ',' };
+        let fields: Vec<&str> = line.split(separator).map(str::trim).collect();
+        if fields[0].eq_ignore_ascii_case("infinitive") {
+            continue;
+        }
+
+        if fields.len() != 5 {
+            import.skipped.push((
+                index + 1,
+                format!("expected 5 fields, found {}", fields.len()),
+            ));
+            continue;
+        }
+        if let Some(empty) = fields.iter().position(|field| field.is_empty()) {
+            import
+                .skipped
+                .push((index + 1, format!("field {} is empty", empty + 1)));
+            continue;
+        }
+
+        let fields = [
+            fields[0], fields[1], fields[2], fields[3], "", "", "", fields[4],
+        ];
+        import.verbs.push(verb(fields, import.verbs.len()));
+    }
+
+    import
+}
+
+/// The verb fields a CSV column can be read into, in the order of a deck entry.
+pub const CSV_FIELDS: [&str; 8] = [
+    "infinitive",
+    "present",
+    "past",
+    "past_participle",
+    "imperative",
+    "present_participle",
+    "auxiliary",
+    "english",
+];
+
+/// The fields a verb can do without, which a CSV file may leave out or leave empty.
+const OPTIONAL_CSV_FIELDS: [&str; 3] = ["imperative", "present_participle", "auxiliary"];
+
+/// A CSV file split into its header and rows, before its columns are matched to fields.
+#[derive(Debug, Default)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// The column of each field in `CSV_FIELDS`, if it has one
+    pub columns: [Option<usize>; 8],
+}
+
+/// Reads a CSV file with a header row, guessing which column holds which field.
+///
+/// Spreadsheets save CSV with commas, semicolons (as Danish Excel does) or tabs, so the
+/// separator is whichever of these the header has most of.
+pub fn read_csv(text: &str) -> CsvTable {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let header = text.lines().next().unwrap_or_default();
+    let separator = [b'\t', b';', b',']
+        .into_iter()
+        .max_by_key(|&separator| header.matches(char::from(separator)).count())
+        .unwrap_or(b',');
+
+    let mut records = csv::ReaderBuilder::new()
+        .delimiter(separator)
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes())
+        .into_records()
+        .filter_map(Result::ok)
+        .map(|record| record.iter().map(str::to_string).collect::<Vec<_>>());
+    let headers = records.next().unwrap_or_default();
+    let columns = CSV_FIELDS.map(|field| guess_column(&headers, field));
+    CsvTable {
+        headers,
+        rows: records
+            .filter(|row| row.iter().any(|value| !value.is_empty()))
+            .collect(),
+        columns,
+    }
+}
+
+/// The column whose header names `field`, in English or Danish.
+fn guess_column(headers: &[String], field: &str) -> Option<usize> {
+    let names: &[&str] = match field {
+        "infinitive" => &[
+            "infinitive",
+            "infinitiv",
+            "danish",
+            "dansk",
+            "verb",
+            "verbum",
+        ],
+        "present" => &["present", "nutid", "præsens"],
+        "past" => &["past", "datid", "præteritum"],
+        "past_participle" => &[
+            "past participle",
+            "past_participle",
+            "participle",
+            "førnutid",
+            "perfektum participium",
+            "participium",
+        ],
+        "imperative" => &["imperative", "imperativ", "bydemåde", "bydeform"],
+        "present_participle" => &[
+            "present participle",
+            "present_participle",
+            "nutids tillægsform",
+            "præsens participium",
+        ],
+        "auxiliary" => &["auxiliary", "hjælpeverbum"],
+        _ => &["english", "engelsk", "meaning", "translation", "betydning"],
+    };
+    headers.iter().position(|header| {
+        let header = header.trim().to_lowercase();
+        names.contains(&header.as_str())
+    })
+}
+
+impl CsvTable {
+    /// Turns the rows into verbs using the chosen columns, skipping rows with a field empty
+    /// that a verb needs.
+    pub fn verbs(&self) -> Import {
+        let mut import = Import::default();
+        for (index, row) in self.rows.iter().enumerate() {
+            // Line numbers count the header
+            let line = index + 2;
+            let fields = self.columns.map(|column| {
+                column
+                    .and_then(|column| row.get(column))
+                    .map_or("", String::as_str)
+            });
+            if let Some(empty) = CSV_FIELDS
+                .iter()
+                .zip(fields)
+                .position(|(name, field)| field.is_empty() && !OPTIONAL_CSV_FIELDS.contains(name))
+            {
+                import
+                    .skipped
+                    .push((line, format!("no {}", CSV_FIELDS[empty].replace('_', " "))));
+                continue;
+            }
+            import.verbs.push(verb(fields, import.verbs.len()));
+        }
+        import
+    }
+}
+
+/// A verb from its fields, in the order of `CSV_FIELDS`.
+fn verb(fields: [&str; 8], position: usize) -> Verb {
+    Verb {
+        infinitive: fields[0].to_string(),
+        present: fields[1].to_string(),
+        past: fields[2].to_string(),
+        past_participle: fields[3].to_string(),
+        imperative: fields[4].to_string(),
+        present_participle: fields[5].to_string(),
+        auxiliary: fields[6].to_string(),
+        english: fields[7].to_string(),
+        ipa: String::new(),
+        present_phrase: String::new(),
+        note: String::new(),
+        frequency: 0.0,
+        confusables: Vec::new(),
+        answer_pattern: AnswerPatterns::default(),
+        group: None,
+        position,
+    }
+}
+
+/// Writes verbs as CSV with a header row, quoting fields that need it.
+pub fn write_csv(writer: impl io::Write, verbs: &[&Verb]) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(CSV_FIELDS)?;
+    for verb in verbs {
+        writer.write_record([
+            &verb.infinitive,
+            &verb.present,
+            &verb.past,
+            &verb.past_participle,
+            &verb.imperative,
+            &verb.present_participle,
+            &verb.auxiliary,
+            &verb.english,
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// How many rows of a CSV file are previewed while its columns are matched.
+const PREVIEW_ROWS: usize = 3;
+
+impl DanishVerbsApp {
+    /// The File menu, importing verbs from a CSV file and exporting the deck to one or to
+    /// Anki.
+    pub(crate) fn show_file_menu(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .button("Import verbs from CSV…")
+            .on_hover_text("Choose which column holds which form next")
+            .clicked()
+        {
+            ui.close_menu();
+            if let Some(path) = self.csv_dialog().pick_file() {
+                match fs::read_to_string(&path) {
+                    Ok(text) => {
+                        self.csv_import = Some(read_csv(&text));
+                        self.csv_message = None;
+                    }
+                    Err(e) => self.csv_message = Some(format!("Couldn't read the file: {}", e)),
+                }
+                self.csv_path = Some(path);
+            }
+        }
+        if ui
+            .button("Export deck to CSV…")
+            .on_hover_text("Write the verbs in deck order, with a header row")
+            .clicked()
+            && let Some(path) = self.csv_dialog().set_file_name("verbs.csv").save_file()
+        {
+            let by_position = self.deck_cache().by_position.clone();
+            let verbs: Vec<&Verb> = by_position
+                .iter()
+                .map(|&index| &self.verbs[index])
+                .collect();
+            let written = fs::File::create(&path)
+                .map_err(csv::Error::from)
+                .and_then(|file| write_csv(file, &verbs));
+            self.csv_message = Some(match written {
+                Ok(()) => format!("Exported {} verbs to {}", verbs.len(), path.display()),
+                Err(e) => format!("Couldn't write the file: {}", e),
+            });
+            self.csv_path = Some(path);
+        }
+        if let Some(csv_message) = &self.csv_message {
+            ui.label(egui::RichText::new(csv_message).small());
+        }
+        ui.separator();
+        self.show_anki_export(ui);
+    }
+
+    /// A file dialog for CSV files, opening where the last one was.
+    fn csv_dialog(&self) -> rfd::FileDialog {
+        let dialog = rfd::FileDialog::new().add_filter("CSV", &["csv", "txt"]);
+        match self.csv_path.as_deref().and_then(|path| path.parent()) {
+            Some(dir) => dialog.set_directory(dir),
+            None => dialog,
+        }
+    }
+
+    /// The window matching the columns of a CSV file to the verb fields, with a preview of
+    /// the verbs they give.
+    pub(crate) fn show_csv_import(&mut self, ctx: &egui::Context) {
+        let Some(table) = &mut self.csv_import else {
+            return;
+        };
+        let mut open = true;
+        let mut practice = false;
+        let mut add = false;
+        let temporary = self.saved_deck.is_some();
+        let form_names = [
+            "Infinitive".to_string(),
+            self.deck_info
+                .form_name(crate::ConjugationForm::Present)
+                .to_string(),
+            self.deck_info
+                .form_name(crate::ConjugationForm::Past)
+                .to_string(),
+            self.deck_info
+                .form_name(crate::ConjugationForm::PastParticiple)
+                .to_string(),
+            self.deck_info
+                .form_name(crate::ConjugationForm::Imperative)
+                .to_string(),
+            self.deck_info
+                .form_name(crate::ConjugationForm::PresentParticiple)
+                .to_string(),
+            "Auxiliary".to_string(),
+            "English".to_string(),
+        ];
+
+        egui::Window::new("Import CSV")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} rows. Choose the column for each field:",
+                    table.rows.len()
+                ));
+                egui::Grid::new("csv_columns")
+                    .num_columns(2)
+                    .spacing([16.0, 6.0])
+                    .show(ui, |ui| {
+                        for (field, name) in form_names.iter().enumerate() {
+                            ui.label(name);
+                            let column = &mut table.columns[field];
+                            let selected = column
+                                .and_then(|column| table.headers.get(column))
+                                .map_or("—", String::as_str);
+                            egui::ComboBox::from_id_salt(("csv_column", field))
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(column, None, "—");
+                                    for (index, header) in table.headers.iter().enumerate() {
+                                        ui.selectable_value(column, Some(index), header);
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+
+                let import = table.verbs();
+                ui.add_space(6.0);
+                for verb in import.verbs.iter().take(PREVIEW_ROWS) {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{}, {}, {}, {} — {}",
+                            verb.infinitive,
+                            verb.present,
+                            verb.past,
+                            verb.past_participle,
+                            verb.english
+                        ))
+                        .small(),
+                    );
+                }
+                let mut summary = format!(
+                    "{} verb{} ready",
+                    import.verbs.len(),
+                    if import.verbs.len() == 1 { "" } else { "s" }
+                );
+                if let Some((line, reason)) = import.skipped.first() {
+                    summary += &format!(
+                        ", {} row{} skipped (first on line {}: {})",
+                        import.skipped.len(),
+                        if import.skipped.len() == 1 { "" } else { "s" },
+                        line,
+                        reason
+                    );
+                }
+                ui.label(egui::RichText::new(summary).small());
+
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    let any = !import.verbs.is_empty();
+                    practice = ui
+                        .add_enabled(any, egui::Button::new("Practice them"))
+                        .on_hover_text("Practice the verbs without changing the deck")
+                        .clicked();
+                    // Verbs of a temporary session have no deck file to save to
+                    add = ui
+                        .add_enabled(any && !temporary, egui::Button::new("Add to my deck"))
+                        .on_hover_text("Add the verbs that aren't in the deck yet and save it")
+                        .clicked();
+                });
+            });
+
+        if practice || add {
+            let verbs = table.verbs().verbs;
+            self.csv_import = None;
+            if practice {
+                let source = self.csv_path.as_ref().map_or_else(
+                    || "a CSV file".to_string(),
+                    |path| path.display().to_string(),
+                );
+                self.start_temporary_session(verbs, source);
+            } else {
+                self.add_to_deck(verbs);
+            }
+        } else if !open {
+            self.csv_import = None;
+        }
+    }
+
+    /// Appends the verbs whose infinitive isn't in the deck yet, then writes the deck.
+    fn add_to_deck(&mut self, verbs: Vec<Verb>) {
+        let mut known: HashSet<String> = self
+            .verbs
+            .iter()
+            .map(|verb| infinitive_key(&verb.infinitive))
+            .collect();
+        let total = verbs.len();
+        let before = self.verbs.len();
+        let mut position = self
+            .verbs
+            .iter()
+            .map(|verb| verb.position + 1)
+            .max()
+            .unwrap_or(0);
+        for mut verb in verbs {
+            if known.insert(infinitive_key(&verb.infinitive)) {
+                verb.position = position;
+                position += 1;
+                self.verbs.push(verb);
+            }
+        }
+        let added = self.verbs.len() - before;
+        self.confusion = crate::confusion::ConfusionIndex::new(&self.verbs);
+        self.deck_revision += 1;
+
+        let mut message = format!(
+            "Added {} verb{} to the deck",
+            added,
+            if added == 1 { "" } else { "s" }
+        );
+        if added < total {
+            message += &format!(", {} were already in it", total - added);
+        }
+        if let Err(e) = self.write_deck() {
+            message += &format!(", but couldn't save the deck: {}", e);
+        }
+        self.csv_message = Some(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tab_and_comma_separated_rows() {
+        let text = "Infinitive\tPresent\tPast\tPast participle\tEnglish\n\
+                    at være\ter\tvar\tværet\tto be\n\
+                    \n\
+                    at have, har, havde, haft, to have\n";
+        let import = parse_rows(text);
+        assert!(import.skipped.is_empty());
+        assert_eq!(import.verbs.len(), 2);
+        assert_eq!(import.verbs[0].past_participle, "været");
+        assert_eq!(import.verbs[1].english, "to have");
+        assert_eq!(import.verbs[1].position, 1);
+    }
+
+    #[test]
+    fn reports_skipped_rows() {
+        let text = "at gå, går, gik, gået\nat se, ser, , set, to see\nat tale\ttaler\ttalte\ttalt\tto speak";
+        let import = parse_rows(text);
+        assert_eq!(import.verbs.len(), 1);
+        assert_eq!(import.verbs[0].infinitive, "at tale");
+        assert_eq!(
+            import.skipped,
+            vec![
+                (1, "expected 5 fields, found 4".to_string()),
+                (2, "field 3 is empty".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_csv_with_its_own_column_order() {
+        let text = "\u{feff}Engelsk;Infinitiv;Nutid;Datid;Førnutid;Kapitel\r\n\
+                    \"to say; to tell\";at sige;siger;sagde;sagt;1\r\n\
+                    ;;;;;\r\n\
+                    to see;at se;ser;;set;2\r\n";
+        let table = read_csv(text);
+        assert_eq!(table.headers.len(), 6);
+        assert_eq!(
+            table.columns,
+            [
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(4),
+                None,
+                None,
+                None,
+                Some(0)
+            ]
+        );
+        let import = table.verbs();
+        assert_eq!(import.verbs.len(), 1);
+        assert_eq!(import.verbs[0].english, "to say; to tell");
+        assert_eq!(import.verbs[0].past_participle, "sagt");
+        assert_eq!(import.skipped, vec![(3, "no past".to_string())]);
+    }
+
+    #[test]
+    fn exported_csv_reads_back() {
+        let mut import = parse_rows(
+            "at sige\tsiger\tsagde\tsagt\tto say, \"tell\"\nat gå\tgår\tgik\tgået\tto go",
+        );
+        import.verbs[0].present = "siger\nsir".to_string();
+        import.verbs[0].imperative = "sig".to_string();
+        import.verbs[0].present_participle = "sigende".to_string();
+        import.verbs[1].auxiliary = "er/har".to_string();
+        let verbs: Vec<&Verb> = import.verbs.iter().collect();
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &verbs).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with(
+            "infinitive,present,past,past_participle,imperative,present_participle,auxiliary,english\n"
+        ));
+
+        let table = read_csv(&csv);
+        assert_eq!(table.columns, [0, 1, 2, 3, 4, 5, 6, 7].map(Some));
+        let read_back = table.verbs();
+        assert!(read_back.skipped.is_empty());
+        let sige = &read_back.verbs[0];
+        assert_eq!(sige.english, "to say, \"tell\"");
+        assert_eq!(sige.present, "siger\nsir");
+        assert_eq!(
+            (sige.imperative.as_str(), sige.present_participle.as_str()),
+            ("sig", "sigende")
+        );
+        assert_eq!(sige.auxiliary, "");
+        let go = &read_back.verbs[1];
+        assert_eq!(
+            (go.imperative.as_str(), go.auxiliary.as_str()),
+            ("", "er/har")
+        );
+    }
+}
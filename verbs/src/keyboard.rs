@@ -0,0 +1,192 @@
+use eframe::egui;
+
+/// A key pressed on the on-screen keyboard.
+pub enum KeyPress {
+    Char(char),
+    Backspace,
+    Enter,
+}
+
+/// Danish keyboard layout, one string per row.
+const ROWS: [&str; 4] = ["1234567890", "qwertyuiopå", "asdfghjklæø", "zxcvbnm-"];
+
+const KEY_SIZE: f32 = 44.0;
+
+/// The Danish letters most other keyboards lack, with the key that types each with Alt.
+pub const DANISH_LETTERS: [(char, egui::Key); 3] = [
+    ('æ', egui::Key::E),
+    ('ø', egui::Key::O),
+    ('å', egui::Key::A),
+];
+
+/// Buttons for the Danish letters, returning the letter clicked this frame.
+pub fn show_letter_buttons(ui: &mut egui::Ui, font: &egui::FontId) -> Option<char> {
+    let mut clicked = None;
+    for (letter, key) in DANISH_LETTERS {
+        let button = ui
+            .add(egui::Button::new(
+                egui::RichText::new(letter.to_string()).font(font.clone()),
+            ))
+            .on_hover_text(format!("Type {letter} (Alt+{})", key.name()));
+        if button.clicked() {
+            clicked = Some(letter);
+        }
+    }
+    clicked
+}
+
+/// The Danish letter typed with Alt this frame, uppercase with Shift as well.
+///
+/// Keyboards that already type a character for the combination, like Option+A for "å" on a
+/// Mac, are left to do so.
+pub fn letter_shortcut(ctx: &egui::Context) -> Option<char> {
+    ctx.input(|input| {
+        if input
+            .events
+            .iter()
+            .any(|event| matches!(event, egui::Event::Text(_)))
+        {
+            return None;
+        }
+        input.events.iter().find_map(|event| match event {
+            egui::Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } if modifiers.alt && !modifiers.command && !modifiers.ctrl => DANISH_LETTERS
+                .iter()
+                .find(|(_, letter_key)| letter_key == key)
+                .map(|&(letter, _)| {
+                    if modifiers.shift {
+                        letter.to_uppercase().next().unwrap_or(letter)
+                    } else {
+                        letter
+                    }
+                }),
+            _ => None,
+        })
+    })
+}
+
+/// Types `letter` into the text field with the given id, replacing its selection, and
+/// moves the cursor on past it. Without a cursor the letter goes at the end.
+pub fn insert_at_cursor(ctx: &egui::Context, id: egui::Id, text: &mut String, letter: char) {
+    let mut state = egui::text_edit::TextEditState::load(ctx, id).unwrap_or_default();
+    let length = text.chars().count();
+    let [start, end] = state
+        .cursor
+        .char_range()
+        .map_or([length; 2], |range| {
+            range.sorted().map(|cursor| cursor.index)
+        })
+        .map(|index| index.min(length));
+    let byte = |index: usize| {
+        text.char_indices()
+            .nth(index)
+            .map_or(text.len(), |(b, _)| b)
+    };
+    let range = byte(start)..byte(end);
+    text.replace_range(range, &letter.to_string());
+
+    let cursor = egui::text::CCursor::new(start + 1);
+    state
+        .cursor
+        .set_char_range(Some(egui::text::CCursorRange::one(cursor)));
+    state.store(ctx, id);
+}
+
+/// Draws a Danish on-screen keyboard and returns the key that was pressed this frame.
+pub fn show(
+    ui: &mut egui::Ui,
+    font: &egui::FontId,
+    accent_color: egui::Color32,
+) -> Option<KeyPress> {
+    let mut pressed = None;
+    let key = |text: &str| {
+        egui::Button::new(egui::RichText::new(text).font(font.clone())).corner_radius(6.0)
+    };
+
+    ui.vertical_centered(|ui| {
+        ui.spacing_mut().item_spacing = egui::vec2(4.0, 4.0);
+
+        for row in ROWS {
+            ui.horizontal(|ui| {
+                for ch in row.chars() {
+                    if ui
+                        .add_sized([KEY_SIZE, KEY_SIZE], key(&ch.to_string()))
+                        .clicked()
+                    {
+                        pressed = Some(KeyPress::Char(ch));
+                    }
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_sized([KEY_SIZE * 2.0, KEY_SIZE], key("Backspace"))
+                .clicked()
+            {
+                pressed = Some(KeyPress::Backspace);
+            }
+            if ui
+                .add_sized([KEY_SIZE * 5.0, KEY_SIZE], key("space"))
+                .clicked()
+            {
+                pressed = Some(KeyPress::Char(' '));
+            }
+            let enter = egui::Button::new(
+                egui::RichText::new("Enter")
+                    .font(font.clone())
+                    .color(egui::Color32::WHITE),
+            )
+            .fill(accent_color)
+            .corner_radius(6.0);
+            if ui.add_sized([KEY_SIZE * 2.0, KEY_SIZE], enter).clicked() {
+                pressed = Some(KeyPress::Enter);
+            }
+        });
+    });
+
+    pressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_letters_at_the_cursor() {
+        let ctx = egui::Context::default();
+        let id = egui::Id::new("answer");
+        let mut text = "blbr".to_string();
+        // Without a cursor the letter goes at the end
+        insert_at_cursor(&ctx, id, &mut text, 'å');
+        assert_eq!(text, "blbrå");
+
+        let mut state = egui::text_edit::TextEditState::default();
+        state
+            .cursor
+            .set_char_range(Some(egui::text::CCursorRange::one(
+                egui::text::CCursor::new(2),
+            )));
+        state.store(&ctx, id);
+        insert_at_cursor(&ctx, id, &mut text, 'å');
+        insert_at_cursor(&ctx, id, &mut text, 'b');
+        assert_eq!(text, "blåbbrå");
+
+        // A selection is replaced, here the "ae" of "laese"
+        let mut text = "laese".to_string();
+        let mut state = egui::text_edit::TextEditState::default();
+        state
+            .cursor
+            .set_char_range(Some(egui::text::CCursorRange::two(
+                egui::text::CCursor::new(3),
+                egui::text::CCursor::new(1),
+            )));
+        state.store(&ctx, id);
+        insert_at_cursor(&ctx, id, &mut text, 'æ');
+        assert_eq!(text, "læse");
+    }
+}
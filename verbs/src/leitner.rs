@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+/// Number of Leitner boxes. Every verb starts in the first.
+pub const BOXES: u8 = 5;
+
+/// The box a verb is in, from 1 to `BOXES`. Verbs never answered are in the first.
+pub fn box_of(boxes: &HashMap<String, u8>, infinitive: &str) -> u8 {
+    boxes.get(infinitive).copied().unwrap_or(1).clamp(1, BOXES)
+}
+
+/// The box a verb moves to after an answer: up one when it's right, back to the first
+/// when it's wrong.
+pub fn next_box(current: u8, correct: bool) -> u8 {
+    if correct { (current + 1).min(BOXES) } else { 1 }
+}
+
+/// How often a verb is drawn compared with others. Each box is drawn from half as often
+/// as the one below it.
+pub fn weight(box_number: u8) -> f64 {
+    2f64.powi(i32::from(BOXES.saturating_sub(box_number)))
+}
+
+/// How many of the verbs are in each box, first box first.
+pub fn distribution<'a>(
+    boxes: &HashMap<String, u8>,
+    infinitives: impl IntoIterator<Item = &'a str>,
+) -> [usize; BOXES as usize] {
+    let mut counts = [0; BOXES as usize];
+    for infinitive in infinitives {
+        counts[usize::from(box_of(boxes, infinitive) - 1)] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_verbs_up_one_box_and_back_to_the_first() {
+        assert_eq!(next_box(1, true), 2);
+        assert_eq!(next_box(BOXES, true), BOXES);
+        assert_eq!(next_box(4, false), 1);
+        assert!(weight(1) > weight(2));
+        assert_eq!(weight(BOXES), 1.0);
+
+        let boxes = HashMap::from([("at gå".to_string(), 3), ("at se".to_string(), 9)]);
+        assert_eq!(box_of(&boxes, "at se"), BOXES);
+        assert_eq!(
+            distribution(&boxes, ["at gå", "at se", "at tage"]),
+            [1, 0, 1, 0, 1]
+        );
+    }
+}
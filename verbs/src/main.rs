@@ -1,9 +1,49 @@
 use eframe::egui;
+use rand::Rng;
 use rand::prelude::*;
-use rand::{Rng, random};
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+mod accessibility;
+mod anki;
+mod browse;
+mod cache;
+mod confusion;
+mod conjugator;
+mod cram;
+mod deck;
+mod editor;
+mod grading;
+mod hint;
+mod import;
+mod keyboard;
+mod leitner;
+mod merge;
+mod multiple_choice;
+mod onboarding;
+mod pattern;
+mod pool;
+mod report;
+mod schedule;
+#[cfg(feature = "serve")]
+mod server;
+mod settings;
+mod speech;
+mod state;
+mod stats;
+mod streak;
+mod theme;
+mod verb_list;
+
+use deck::DeckInfo;
+use pattern::{AnswerPattern, AnswerPatterns};
+use settings::Settings;
+use state::{AppState, SavedQuestion};
+use stats::{AttemptEvent, Stats};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 struct Verb {
@@ -11,137 +51,3867 @@ struct Verb {
     present: String,
     past: String,
     past_participle: String,
+    /// Command form, e.g. "spis". Empty for verbs without one, which are never asked for it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    imperative: String,
+    /// e.g. "spisende". Empty for verbs without one, which are never asked for it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    present_participle: String,
+    /// Auxiliary of the perfect tense, "har" or "er", or "er/har" for verbs that take
+    /// either. Empty means "har", which most verbs take
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    auxiliary: String,
+    /// Every accepted meaning, separated by "/" or "|", e.g. "to learn/teach"
+    #[serde(deserialize_with = "deck::deserialize_meanings")]
     english: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    ipa: String,
+    /// Present tense as a full phrase with its subject, e.g. "jeg spiser"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    present_phrase: String,
+    /// The user's own memory hook, shown once the question is graded
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    note: String,
+    /// How common the verb is, as a relative weight. Zero when the deck doesn't say
+    #[serde(default, skip_serializing_if = "is_unset")]
+    frequency: f32,
+    /// Infinitives of verbs that are easily mixed up with this one, e.g. "at ligge"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    confusables: Vec<String>,
+    /// Patterns accepted next to the plain fields, e.g. `{"past": "lå|lagde"}`
+    #[serde(default, skip_serializing_if = "AnswerPatterns::is_empty")]
+    answer_pattern: AnswerPatterns,
+    /// The verb's conjugation group, for verbs the rules would put in the wrong one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group: Option<conjugator::Group>,
+    /// Position of the verb in the deck file
+    #[serde(skip)]
+    position: usize,
+}
+
+fn is_unset(frequency: &f32) -> bool {
+    *frequency <= 0.0
+}
+
+impl Verb {
+    fn form(&self, form: ConjugationForm) -> &str {
+        match form {
+            ConjugationForm::Present => &self.present,
+            ConjugationForm::Past => &self.past,
+            ConjugationForm::PastParticiple => &self.past_participle,
+            ConjugationForm::Imperative => &self.imperative,
+            ConjugationForm::PresentParticiple => &self.present_participle,
+        }
+    }
+
+    /// The infinitive and every form an all forms question asks for, as shown when it's
+    /// missed.
+    fn all_forms(&self) -> String {
+        format!(
+            "{}, {}, {}, {}",
+            self.infinitive,
+            grading::format_variants(&self.present),
+            grading::format_variants(&self.past),
+            grading::format_variants(&self.past_participle)
+        )
+    }
+
+    /// The auxiliary of the perfect tense, with variants separated by "/".
+    fn auxiliary(&self) -> &str {
+        if self.auxiliary.trim().is_empty() {
+            "har"
+        } else {
+            &self.auxiliary
+        }
+    }
+
+    /// Whether the verb has the form, as the imperative and present participle are optional.
+    fn has_form(&self, form: ConjugationForm) -> bool {
+        !self.form(form).trim().is_empty()
+    }
+
+    /// The verb's other forms that share a spelling with `form`, e.g. the past tense of a
+    /// verb whose present and past are both "sætter".
+    fn forms_spelled_like(&self, form: ConjugationForm) -> Vec<ConjugationForm> {
+        let spellings = |form| -> Vec<String> {
+            grading::variants(self.form(form))
+                .into_iter()
+                .map(grading::normalize)
+                .collect()
+        };
+        let asked = spellings(form);
+        ConjugationForm::ALL
+            .into_iter()
+            .filter(|&other| {
+                other != form && spellings(other).iter().any(|other| asked.contains(other))
+            })
+            .collect()
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 enum PracticeMode {
     Translation,
+    ReverseTranslation,
+    MultipleChoice,
     Conjugation,
+    /// The English meaning is shown and the infinitive and every form are typed
+    AllForms,
+    /// The infinitive is only read aloud and typed as heard
+    Dictation,
+    /// The perfect tense with its auxiliary, e.g. "jeg er gået"
+    Perfect,
+}
+
+impl PracticeMode {
+    const ALL: [PracticeMode; 7] = [
+        PracticeMode::Translation,
+        PracticeMode::ReverseTranslation,
+        PracticeMode::MultipleChoice,
+        PracticeMode::Conjugation,
+        PracticeMode::AllForms,
+        PracticeMode::Dictation,
+        PracticeMode::Perfect,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            PracticeMode::Translation => "Translation",
+            PracticeMode::ReverseTranslation => "Reverse translation",
+            PracticeMode::MultipleChoice => "Multiple choice",
+            PracticeMode::Conjugation => "Conjugation",
+            PracticeMode::AllForms => "All forms",
+            PracticeMode::Dictation => "Dictation",
+            PracticeMode::Perfect => "Perfect tense",
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 enum ConjugationForm {
     Present,
     Past,
     PastParticiple,
+    Imperative,
+    PresentParticiple,
+}
+
+impl ConjugationForm {
+    const ALL: [ConjugationForm; 5] = [
+        ConjugationForm::Present,
+        ConjugationForm::Past,
+        ConjugationForm::PastParticiple,
+        ConjugationForm::Imperative,
+        ConjugationForm::PresentParticiple,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ConjugationForm::Present => "present tense",
+            ConjugationForm::Past => "past tense",
+            ConjugationForm::PastParticiple => "past participle",
+            ConjugationForm::Imperative => "imperative",
+            ConjugationForm::PresentParticiple => "present participle",
+        }
+    }
+}
+
+const APP_TITLE: &str = "Danish Verbs Practice";
+/// The deck built into the app, used until the user has a deck of their own
+const BUILT_IN_DECK: &str = include_str!("verbs.json");
+/// Where the deck is kept if there's no configuration directory, relative to the working
+/// directory
+const DEFAULT_DECK_PATH: &str = "src/verbs.json";
+/// How long a picked multiple choice option stays outlined
+const CHOICE_HIGHLIGHT: Duration = Duration::from_millis(400);
+/// Below this width the answer label goes above the answer field instead of beside it
+const NARROW_LAYOUT_WIDTH: f32 = 520.0;
+/// The answer field never gets narrower than this
+const MIN_ANSWER_WIDTH: f32 = 120.0;
+/// Verbs listed under "Most improved" and "Slipped" when a session ends
+const SESSION_CHANGES_SHOWN: usize = 3;
+/// Share of its usual selection weight a verb keeps once marked as learned
+const LEARNED_WEIGHT: f64 = 0.05;
+
+/// A single kind of question asked over and over, instead of a random mix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Drill {
+    Translation,
+    /// Translations from English, answered with the infinitive
+    ReverseTranslation,
+    /// Translations picked from four options, for verbs that are still new
+    MultipleChoice,
+    Form(ConjugationForm),
+    /// The verbs of a confusable group in turn, asked from the English and graded strictly
+    Confusables,
+    Perfect,
+}
+
+/// Answers of a kind needed before it can be picked as the weakest area to drill.
+const WEAKEST_MIN_ATTEMPTS: usize = 10;
+
+impl Drill {
+    const ALL: [Drill; 10] = [
+        Drill::Form(ConjugationForm::Present),
+        Drill::Form(ConjugationForm::Past),
+        Drill::Form(ConjugationForm::PastParticiple),
+        Drill::Form(ConjugationForm::Imperative),
+        Drill::Form(ConjugationForm::PresentParticiple),
+        Drill::Perfect,
+        Drill::Translation,
+        Drill::ReverseTranslation,
+        Drill::MultipleChoice,
+        Drill::Confusables,
+    ];
+
+    fn label(self, info: &DeckInfo) -> &str {
+        match self {
+            Drill::Translation => "translation",
+            Drill::ReverseTranslation => "reverse translation",
+            Drill::MultipleChoice => "multiple choice",
+            Drill::Form(form) => info.form_name(form),
+            Drill::Confusables => "confusable verbs",
+            Drill::Perfect => "perfect tense",
+        }
+    }
+}
+
+/// Column the verb table in the statistics or the verb list is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VerbSort {
+    Name,
+    /// By meaning, leaving out the leading "to"
+    English,
+    Accuracy,
+    /// Least recently practiced first, never practiced verbs at the top
+    LastSeen,
+}
+
+/// The screen shown in the main panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Practice,
+    Browse,
+    /// Every verb in a table
+    Verbs,
 }
 
 struct DanishVerbsApp {
+    view: View,
+    /// Verb shown in the browse view
+    browse_index: usize,
+    browse_search: String,
+    verb_list_search: String,
+    verb_list_sort: VerbSort,
+    /// Verb to ask next, picked from the verb list
+    requested_verb: Option<String>,
     verbs: Vec<Verb>,
+    /// Lookup from answers to the verbs they belong to, rebuilt when the verbs change
+    confusion: confusion::ConfusionIndex,
+    /// Bumped whenever the verbs or their order change, so cached figures are redone
+    deck_revision: u64,
+    deck_cache: RefCell<cache::DeckCache>,
+    /// Infinitives of the confusable group still to be asked in the confusables drill
+    confusable_queue: Vec<String>,
+    /// Deck file given on the command line, `None` for the default deck
+    deck_path: Option<PathBuf>,
+    /// Whether the deck was read from a file, rather than being the built-in list
+    deck_from_file: bool,
+    /// Language and form names declared by the deck
+    deck_info: DeckInfo,
+    /// Why the deck couldn't be loaded, shown above the question
+    load_error: Option<String>,
+    /// Problem with the deck file that didn't stop it from loading
+    load_warning: Option<String>,
+    /// Why the last attempt to save the state failed
+    save_error: Option<String>,
+    /// When progress is next saved after an answer
+    autosave: state::Autosave,
+    /// Outcome of the last restore from a backup
+    backup_message: Option<String>,
     current_verb_index: usize,
     practice_mode: PracticeMode,
     conjugation_form: ConjugationForm,
+    /// The conjugation question gives the form and asks for the infinitive
+    conjugation_flipped: bool,
     user_answer: String,
+    /// Options offered in multiple choice mode, in display order
+    choices: Vec<String>,
+    /// The option that was just picked and when, to briefly outline it
+    choice_highlight: Option<(usize, Instant)>,
     result_message: String,
     show_result: bool,
+    attempts_used: u32,
+    answered: bool,
+    /// Whether the correct answer was uncovered, or had nothing to hide
+    answer_revealed: bool,
+    /// When the current question was put on screen
+    question_shown_at: Instant,
+    /// Character differences between a wrong answer and the correct one
+    answer_diff: Vec<grading::DiffChar>,
+    /// Infinitive and conjugated forms typed in an all forms question
+    form_answers: [String; 4],
+    /// Which of the all forms fields were right, once checked
+    form_results: Option<[bool; 4]>,
+    /// The all forms field the on-screen keyboard types into
+    active_form_field: usize,
+    /// Put the cursor in the answer field, or the first all forms field, on the next frame
+    focus_answer: bool,
+    /// Move the focus to "Next verb" on the next frame, once a question is over
+    focus_next_button: bool,
+    /// How much of the answer the hint button or the auto-hint has given away, up to
+    /// `hint::LEVELS`
+    hint_level: u8,
+    /// The question is one of the first translations asked about a new verb
+    introducing: bool,
+    /// The verb's forms are on show in teach mode, before the question on them is asked
+    teaching: bool,
+    /// Infinitives of the verbs taught in teach mode this session
+    taught_verbs: HashSet<String>,
+    /// Number of verbs taught in the session that was just ended
+    session_taught: usize,
+    settings: Settings,
+    streak: streak::Streak,
+    /// Length of the streak that ran out before this launch, until practice starts a new one
+    streak_lost: Option<u32>,
+    /// Best session accuracy in percent, keyed by deck path
+    best_accuracy: HashMap<String, f32>,
+    /// When the first answer of the session was graded
+    session_started_at: Option<Instant>,
+    /// Result of the session that was just ended, with how it compares to the best
+    session_summary: Option<(stats::Tally, stats::BestComparison)>,
+    /// Verbs of the ended session that improved most and those that slipped most
+    session_changes: (Vec<stats::VerbChange>, Vec<stats::VerbChange>),
+    /// The CSV file last imported from or exported to
+    csv_path: Option<PathBuf>,
+    /// A CSV file being imported, while its columns are matched to the verb fields
+    csv_import: Option<import::CsvTable>,
+    /// Outcome of the last CSV import or export
+    csv_message: Option<String>,
+    anki_message: Option<String>,
+    /// Outcome of the last report export
+    report_message: Option<String>,
+    /// Active verbs asked so far in the current pass through the deck
+    pass_seen: HashSet<String>,
+    passes_completed: u32,
+    /// The deck was finished and is waiting for the user to start another pass
+    pass_finished: bool,
+    /// Whether the verb details were open after the last graded question
+    details_open: bool,
+    /// Open or close the verb details to match the question state on the next frame
+    sync_details: bool,
+    /// Outcome of the last attempt to save a note to the deck file
+    note_message: Option<String>,
+    /// Outcome of saving the deck order, or a reminder that it isn't saved yet
+    order_message: Option<String>,
+    /// The verb being typed into the editor in the browse view
+    verb_draft: editor::VerbDraft,
+    /// Outcome of the last verb added, edited or deleted with the editor
+    editor_message: Option<String>,
+    /// Open the editor on the next frame, after a verb was picked to edit
+    open_verb_editor: bool,
+    /// Delete was pressed for the verb being edited, and waits to be confirmed
+    confirm_delete: bool,
+    /// Infinitives of verbs the user has starred
+    starred_verbs: HashSet<String>,
+    /// Infinitives of verbs the user has marked as learned
+    learned_verbs: HashSet<String>,
+    /// When each verb answered so far is next due for review
+    schedule: HashMap<String, schedule::Review>,
+    /// The Leitner box each verb answered so far is in
+    leitner_boxes: HashMap<String, u8>,
+    /// Each answer's verb with its review, Leitner box and the streak from before the
+    /// answer, to undo answers with
+    review_history: Vec<(String, Option<schedule::Review>, Option<u8>, streak::Streak)>,
+    /// Why the last attempt to pronounce a verb failed
+    speech_error: Option<String>,
+    /// Whether the speech command can read the deck's language, once checked
+    voice_available: Option<bool>,
+    /// Show the keyboard shortcuts window
+    show_shortcuts: bool,
+    /// Whether the settings are open in a window of their own
+    settings_window_open: bool,
+    /// The walkthrough, while it's on screen
+    onboarding: Option<onboarding::Onboarding>,
+    /// The walkthrough was finished or turned off, so it isn't shown on launch
+    seen_onboarding: bool,
+    /// The deck's verbs, put aside while a temporary session from the clipboard or merged
+    /// decks runs
+    saved_deck: Option<Vec<Verb>>,
+    /// Where the verbs of the temporary session came from, e.g. "the clipboard"
+    temporary_source: String,
+    /// The window title last sent to the viewport
+    shown_title: String,
+    /// Outcome of the last clipboard import
+    import_message: Option<String>,
+    /// Deck files to merge, one per line
+    merge_paths: String,
+    merge_conflict: merge::Conflict,
+    /// Outcome of the last merge
+    merge_message: Option<String>,
+    verb_sort: VerbSort,
+    /// The quick drill in progress, if any
+    drill: Option<Drill>,
+    /// Why the drill was picked, when it was picked as the weakest area
+    drill_reason: Option<String>,
+    /// The running cram session, if any
+    cram: Option<cram::CramSession>,
+    /// The random pool drilled this session, if any
+    pool: Option<pool::PoolSession>,
+    /// Size of the pool the session that was just ended cleared, if it did
+    session_pool_cleared: Option<usize>,
+    /// Typing speed of the last session, in words per minute
+    session_typing_speed: Option<f64>,
+    /// Results of the last session per kind of question, for the kinds that were asked
+    session_modes: Vec<(PracticeMode, stats::Tally)>,
+    /// The verbs answered slowest in the last session, with their average response time
+    session_slowest: Vec<(String, Duration)>,
+    /// Wrong answers of the last session, offered to be retried
+    session_mistakes: Vec<stats::AttemptEvent>,
+    /// Infinitives of verbs excluded from practice
+    disabled_verbs: HashSet<String>,
+    stats: Stats,
+    /// Stats shared with the HTTP server, when it's running
+    #[cfg(feature = "serve")]
+    stats_snapshot: Option<server::SharedSnapshot>,
     fonts_loaded: bool,
     heading_font: Option<egui::FontId>,
     body_font: Option<egui::FontId>,
-    accent_color: egui::Color32,
-    background_color: egui::Color32,
-    text_color: egui::Color32,
+    theme: theme::Theme,
 }
 
 impl DanishVerbsApp {
-    fn new() -> Self {
-        let mut verbs = load_verbs();
-        let mut rng = rand::rng();
-        verbs.shuffle(&mut rng);
+    /// Opens the deck given on the command line, or else the user's own deck, or else the
+    /// built-in one until the user's deck is first saved.
+    fn new(deck_path: Option<PathBuf>) -> Self {
+        Self::with_state(deck_path, state::load_state())
+    }
+
+    /// Opens the deck as `new` does, picking up from the given state instead of the saved one.
+    fn with_state(deck_path: Option<PathBuf>, state: AppState) -> Self {
+        let path = deck_path.clone().unwrap_or_else(default_deck_path);
+        let deck_from_file = deck_path.is_some() || path.exists();
+        let loaded = if deck_from_file {
+            load_deck(&path)
+        } else {
+            Ok(built_in_deck())
+        };
+        // A deck that doesn't load is left alone, and the built-in verbs are practiced in a
+        // temporary session so nothing is saved over it
+        let (deck, load_error) = match loaded {
+            Ok(deck) => (deck, None),
+            Err(e) => {
+                eprintln!("{}", e);
+                (built_in_deck(), Some(e))
+            }
+        };
+        let saved_deck = load_error.as_ref().map(|_| Vec::new());
+        let (deck_info, mut verbs, load_warning) = (deck.info, deck.verbs, deck.warning);
+        if !state.settings.preserve_deck_order {
+            let mut rng = rand::rng();
+            verbs.shuffle(&mut rng);
+        }
+
+        let mut streak = state.streak;
+        let streak_lost = streak.check(streak::today());
 
-        Self {
+        let mut settings = state.settings;
+        settings.grading.case_folding = grading::CaseFolding::for_language(&deck_info.language);
+
+        let theme = theme::Theme::new(settings.theme, settings.colors);
+        let mut app = Self {
+            view: View::Practice,
+            browse_index: 0,
+            browse_search: String::new(),
+            verb_list_search: String::new(),
+            verb_list_sort: VerbSort::Name,
+            requested_verb: None,
+            confusion: confusion::ConfusionIndex::new(&verbs),
+            deck_revision: 0,
+            deck_cache: RefCell::default(),
+            confusable_queue: Vec::new(),
             verbs,
+            deck_path,
+            deck_from_file: deck_from_file && load_error.is_none(),
+            deck_info,
+            load_error,
+            load_warning,
+            save_error: None,
+            autosave: state::Autosave::default(),
+            backup_message: None,
             current_verb_index: 0,
             practice_mode: PracticeMode::Translation,
             conjugation_form: ConjugationForm::Present,
+            conjugation_flipped: false,
             user_answer: String::new(),
+            choices: Vec::new(),
+            choice_highlight: None,
             result_message: String::new(),
             show_result: false,
+            attempts_used: 0,
+            answered: false,
+            answer_revealed: false,
+            question_shown_at: Instant::now(),
+            answer_diff: Vec::new(),
+            form_answers: Default::default(),
+            form_results: None,
+            active_form_field: 0,
+            focus_answer: true,
+            focus_next_button: false,
+            hint_level: 0,
+            introducing: false,
+            teaching: false,
+            taught_verbs: HashSet::new(),
+            session_taught: 0,
+            settings,
+            streak,
+            streak_lost,
+            best_accuracy: state.best_accuracy,
+            session_started_at: None,
+            session_summary: None,
+            session_changes: Default::default(),
+            csv_path: None,
+            csv_import: None,
+            csv_message: None,
+            anki_message: None,
+            report_message: None,
+            pass_seen: HashSet::new(),
+            passes_completed: 0,
+            pass_finished: false,
+            details_open: false,
+            sync_details: true,
+            note_message: None,
+            order_message: None,
+            verb_draft: editor::VerbDraft::default(),
+            editor_message: None,
+            open_verb_editor: false,
+            confirm_delete: false,
+            starred_verbs: state.starred_verbs,
+            learned_verbs: state.learned_verbs,
+            schedule: state.schedule,
+            leitner_boxes: state.leitner_boxes,
+            review_history: Vec::new(),
+            speech_error: None,
+            voice_available: None,
+            show_shortcuts: false,
+            settings_window_open: false,
+            onboarding: (!state.seen_onboarding).then(onboarding::Onboarding::default),
+            seen_onboarding: state.seen_onboarding,
+            saved_deck,
+            temporary_source: "the built-in list".to_string(),
+            shown_title: String::new(),
+            import_message: None,
+            merge_paths: String::new(),
+            merge_conflict: merge::Conflict::default(),
+            merge_message: None,
+            verb_sort: VerbSort::LastSeen,
+            drill: None,
+            drill_reason: None,
+            cram: None,
+            pool: None,
+            session_pool_cleared: None,
+            session_typing_speed: None,
+            session_modes: Vec::new(),
+            session_slowest: Vec::new(),
+            session_mistakes: Vec::new(),
+            disabled_verbs: state.disabled_verbs,
+            stats: Stats::new(state.verb_stats),
+            #[cfg(feature = "serve")]
+            stats_snapshot: None,
             fonts_loaded: false,
             heading_font: None,
             body_font: None,
-            accent_color: egui::Color32::from_rgb(66, 135, 245), // Blue
-            background_color: egui::Color32::from_rgb(240, 240, 255), // Light blue-gray
-            text_color: egui::Color32::from_rgb(40, 40, 60),     // Dark blue-gray
+            theme,
+        };
+
+        // Resume the question from the last run, unless the verb left the deck
+        if let Some(question) = state.current_question
+            && let Some(index) = app
+                .verbs
+                .iter()
+                .position(|verb| verb.infinitive == question.infinitive)
+        {
+            app.current_verb_index = index;
+            app.practice_mode = question.practice_mode;
+            app.conjugation_form = question.conjugation_form;
+            app.conjugation_flipped = question.conjugation_flipped;
+            app.user_answer = question.user_answer;
+        }
+
+        if !app.is_current_verb_active() {
+            app.next_verb();
+        }
+        if let Some(verb) = app.verbs.get(app.current_verb_index) {
+            app.pass_seen.insert(verb.infinitive.clone());
         }
+        app.prepare_choices();
+
+        app
     }
 
     fn load_fonts(&mut self, ctx: &egui::Context) {
         if !self.fonts_loaded {
+            let scale = self.settings.text_size_percent.clamp(50, 300) as f32 / 100.0;
             // Define custom fonts
-            self.heading_font = Some(egui::FontId::proportional(32.0));
-            self.body_font = Some(egui::FontId::proportional(20.0));
+            self.heading_font = Some(egui::FontId::proportional(32.0 * scale));
+            self.body_font = Some(egui::FontId::proportional(20.0 * scale));
             self.fonts_loaded = true;
 
-            // Configure global Visual settings
+            // Configure global Visual settings, starting from egui's own for the theme
+            ctx.set_theme(self.theme.egui_theme());
             let mut style = (*ctx.style()).clone();
             style.text_styles = [
-                (egui::TextStyle::Heading, egui::FontId::proportional(32.0)),
-                (egui::TextStyle::Body, egui::FontId::proportional(20.0)),
-                (egui::TextStyle::Monospace, egui::FontId::monospace(18.0)),
-                (egui::TextStyle::Button, egui::FontId::proportional(20.0)),
-                (egui::TextStyle::Small, egui::FontId::proportional(16.0)),
+                (
+                    egui::TextStyle::Heading,
+                    egui::FontId::proportional(32.0 * scale),
+                ),
+                (
+                    egui::TextStyle::Body,
+                    egui::FontId::proportional(20.0 * scale),
+                ),
+                (
+                    egui::TextStyle::Monospace,
+                    egui::FontId::monospace(18.0 * scale),
+                ),
+                (
+                    egui::TextStyle::Button,
+                    egui::FontId::proportional(20.0 * scale),
+                ),
+                (
+                    egui::TextStyle::Small,
+                    egui::FontId::proportional(16.0 * scale),
+                ),
             ]
             .into();
             ctx.set_style(style);
         }
     }
 
-    fn next_verb(&mut self) {
-        self.current_verb_index = (self.current_verb_index + 1) % self.verbs.len();
-        self.user_answer.clear();
-        self.result_message.clear();
-        self.show_result = false;
+    /// The window title, naming the deck file or where a temporary session's verbs came
+    /// from. The built-in list gets the plain title.
+    fn window_title(&self) -> String {
+        if self.saved_deck.is_some() {
+            return format!(
+                "{} — {} ({} temporary verbs)",
+                APP_TITLE,
+                self.temporary_source,
+                self.verbs.len()
+            );
+        }
+        if !self.deck_from_file {
+            return APP_TITLE.to_string();
+        }
+        match self.deck_file().file_stem() {
+            Some(stem) => format!(
+                "{} — {} ({} verbs)",
+                APP_TITLE,
+                stem.to_string_lossy(),
+                self.verbs.len()
+            ),
+            None => APP_TITLE.to_string(),
+        }
+    }
 
-        // Randomly select practice mode and conjugation form
-        if random() {
-            self.practice_mode = PracticeMode::Translation;
+    #[cfg(feature = "serve")]
+    fn serve_stats(&mut self, port: u16) {
+        match server::start(port, &self.stats) {
+            Ok(snapshot) => {
+                eprintln!("Serving stats on http://127.0.0.1:{}/stats", port);
+                self.stats_snapshot = Some(snapshot);
+            }
+            Err(e) => eprintln!("Error starting the stats server: {}", e),
+        }
+    }
+
+    #[cfg(not(feature = "serve"))]
+    fn serve_stats(&mut self, port: u16) {
+        eprintln!(
+            "Ignoring --serve {}: built without the \"serve\" feature",
+            port
+        );
+    }
+
+    /// Fill of the framed sections, a tint of the background towards the accent color.
+    fn panel_color(&self) -> egui::Color32 {
+        self.theme.panel()
+    }
+
+    /// The share of the deck that's mastered, the long-term goal next to each session.
+    fn show_mastery(&self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        let (mastered, active) = {
+            let cache = self.deck_cache();
+            (cache.mastered, cache.active)
+        };
+        let share = mastered as f32 / active.max(1) as f32;
+        ui.label(
+            egui::RichText::new(format!("{:.0}% of the deck mastered", share * 100.0))
+                .heading()
+                .color(accent_color),
+        )
+        .on_hover_text(format!(
+            "Verbs answered at least {} times with {}% or more right, or marked as learned. \
+             Answers helped by a hint count as half right",
+            self.settings.mastery.min_attempts, self.settings.mastery.min_accuracy
+        ));
+        ui.add(
+            egui::ProgressBar::new(share)
+                .desired_width(300.0)
+                .text(format!("{}/{} verbs mastered", mastered, active)),
+        );
+        ui.add_space(10.0);
+    }
+
+    /// Paints this session's score in the top-right corner, e.g. "session: 12/15 (80%)".
+    fn paint_score_badge(&self, ui: &egui::Ui, text_color: egui::Color32) {
+        let tally = self.stats.total();
+        let text = if tally.attempts == 0 {
+            "session: 0/0".to_string()
         } else {
-            self.practice_mode = PracticeMode::Conjugation;
-            let mut rng = rand::rng();
-            let form = rng.random_range(0..=2);
-            self.conjugation_form = match form {
-                0 => ConjugationForm::Present,
-                1 => ConjugationForm::Past,
-                _ => ConjugationForm::PastParticiple,
+            format!(
+                "session: {}/{} ({:.0}%)",
+                tally.correct,
+                tally.attempts,
+                tally.accuracy()
+            )
+        };
+
+        let painter = ui.painter();
+        let galley = painter.layout_no_wrap(text, egui::FontId::proportional(12.0), text_color);
+        let rect = egui::Rect::from_min_size(
+            ui.max_rect().right_top() - egui::vec2(galley.size().x + 12.0, 0.0),
+            galley.size() + egui::vec2(12.0, 6.0),
+        );
+        painter.rect(
+            rect,
+            6.0,
+            self.panel_color(),
+            egui::Stroke::new(1.0, self.theme.accent.gamma_multiply(0.5)),
+            egui::StrokeKind::Inside,
+        );
+        painter.galley(rect.min + egui::vec2(6.0, 3.0), galley, text_color);
+    }
+
+    fn start_cram(&mut self) {
+        let active = self
+            .verbs
+            .iter()
+            .filter(|verb| self.is_active(verb))
+            .map(|verb| verb.infinitive.as_str());
+        let verbs = cram::pick_weakest(active, &self.stats, self.settings.cram_size);
+        self.cram = Some(cram::CramSession::new(verbs));
+        self.next_verb();
+    }
+
+    fn show_cram_controls(&mut self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        ui.horizontal(|ui| match &self.cram {
+            Some(cram) => {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Cram round {}: {}/{} nailed",
+                        cram.round,
+                        cram.nailed.len(),
+                        cram.verbs.len()
+                    ))
+                    .color(accent_color)
+                    .strong(),
+                );
+                if ui.small_button("Stop cramming").clicked() {
+                    self.cram = None;
+                }
+            }
+            None => {
+                if ui
+                    .small_button(format!("Cram {} weakest verbs", self.settings.cram_size))
+                    .on_hover_text("Cycle a small set quickly, moving on automatically")
+                    .clicked()
+                {
+                    self.start_cram();
+                }
+            }
+        });
+    }
+
+    fn start_pool(&mut self) {
+        let active = self
+            .verbs
+            .iter()
+            .filter(|verb| self.is_active(verb))
+            .map(|verb| verb.infinitive.as_str());
+        self.pool = Some(pool::PoolSession::draw(
+            active,
+            self.settings.pool_size,
+            &mut rand::rng(),
+        ));
+        self.next_verb();
+    }
+
+    /// Asks each of the verbs until it's answered right once, like a random pool.
+    fn retry_mistakes(&mut self, mistakes: &[stats::AttemptEvent]) {
+        let verbs: Vec<String> = stats::missed_verbs(mistakes)
+            .into_iter()
+            .filter(|infinitive| self.verbs.iter().any(|verb| &verb.infinitive == infinitive))
+            .collect();
+        if verbs.is_empty() {
+            return;
+        }
+        self.cram = None;
+        self.drill = None;
+        self.pool = Some(pool::PoolSession::of_mistakes(verbs));
+        self.next_verb();
+    }
+
+    fn show_pool_controls(&mut self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        // Cramming takes over the questions, so the pool waits until it's done
+        if self.cram.is_some() {
+            return;
+        }
+        ui.horizontal(|ui| match &self.pool {
+            Some(pool) => {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}: {}/{} cleared",
+                        if pool.mistakes {
+                            "Retrying mistakes"
+                        } else {
+                            "Random pool"
+                        },
+                        pool.cleared.len(),
+                        pool.verbs.len()
+                    ))
+                    .color(accent_color)
+                    .strong(),
+                );
+                if ui
+                    .small_button("New random pool")
+                    .on_hover_text("Draw a different set of verbs")
+                    .clicked()
+                {
+                    self.start_pool();
+                }
+                if ui.small_button("Stop pool").clicked() {
+                    self.pool = None;
+                }
+            }
+            None => {
+                if ui
+                    .small_button(format!("Random pool of {} verbs", self.settings.pool_size))
+                    .on_hover_text(
+                        "Drill a fixed random set for this session, until each is answered right once",
+                    )
+                    .clicked()
+                {
+                    self.start_pool();
+                }
+            }
+        });
+    }
+
+    /// The streak, and progress towards the daily goal if there is one.
+    fn show_streak_banner(&self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        let today = streak::today();
+        let message = if let Some(lost) = self.streak_lost {
+            Some(format!(
+                "Your {}-day streak has ended. No worries, practice today to start a new one.",
+                lost
+            ))
+        } else if self.streak.days == 0 {
+            None
+        } else if self.streak.practiced_today(today) {
+            Some(format!(
+                "🔥 {}-day streak, you've practiced today!",
+                self.streak.days
+            ))
+        } else {
+            Some(format!(
+                "🔥 {}-day streak — practice today to keep it!",
+                self.streak.days
+            ))
+        };
+        let goal = self.settings.daily_goal;
+        if message.is_none() && goal == 0 {
+            return;
+        }
+        if let Some(message) = message {
+            ui.label(egui::RichText::new(message).color(accent_color).strong());
+        }
+
+        if goal > 0 {
+            let answered = self.streak.answered_today(today);
+            let text = if answered >= goal {
+                format!("🎯 Daily goal reached: {} of {} answers", answered, goal)
+            } else {
+                format!("Daily goal: {} of {} answers", answered, goal)
             };
+            ui.add(
+                egui::ProgressBar::new((answered as f32 / goal as f32).min(1.0))
+                    .desired_width(300.0)
+                    .text(text),
+            );
         }
+        ui.add_space(10.0);
     }
 
-    fn check_answer(&mut self) {
-        let current_verb = &self.verbs[self.current_verb_index];
-        let correct_answer = match self.practice_mode {
-            PracticeMode::Translation => current_verb.english.clone(),
-            PracticeMode::Conjugation => match self.conjugation_form {
-                ConjugationForm::Present => current_verb.present.clone(),
-                ConjugationForm::Past => current_verb.past.clone(),
-                ConjugationForm::PastParticiple => current_verb.past_participle.clone(),
-            },
+    /// Starts a temporary session with verbs copied to the clipboard, keeping the deck aside.
+    fn start_clipboard_session(&mut self) {
+        let text = match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+            Ok(text) => text,
+            Err(e) => {
+                self.import_message = Some(format!("Couldn't read the clipboard: {}", e));
+                return;
+            }
         };
 
-        if self.user_answer.trim().to_lowercase() == correct_answer.trim().to_lowercase() {
-            self.result_message = "Correct! 🎉".to_string();
+        let import = import::parse_rows(&text);
+        let mut message = format!(
+            "Imported {} verb{}",
+            import.verbs.len(),
+            if import.verbs.len() == 1 { "" } else { "s" }
+        );
+        if !import.skipped.is_empty() {
+            let reasons: Vec<String> = import
+                .skipped
+                .iter()
+                .map(|(line, reason)| format!("line {}: {}", line, reason))
+                .collect();
+            message += &format!(
+                ", skipped {} ({})",
+                import.skipped.len(),
+                reasons.join("; ")
+            );
+        }
+        self.import_message = Some(message);
+        if import.verbs.is_empty() {
+            return;
+        }
+
+        self.start_temporary_session(import.verbs, "the clipboard".to_string());
+    }
+
+    /// Practices `verbs` instead of the deck until the session is left, keeping the deck aside.
+    fn start_temporary_session(&mut self, verbs: Vec<Verb>, source: String) {
+        let deck = std::mem::replace(&mut self.verbs, verbs);
+        if self.saved_deck.is_none() {
+            self.saved_deck = Some(deck);
+        }
+        self.temporary_source = source;
+        self.restart_with_verbs();
+    }
+
+    /// Goes back to the deck, unless it didn't load and there's none to go back to.
+    fn end_clipboard_session(&mut self) {
+        if self.load_error.is_some() {
+            return;
+        }
+        if let Some(deck) = self.saved_deck.take() {
+            self.verbs = deck;
+            self.import_message = None;
+            self.restart_with_verbs();
+        }
+    }
+
+    /// Starts over on the first verb after the verbs were swapped out.
+    fn restart_with_verbs(&mut self) {
+        self.confusion = confusion::ConfusionIndex::new(&self.verbs);
+        self.deck_revision += 1;
+        self.cram = None;
+        self.pool = None;
+        self.pass_seen.clear();
+        self.browse_index = 0;
+        self.current_verb_index = self.verbs.len().saturating_sub(1);
+        self.next_verb();
+    }
+
+    /// The file the deck is saved to.
+    fn deck_file(&self) -> PathBuf {
+        self.deck_path.clone().unwrap_or_else(default_deck_path)
+    }
+
+    fn save_note(&mut self) {
+        self.note_message = Some(match self.write_deck() {
+            Ok(()) => "Note saved to the deck".to_string(),
+            Err(e) => format!("Couldn't save the note: {}", e),
+        });
+    }
+
+    /// Moves a verb within the deck order, keeping the current and browsed verbs in place.
+    fn move_verb(&mut self, from: usize, to: usize) {
+        let browsed = self
+            .verbs
+            .get(self.browse_index)
+            .map(|verb| verb.infinitive.clone());
+        deck::move_verb(&mut self.verbs, from, to);
+        self.deck_revision += 1;
+        if self.settings.preserve_deck_order {
+            self.apply_deck_order();
+        }
+        if let Some(infinitive) = browsed
+            && let Some(index) = self
+                .verbs
+                .iter()
+                .position(|verb| verb.infinitive == infinitive)
+        {
+            self.browse_index = index;
+        }
+        self.order_message = Some("Order changed, not saved to the deck yet".to_string());
+    }
+
+    fn save_deck_order(&mut self) {
+        self.order_message = Some(match self.write_deck() {
+            Ok(()) => "Order saved to the deck".to_string(),
+            Err(e) => format!("Couldn't save the order: {}", e),
+        });
+    }
+
+    /// Limits practice to a stretch of the deck, e.g. the verbs of one textbook chapter.
+    /// Returns true if anything changed.
+    fn show_range_settings(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = ui
+            .checkbox(
+                &mut self.settings.limit_to_range,
+                "Only practice a range of the deck",
+            )
+            .on_hover_text("Verbs are counted in deck file order")
+            .changed();
+        if !self.settings.limit_to_range {
+            return changed;
+        }
+
+        let count = self.verbs.len().max(1);
+        ui.horizontal(|ui| {
+            ui.label("Verbs");
+            changed |= ui
+                .add(egui::DragValue::new(&mut self.settings.range_start).range(1..=count))
+                .changed();
+            ui.label("to");
+            changed |= ui
+                .add(egui::DragValue::new(&mut self.settings.range_end).range(1..=count))
+                .changed();
+        });
+
+        let in_range = self.deck_range().map_or(0, |range| range.clone().count());
+        let readout = if in_range == 0 {
+            "The range is empty; the first verb must come before the last".to_string()
+        } else {
+            format!("{} verbs in the range", in_range)
+        };
+        ui.label(egui::RichText::new(readout).small());
+        changed
+    }
+
+    /// Grading presets with the individual options below them. Returns true if anything changed.
+    fn show_grading_settings(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Grading:");
+            for preset in grading::GradingPreset::ALL {
+                // Custom can't be picked, it's where hand-tweaked options end up
+                let Some(options) = preset.options() else {
+                    if self.settings.grading_preset == preset {
+                        ui.add_enabled(false, egui::SelectableLabel::new(true, preset.label()));
+                    }
+                    continue;
+                };
+                if ui
+                    .selectable_label(self.settings.grading_preset == preset, preset.label())
+                    .clicked()
+                {
+                    self.settings.grading = grading::GradingOptions {
+                        case_folding: self.settings.grading.case_folding,
+                        ..options
+                    };
+                    self.settings.grading_preset = preset;
+                    changed = true;
+                }
+            }
+        });
+
+        let grading = &mut self.settings.grading;
+        let mut tweaked = false;
+        ui.indent("grading_options", |ui| {
+            for (value, label) in [
+                (&mut grading.case_sensitive, "Case sensitive"),
+                (&mut grading.ignore_punctuation, "Ignore punctuation"),
+                (
+                    &mut grading.fold_diacritics,
+                    "Accept letters without accents, and ae, oe, aa for æ, ø, å",
+                ),
+                (
+                    &mut grading.typo_tolerance,
+                    "Allow one typo in words of five letters or more",
+                ),
+                (
+                    &mut grading.optional_infinitive_marker,
+                    "Accept infinitives with or without \"at\"",
+                ),
+                (
+                    &mut grading.optional_english_to,
+                    "Accept English meanings with or without \"to\"",
+                ),
+                (
+                    &mut grading.ignore_reflexive_sig,
+                    "Accept reflexive verbs with or without \"sig\"",
+                ),
+                (
+                    &mut grading.accept_subject_pronoun,
+                    "Accept conjugations with a pronoun in front (\"han spiser\")",
+                ),
+            ] {
+                tweaked |= ui.checkbox(value, label).changed();
+            }
+        });
+
+        if tweaked {
+            self.settings.grading_preset = grading::GradingPreset::matching(&self.settings.grading);
+            changed = true;
+        }
+        changed |= ui
+            .add(
+                egui::Slider::new(&mut self.settings.near_miss_distance, 0..=3)
+                    .text("letters off for \"almost correct\""),
+            )
+            .on_hover_text(
+                "Wrong answers this close to the right one are pointed out as spelling \
+                 mistakes. They still count as wrong. 0 turns this off",
+            )
+            .changed();
+        changed
+    }
+
+    /// Hearing the infinitive would give the answer away in the modes that ask for it.
+    /// True if the question gives a form of the verb and asks for its infinitive.
+    fn asks_infinitive_of_form(&self) -> bool {
+        self.practice_mode == PracticeMode::Conjugation && self.conjugation_flipped
+    }
+
+    fn can_pronounce(&self) -> bool {
+        let asks_for_infinitive = matches!(
+            self.practice_mode,
+            PracticeMode::ReverseTranslation | PracticeMode::AllForms
+        ) || self.practice_mode == PracticeMode::Dictation
+            || self.asks_infinitive_of_form();
+        self.is_current_verb_active() && (self.answered || !asks_for_infinitive)
+    }
+
+    /// Dictation needs a voice for the deck's language, which is looked up on first use.
+    fn can_dictate(&mut self) -> bool {
+        let language = &self.deck_info.language;
+        *self
+            .voice_available
+            .get_or_insert_with(|| speech::has_voice(language))
+    }
+
+    fn pronounce(&mut self) {
+        let Some(verb) = self.verbs.get(self.current_verb_index) else {
+            return;
+        };
+        self.speech_error = speech::speak(&verb.infinitive, &self.deck_info.language)
+            .err()
+            .map(|e| format!("Couldn't pronounce the verb: {}", e));
+    }
+
+    fn toggle_star(&mut self) {
+        let Some(verb) = self.verbs.get(self.current_verb_index) else {
+            return;
+        };
+        if !self.starred_verbs.remove(&verb.infinitive) {
+            self.starred_verbs.insert(verb.infinitive.clone());
+        }
+        self.save_state();
+    }
+
+    /// Marks the verb as learned, or back to being practiced normally.
+    fn toggle_learned(&mut self, infinitive: &str) {
+        if !self.learned_verbs.remove(infinitive) {
+            self.learned_verbs.insert(infinitive.to_string());
+        }
+        self.save_state();
+    }
+
+    /// Keyboard shortcuts of the practice view, so a question can be answered without the mouse.
+    fn handle_practice_keys(&mut self, ctx: &egui::Context) {
+        let pressed = |key| ctx.input(|input| input.key_pressed(key));
+
+        // Esc clears the answer, and a second one leaves the field as egui does by itself
+        if pressed(egui::Key::Escape) && !self.answered {
+            let typed = !self.user_answer.is_empty()
+                || self.form_answers.iter().any(|answer| !answer.is_empty());
+            if typed {
+                self.user_answer.clear();
+                self.form_answers = Default::default();
+                self.active_form_field = 0;
+                self.focus_answer = true;
+            }
+        }
+
+        // A focused button takes Enter and Space itself
+        if ctx.memory(|memory| memory.focused().is_none()) {
+            if self.answered && (pressed(egui::Key::Enter) || pressed(egui::Key::Space)) {
+                self.next_verb();
+                return;
+            }
+            if !self.answered
+                && self.practice_mode != PracticeMode::MultipleChoice
+                && pressed(egui::Key::Enter)
+            {
+                self.check_answer();
+                return;
+            }
+        }
+
+        // Letter shortcuts would end up in the answer if a text field had focus
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        if pressed(egui::Key::N) && self.answered {
+            self.next_verb();
+            return;
+        }
+        if pressed(egui::Key::H) && !self.answered {
+            self.reveal_hint();
+        }
+        if pressed(egui::Key::P) && self.can_pronounce() {
+            self.pronounce();
+        }
+        if pressed(egui::Key::S) && self.is_current_verb_active() {
+            self.toggle_star();
+        }
+        if pressed(egui::Key::Questionmark) {
+            self.show_shortcuts = !self.show_shortcuts;
+        }
+    }
+
+    fn show_shortcuts_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Keyboard shortcuts")
+            .open(&mut self.show_shortcuts)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Letter shortcuts work while no answer field has focus.");
+                ui.add_space(6.0);
+                egui::Grid::new("shortcuts")
+                    .spacing([24.0, 6.0])
+                    .show(ui, |ui| {
+                        for (keys, action) in [
+                            ("Enter", "Check the answer, or go on once it's checked"),
+                            ("Space, N", "Go to the next verb once the answer is checked"),
+                            ("Esc", "Clear the answer, or press again to leave the field"),
+                            ("Alt+E, Alt+O, Alt+A", "Type æ, ø or å in an answer field"),
+                            ("1 – 4", "Pick a multiple choice option"),
+                            ("H", "Give away more of the answer"),
+                            ("P", "Pronounce the verb"),
+                            ("S", "Star or unstar the verb"),
+                            ("?", "Show this list"),
+                            ("Ctrl+,", "Open or close the settings"),
+                        ] {
+                            ui.strong(keys);
+                            ui.label(action);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn show_clipboard_controls(&mut self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        ui.horizontal_wrapped(|ui| {
+            if self.saved_deck.is_some() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "📋 Temporary session: {} verbs from {}, not saved to your deck",
+                        self.verbs.len(),
+                        self.temporary_source
+                    ))
+                    .color(accent_color)
+                    .strong(),
+                );
+                if self.load_error.is_none() && ui.small_button("Back to my deck").clicked() {
+                    self.end_clipboard_session();
+                }
+            } else if ui
+                .small_button("📋 Quiz from clipboard")
+                .on_hover_text(
+                    "Practice rows of infinitive, present, past, past participle and English, \
+                     separated by tabs or commas",
+                )
+                .clicked()
+            {
+                self.start_clipboard_session();
+            }
+        });
+        if let Some(import_message) = &self.import_message {
+            ui.label(egui::RichText::new(import_message).small());
+        }
+    }
+
+    fn show_drill_controls(&mut self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        ui.horizontal_wrapped(|ui| match self.drill {
+            Some(drill) => {
+                ui.label(
+                    egui::RichText::new(format!("Drilling {}", drill.label(&self.deck_info)))
+                        .color(accent_color)
+                        .strong(),
+                );
+                if let Some(reason) = &self.drill_reason {
+                    ui.label(egui::RichText::new(format!("(weakest: {})", reason)).small());
+                }
+                if ui.small_button("Stop drill").clicked() {
+                    self.drill = None;
+                    self.drill_reason = None;
+                    self.next_verb();
+                }
+            }
+            None => {
+                ui.label("Quick drill:");
+                let weakest = self.weakest_drill();
+                if ui
+                    .add_enabled(
+                        weakest.is_some(),
+                        egui::Button::new("🎯 My weakest area").small(),
+                    )
+                    .on_hover_text("Drill the kind of question you get wrong most this session")
+                    .on_disabled_hover_text(format!(
+                        "Answer at least {} questions of a kind first",
+                        WEAKEST_MIN_ATTEMPTS
+                    ))
+                    .clicked()
+                    && let Some((drill, tally)) = weakest
+                {
+                    let name = match drill {
+                        Drill::Translation => PracticeMode::Translation.label(),
+                        Drill::ReverseTranslation => PracticeMode::ReverseTranslation.label(),
+                        Drill::MultipleChoice => PracticeMode::MultipleChoice.label(),
+                        _ => drill.label(&self.deck_info),
+                    };
+                    self.drill_reason = Some(format!(
+                        "{} — {:.0}% over {} attempts",
+                        name,
+                        tally.accuracy(),
+                        tally.attempts
+                    ));
+                    self.drill = Some(drill);
+                    self.confusable_queue.clear();
+                    self.next_verb();
+                }
+                let has_confusables = !self.deck_cache().confusable_groups.is_empty();
+                for drill in Drill::ALL {
+                    let (enabled, disabled_text) = match drill {
+                        Drill::Confusables => (
+                            has_confusables,
+                            "No verbs in the deck list \"confusables\" of each other",
+                        ),
+                        Drill::Form(form) => (
+                            self.verbs.iter().any(|verb| verb.has_form(form)),
+                            "No verbs in the deck have this form",
+                        ),
+                        _ => (true, ""),
+                    };
+                    if ui
+                        .add_enabled(
+                            enabled,
+                            egui::Button::new(drill.label(&self.deck_info)).small(),
+                        )
+                        .on_disabled_hover_text(disabled_text)
+                        .clicked()
+                    {
+                        self.drill = Some(drill);
+                        self.confusable_queue.clear();
+                        self.next_verb();
+                    }
+                }
+            }
+        });
+    }
+
+    /// The drill on the kind of question answered worst this session, with its results.
+    fn weakest_drill(&self) -> Option<(Drill, stats::Tally)> {
+        let buckets = Drill::ALL.into_iter().filter_map(|drill| {
+            let tally = match drill {
+                Drill::Translation => self.stats.by_mode(PracticeMode::Translation),
+                Drill::ReverseTranslation => self.stats.by_mode(PracticeMode::ReverseTranslation),
+                Drill::MultipleChoice => self.stats.by_mode(PracticeMode::MultipleChoice),
+                Drill::Perfect => self.stats.by_mode(PracticeMode::Perfect),
+                Drill::Form(form) => self.stats.by_form(form),
+                // Not a kind of question of its own
+                Drill::Confusables => return None,
+            };
+            Some((drill, tally))
+        });
+        stats::weakest(buckets, WEAKEST_MIN_ATTEMPTS)
+    }
+
+    fn save_state(&mut self) {
+        self.autosave.saved(Instant::now());
+        let state = AppState {
+            settings: self.settings.clone(),
+            disabled_verbs: self.disabled_verbs.clone(),
+            starred_verbs: self.starred_verbs.clone(),
+            learned_verbs: self.learned_verbs.clone(),
+            schedule: self.schedule.clone(),
+            leitner_boxes: self.leitner_boxes.clone(),
+            verb_stats: self.stats.verbs.clone(),
+            current_question: self
+                .verbs
+                .get(self.current_verb_index)
+                .map(|verb| SavedQuestion {
+                    infinitive: verb.infinitive.clone(),
+                    practice_mode: self.practice_mode,
+                    conjugation_form: self.conjugation_form,
+                    conjugation_flipped: self.conjugation_flipped,
+                    user_answer: self.user_answer.clone(),
+                }),
+            streak: self.streak,
+            best_accuracy: self.best_accuracy.clone(),
+            seen_onboarding: self.seen_onboarding,
+        };
+        // Carry on without saving, but let the user know their progress isn't kept
+        self.save_error = match state::save_state(&state) {
+            Ok(()) => None,
+            Err(e) => {
+                eprintln!("Error saving state: {}", e);
+                Some(format!("Couldn't save progress: {}", e))
+            }
+        };
+    }
+
+    /// Replaces everything remembered between runs with a backup of it.
+    fn restore_backup(&mut self, backup: &state::Backup) {
+        let restored = match state::restore_backup(backup) {
+            Ok(restored) => restored,
+            Err(e) => {
+                self.backup_message = Some(format!("Couldn't restore the backup: {}", e));
+                return;
+            }
+        };
+
+        let case_folding = self.settings.grading.case_folding;
+        self.settings = restored.settings;
+        self.settings.grading.case_folding = case_folding;
+        self.theme = theme::Theme::new(self.settings.theme, self.settings.colors);
+        self.fonts_loaded = false;
+        self.disabled_verbs = restored.disabled_verbs;
+        self.starred_verbs = restored.starred_verbs;
+        self.learned_verbs = restored.learned_verbs;
+        self.schedule = restored.schedule;
+        self.leitner_boxes = restored.leitner_boxes;
+        self.review_history.clear();
+        self.stats = Stats::new(restored.verb_stats);
+        self.streak = restored.streak;
+        self.best_accuracy = restored.best_accuracy;
+        // The new stats start counting revisions afresh, so the cached figures can't tell
+        self.deck_revision += 1;
+        #[cfg(feature = "serve")]
+        if let Some(snapshot) = &self.stats_snapshot {
+            server::publish(snapshot, &self.stats);
+        }
+
+        if !self.is_current_verb_active() {
+            self.next_verb();
+        }
+        self.save_state();
+        self.backup_message = Some(format!(
+            "Restored the backup from {}",
+            stats::format_date(backup.timestamp)
+        ));
+    }
+
+    /// Puts the deck back in file order, or shuffles it, keeping the current verb.
+    fn apply_deck_order(&mut self) {
+        let Some(current) = self.verbs.get(self.current_verb_index) else {
+            return;
+        };
+        let current_position = current.position;
+
+        if self.settings.preserve_deck_order {
+            self.verbs.sort_by_key(|verb| verb.position);
         } else {
-            self.result_message = format!("Incorrect. The correct answer is: {}", correct_answer);
+            let mut rng = rand::rng();
+            self.verbs.shuffle(&mut rng);
+        }
+
+        self.current_verb_index = self
+            .verbs
+            .iter()
+            .position(|verb| verb.position == current_position)
+            .unwrap_or(0);
+    }
+
+    /// The deck file positions practice is limited to, if a range is set.
+    ///
+    /// The range is clamped to the deck, and is empty if it starts after it ends.
+    fn deck_range(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        if !self.settings.limit_to_range {
+            return None;
+        }
+        let last = self.verbs.len().saturating_sub(1);
+        let start = self.settings.range_start.saturating_sub(1).min(last);
+        let end = self.settings.range_end.saturating_sub(1).min(last);
+        Some(start..=end)
+    }
+
+    /// Verbs are practiced unless they're disabled, outside the chosen range or not in the
+    /// chosen group.
+    fn is_active(&self, verb: &Verb) -> bool {
+        !self.disabled_verbs.contains(&verb.infinitive)
+            && self
+                .deck_range()
+                .is_none_or(|range| range.contains(&verb.position))
+            && self
+                .settings
+                .only_group
+                .is_none_or(|group| conjugator::classify(verb) == group)
+    }
+
+    fn is_current_verb_active(&self) -> bool {
+        self.verbs
+            .get(self.current_verb_index)
+            .is_some_and(|verb| self.is_active(verb))
+    }
+
+    fn active_verb_count(&self) -> usize {
+        self.deck_cache().active
+    }
+
+    fn disable_current_verb(&mut self) {
+        if let Some(verb) = self.verbs.get(self.current_verb_index) {
+            self.disabled_verbs.insert(verb.infinitive.clone());
+            self.next_verb();
+            self.save_state();
+        }
+    }
+
+    /// Frequency assumed for verbs without one: the average of the deck's frequencies, so
+    /// a deck without any is weighted uniformly.
+    fn default_frequency(&self) -> f64 {
+        let given: Vec<f64> = self
+            .verbs
+            .iter()
+            .filter(|verb| !is_unset(&verb.frequency))
+            .map(|verb| f64::from(verb.frequency))
+            .collect();
+        if given.is_empty() {
+            1.0
+        } else {
+            given.iter().sum::<f64>() / given.len() as f64
+        }
+    }
+
+    /// How likely a verb is to be picked when common verbs are prioritized: its frequency
+    /// times a factor between 1 and 2 that grows as its accuracy drops, or times
+    /// [`LEARNED_WEIGHT`] once the verb is marked as learned.
+    fn selection_weight(&self, verb: &Verb, default_frequency: f64) -> f64 {
+        let frequency = if is_unset(&verb.frequency) {
+            default_frequency
+        } else {
+            f64::from(verb.frequency)
+        };
+        if self.learned_verbs.contains(&verb.infinitive) {
+            return frequency * LEARNED_WEIGHT;
+        }
+        let (correct, attempts) = self
+            .stats
+            .verbs
+            .get(&verb.infinitive)
+            .map_or((0, 0), |verb_stat| (verb_stat.correct, verb_stat.attempts));
+        let accuracy = (f64::from(correct) + 1.0) / (f64::from(attempts) + 2.0);
+        frequency * (2.0 - accuracy)
+    }
+
+    /// Decides between a new and a review verb by the configured ratio, falling back to
+    /// whichever kind is left when the other has run out.
+    fn pick_new_or_review(&self) -> Option<bool> {
+        let new = self.unseen_verb_count();
+        let review = self.active_verb_count() - new;
+        match (new, review) {
+            (0, 0) => None,
+            (0, _) => Some(false),
+            (_, 0) => Some(true),
+            _ => Some(rand::rng().random_ratio(self.settings.new_verb_percent.min(100), 100)),
+        }
+    }
+
+    /// The active verb whose review has been due the longest, other than the current one.
+    /// Ties go to the verb earlier in the deck.
+    fn next_due_verb(&self) -> Option<String> {
+        let now = stats::now();
+        self.verbs
+            .iter()
+            .enumerate()
+            .filter(|&(index, verb)| index != self.current_verb_index && self.is_active(verb))
+            .filter_map(|(_, verb)| {
+                let review = self.schedule.get(&verb.infinitive)?;
+                review.is_due(now).then_some((review.due, verb))
+            })
+            .min_by_key(|(due, _)| *due)
+            .map(|(_, verb)| verb.infinitive.clone())
+    }
+
+    /// Number of active verbs whose review is due.
+    fn due_verb_count(&self) -> usize {
+        let now = stats::now();
+        self.deck_cache()
+            .due_times
+            .partition_point(|&due| due <= now)
+    }
+
+    /// A verb is new until it's answered or marked as learned.
+    fn is_new(&self, verb: &Verb) -> bool {
+        self.stats.is_unseen(&verb.infinitive) && !self.learned_verbs.contains(&verb.infinitive)
+    }
+
+    /// Number of active verbs that are still new.
+    fn unseen_verb_count(&self) -> usize {
+        self.deck_cache().unseen
+    }
+
+    /// True once every active verb has been asked in the current pass.
+    fn is_pass_complete(&self) -> bool {
+        self.verbs
+            .iter()
+            .all(|verb| !self.is_active(verb) || self.pass_seen.contains(&verb.infinitive))
+    }
+
+    /// Counts a finished pass and starts the next, reshuffled unless the deck order is kept.
+    fn complete_pass(&mut self) {
+        self.passes_completed += 1;
+        self.pass_seen.clear();
+        if !self.settings.preserve_deck_order {
+            self.verbs.shuffle(&mut rand::rng());
+        }
+    }
+
+    /// Groups of at least two active verbs that are declared confusable.
+    fn confusable_groups(&self) -> Vec<Vec<String>> {
+        self.deck_cache().confusable_groups.clone()
+    }
+
+    /// The next verb of the confusables drill, taking the members of a random group in turn.
+    fn next_confusable(&mut self) -> Option<String> {
+        if self.drill != Some(Drill::Confusables) {
+            return None;
+        }
+        if self.confusable_queue.is_empty() {
+            let mut rng = rand::rng();
+            let mut group = self.confusable_groups().choose(&mut rng)?.clone();
+            group.shuffle(&mut rng);
+            // Popped from the back, so the verb just asked must not come first again
+            if let Some(current) = self.verbs.get(self.current_verb_index)
+                && group.last() == Some(&current.infinitive)
+            {
+                group.rotate_right(1);
+            }
+            self.confusable_queue = group;
+        }
+        self.confusable_queue.pop()
+    }
+
+    /// Clears the answer and its outcome, ready for a new question.
+    fn reset_answer(&mut self) {
+        self.user_answer.clear();
+        self.result_message.clear();
+        self.show_result = false;
+        self.attempts_used = 0;
+        self.answered = false;
+        self.answer_revealed = false;
+        self.question_shown_at = Instant::now();
+        self.answer_diff.clear();
+        self.note_message = None;
+        self.sync_details = true;
+        self.form_answers = Default::default();
+        self.form_results = None;
+        self.active_form_field = 0;
+        self.focus_answer = true;
+        self.focus_next_button = false;
+        self.hint_level = 0;
+    }
+
+    fn next_verb(&mut self) {
+        let requested = self.requested_verb.take();
+        // Cram sessions and pools have their own progress and don't count towards passes
+        if requested.is_none()
+            && self.cram.is_none()
+            && self.pool.is_none()
+            && self.active_verb_count() > 0
+            && self.is_pass_complete()
+        {
+            self.complete_pass();
+            if !self.settings.reshuffle_each_pass {
+                self.pass_finished = true;
+                self.reset_answer();
+                return;
+            }
+        }
+
+        // Only fall back to verbs that were answered before once every verb has been seen
+        let unseen_only = self.settings.unseen_first && self.unseen_verb_count() > 0;
+        // Whether to ask a new verb or review a seen one, if that is being balanced
+        let want_new = if unseen_only {
+            Some(true)
+        } else if self.settings.balance_new_and_review {
+            self.pick_new_or_review()
+        } else {
+            None
+        };
+
+        let cram_verb = self
+            .cram
+            .as_mut()
+            .filter(|_| requested.is_none())
+            .and_then(|cram| cram.next_verb().map(str::to_string));
+        let pool_verb = self
+            .pool
+            .as_mut()
+            .filter(|_| requested.is_none())
+            .and_then(|pool| pool.next_verb().map(str::to_string));
+        // Reviews that are due come before other verbs, unless new verbs were asked for or
+        // the Leitner boxes decide instead
+        let review_due =
+            self.settings.review_due_first && !self.settings.leitner && want_new != Some(true);
+        let set_verb = requested
+            .or(cram_verb)
+            .or(pool_verb)
+            .or_else(|| self.next_confusable())
+            .or_else(|| review_due.then(|| self.next_due_verb()).flatten());
+
+        if let Some(infinitive) = set_verb {
+            if let Some(index) = self.verbs.iter().position(|v| v.infinitive == infinitive) {
+                self.current_verb_index = index;
+            }
+        } else {
+            // A form drill skips the verbs without that form
+            let eligible = |verb: &Verb| {
+                self.is_active(verb)
+                    && want_new.is_none_or(|new| self.is_new(verb) == new)
+                    && !matches!(self.drill, Some(Drill::Form(form)) if !verb.has_form(form))
+            };
+
+            if self.settings.leitner || self.settings.prioritize_common_verbs {
+                let default_frequency = self.default_frequency();
+                let candidates: Vec<usize> = (0..self.verbs.len())
+                    .filter(|&index| {
+                        index != self.current_verb_index && eligible(&self.verbs[index])
+                    })
+                    .collect();
+                if let Ok(&index) = candidates.choose_weighted(&mut rand::rng(), |&index| {
+                    let verb = &self.verbs[index];
+                    if self.settings.leitner {
+                        leitner::weight(leitner::box_of(&self.leitner_boxes, &verb.infinitive))
+                    } else {
+                        self.selection_weight(verb, default_frequency)
+                    }
+                }) {
+                    self.current_verb_index = index;
+                }
+            } else {
+                // Move to the next verb that isn't disabled, staying put if there is none
+                for step in 1..=self.verbs.len() {
+                    let index = (self.current_verb_index + step) % self.verbs.len();
+                    if eligible(&self.verbs[index]) {
+                        self.current_verb_index = index;
+                        break;
+                    }
+                }
+            }
+        }
+        if let Some(cram) = &mut self.cram {
+            cram.advance_at = None;
+        } else if let Some(verb) = self.verbs.get(self.current_verb_index) {
+            self.pass_seen.insert(verb.infinitive.clone());
+        }
+        self.reset_answer();
+
+        // A quick drill fixes the kind of question, otherwise pick one at random
+        if let Some(drill) = self.drill {
+            match drill {
+                Drill::Translation => self.practice_mode = PracticeMode::Translation,
+                Drill::ReverseTranslation => self.practice_mode = PracticeMode::ReverseTranslation,
+                Drill::MultipleChoice => self.practice_mode = PracticeMode::MultipleChoice,
+                Drill::Confusables => self.practice_mode = PracticeMode::ReverseTranslation,
+                Drill::Perfect => self.practice_mode = PracticeMode::Perfect,
+                Drill::Form(form) => {
+                    self.practice_mode = PracticeMode::Conjugation;
+                    let has_form = self
+                        .verbs
+                        .get(self.current_verb_index)
+                        .is_some_and(|verb| verb.has_form(form));
+                    self.conjugation_form = if has_form {
+                        form
+                    } else {
+                        ConjugationForm::Present
+                    };
+                }
+            }
+        } else {
+            self.practice_mode = self.random_mode();
+            if self.practice_mode == PracticeMode::Conjugation {
+                self.conjugation_form = self.random_form();
+            }
+        }
+
+        // A new verb is recognized a few times before it has to be produced
+        self.introducing = self.settings.introduce_with_translation
+            && self.drill.is_none()
+            && self.verbs.get(self.current_verb_index).is_some_and(|verb| {
+                !self.learned_verbs.contains(&verb.infinitive)
+                    && !self
+                        .stats
+                        .is_introduced(&verb.infinitive, self.settings.introduction_questions)
+            });
+        if self.introducing {
+            self.practice_mode = PracticeMode::Translation;
+        }
+
+        // Teach mode shows a verb's forms the first time it comes up in a session, then
+        // quizzes one of them
+        self.teaching = self.settings.teach_mode
+            && self.drill.is_none()
+            && self.cram.is_none()
+            && self
+                .verbs
+                .get(self.current_verb_index)
+                .is_some_and(|verb| !self.taught_verbs.contains(&verb.infinitive));
+        if self.teaching {
+            self.introducing = false;
+            self.practice_mode = PracticeMode::Conjugation;
+            self.conjugation_form = self.random_form();
+        }
+
+        // Some conjugation questions go the other way, from the form to the infinitive,
+        // though not right after the forms were taught
+        self.conjugation_flipped = self.practice_mode == PracticeMode::Conjugation
+            && !self.teaching
+            && rand::rng().random_ratio(self.settings.flipped_conjugation_percent.min(100), 100);
+        self.prepare_choices();
+
+        if self.practice_mode == PracticeMode::Dictation {
+            self.pronounce();
+        }
+    }
+
+    /// The conjugation forms questions may ask for, which is all of them if every form was
+    /// left out.
+    fn included_forms(&self) -> Vec<ConjugationForm> {
+        let included: Vec<ConjugationForm> = ConjugationForm::ALL
+            .into_iter()
+            .filter(|form| !self.settings.excluded_forms.contains(form))
+            .collect();
+        if included.is_empty() {
+            ConjugationForm::ALL.to_vec()
+        } else {
+            included
+        }
+    }
+
+    /// Whether questions of this kind are mixed in with the others.
+    fn is_mixed_in(&mut self, mode: PracticeMode) -> bool {
+        match mode {
+            PracticeMode::Translation | PracticeMode::Conjugation => true,
+            PracticeMode::ReverseTranslation => self.settings.translate_both_ways,
+            PracticeMode::MultipleChoice => self.settings.multiple_choice,
+            PracticeMode::AllForms => self.settings.all_forms,
+            PracticeMode::Dictation => self.settings.dictation && self.can_dictate(),
+            PracticeMode::Perfect => self.settings.perfect,
+        }
+    }
+
+    /// Picks the kind of the next question by the mode weights, among the kinds mixed in.
+    fn random_mode(&mut self) -> PracticeMode {
+        let modes: Vec<PracticeMode> = PracticeMode::ALL
+            .into_iter()
+            .filter(|&mode| self.is_mixed_in(mode))
+            .collect();
+        let weights = self.settings.mode_weights;
+        modes
+            .choose_weighted(&mut rand::rng(), |&mode| weights.get(mode))
+            .copied()
+            .unwrap_or(PracticeMode::Translation)
+    }
+
+    /// A form to ask the current verb for, out of the included forms it has. A verb with
+    /// none of them is asked for its present tense.
+    fn random_form(&self) -> ConjugationForm {
+        let Some(verb) = self.verbs.get(self.current_verb_index) else {
+            return ConjugationForm::Present;
+        };
+        let forms: Vec<ConjugationForm> = self
+            .included_forms()
+            .into_iter()
+            .filter(|&form| verb.has_form(form))
+            .collect();
+        forms
+            .choose(&mut rand::rng())
+            .copied()
+            .unwrap_or(ConjugationForm::Present)
+    }
+
+    /// Draws the options for a multiple choice question, or clears them for other modes.
+    fn prepare_choices(&mut self) {
+        self.choices.clear();
+        self.choice_highlight = None;
+        if self.practice_mode != PracticeMode::MultipleChoice {
+            return;
+        }
+        let Some(current_verb) = self.verbs.get(self.current_verb_index) else {
+            return;
+        };
+
+        let candidates: Vec<&str> = self
+            .verbs
+            .iter()
+            .filter(|verb| !self.disabled_verbs.contains(&verb.infinitive))
+            .map(|verb| verb.english.as_str())
+            .collect();
+        let mut rng = rand::rng();
+        let mut choices = multiple_choice::pick_distractors(
+            &current_verb.english,
+            &candidates,
+            3,
+            self.settings.distractor_difficulty,
+            &mut rng,
+        );
+        choices.push(current_verb.english.clone());
+        choices.shuffle(&mut rng);
+        self.choices = choices;
+    }
+
+    fn choose(&mut self, index: usize) {
+        let Some(choice) = self.choices.get(index) else {
+            return;
+        };
+        self.user_answer = choice.clone();
+        self.choice_highlight = Some((index, Instant::now()));
+        self.check_answer();
+    }
+
+    /// The answer the hint gives away letters of, if the question takes a typed answer.
+    fn hint_answer(&self) -> Option<String> {
+        let verb = self.verbs.get(self.current_verb_index)?;
+        let answer = match self.practice_mode {
+            PracticeMode::MultipleChoice => return None,
+            // One meaning is enough, e.g. "to learn" of "to learn/teach"
+            PracticeMode::Translation => grading::meanings(&verb.english).into_iter().next()?,
+            PracticeMode::ReverseTranslation | PracticeMode::AllForms | PracticeMode::Dictation => {
+                verb.infinitive.clone()
+            }
+            PracticeMode::Conjugation if self.conjugation_flipped => verb.infinitive.clone(),
+            PracticeMode::Perfect => self.perfect_phrases(verb).into_iter().next()?,
+            PracticeMode::Conjugation => match self.expected_phrases(verb).into_iter().next() {
+                Some(phrase) => phrase,
+                None => grading::variants(verb.form(self.conjugation_form))
+                    .first()?
+                    .to_string(),
+            },
+        };
+        Some(answer)
+    }
+
+    /// The hint as shown under the answer, such as "s _ i _ e" at the last level.
+    fn hint_text(&self) -> Option<String> {
+        hint::reveal(&self.hint_answer()?, self.hint_level)
+    }
+
+    /// Gives away a little more of the answer.
+    fn reveal_hint(&mut self) {
+        if self.hint_answer().is_some() {
+            self.hint_level = (self.hint_level + 1).min(hint::LEVELS);
+        }
+    }
+
+    /// The full phrases expected for a present tense question, or none to grade the bare form.
+    fn expected_phrases(&self, verb: &Verb) -> Vec<String> {
+        if self.practice_mode != PracticeMode::Conjugation
+            || self.conjugation_flipped
+            || self.conjugation_form != ConjugationForm::Present
+            || !self.settings.present_phrase
+        {
+            return Vec::new();
+        }
+        grading::present_phrases(
+            &verb.present_phrase,
+            &verb.present,
+            &self.settings.phrase_pronoun,
+        )
+    }
+
+    /// The full phrases accepted for a perfect tense question, e.g. "jeg er gået".
+    fn perfect_phrases(&self, verb: &Verb) -> Vec<String> {
+        grading::perfect_phrases(
+            verb.auxiliary(),
+            &verb.past_participle,
+            &self.settings.phrase_pronoun,
+        )
+    }
+
+    /// Grades the infinitive and each form of an all forms question separately.
+    fn grade_all_forms(&self, verb: &Verb) -> [bool; 4] {
+        let options = &self.grading_options();
+        let patterns = &verb.answer_pattern;
+        let [infinitive, present, past, past_participle] = &self.form_answers;
+        let form = |answer: &str, field: &str, pattern: &Option<AnswerPattern>| {
+            grading::matches_conjugation(answer, &grading::variants(field), options)
+                || grading::matches_pattern(answer, pattern.as_ref(), options)
+        };
+        [
+            grading::matches_infinitive(infinitive, &verb.infinitive, options)
+                || grading::matches_pattern(infinitive, patterns.infinitive.as_ref(), options),
+            form(present, &verb.present, &patterns.present),
+            form(past, &verb.past, &patterns.past),
+            form(
+                past_participle,
+                &verb.past_participle,
+                &patterns.past_participle,
+            ),
+        ]
+    }
+
+    /// The deck's pattern for the field the current question asks for, if it has one.
+    fn answer_pattern<'a>(&self, verb: &'a Verb) -> Option<&'a AnswerPattern> {
+        let patterns = &verb.answer_pattern;
+        match self.practice_mode {
+            PracticeMode::Translation => patterns.english.as_ref(),
+            PracticeMode::ReverseTranslation | PracticeMode::Dictation => {
+                patterns.infinitive.as_ref()
+            }
+            PracticeMode::Conjugation if self.conjugation_flipped => patterns.infinitive.as_ref(),
+            PracticeMode::Conjugation => patterns.form(self.conjugation_form),
+            PracticeMode::MultipleChoice | PracticeMode::AllForms | PracticeMode::Perfect => None,
+        }
+    }
+
+    /// The text the on-screen keyboard types into.
+    fn answer_field_mut(&mut self) -> &mut String {
+        if self.practice_mode == PracticeMode::AllForms {
+            &mut self.form_answers[self.active_form_field]
+        } else {
+            &mut self.user_answer
+        }
+    }
+
+    /// The options answers are graded with, which are strict in the confusables drill.
+    fn grading_options(&self) -> grading::GradingOptions {
+        match self.drill {
+            Some(Drill::Confusables) => grading::GradingOptions {
+                case_folding: self.settings.grading.case_folding,
+                ..grading::GradingPreset::Strict.options().unwrap_or_default()
+            },
+            _ => self.settings.grading.clone(),
+        }
+    }
+
+    fn check_answer(&mut self) {
+        // A graded question keeps its outcome until the next one
+        if self.answered {
+            return;
+        }
+        let Some(current_verb) = self.verbs.get(self.current_verb_index) else {
+            return;
+        };
+        let options = self.grading_options();
+        let confusables_drill = self.drill == Some(Drill::Confusables);
+        let phrases = self.expected_phrases(current_verb);
+        let meanings = grading::meanings(&current_verb.english);
+        let perfect = self.perfect_phrases(current_verb);
+        let accepted = match self.practice_mode {
+            PracticeMode::Conjugation if !phrases.is_empty() => {
+                phrases.iter().map(String::as_str).collect()
+            }
+            PracticeMode::Translation => meanings.iter().map(String::as_str).collect(),
+            // The options are whole meanings, so the pick is compared with the whole field
+            PracticeMode::MultipleChoice => {
+                vec![current_verb.english.as_str()]
+            }
+            PracticeMode::ReverseTranslation | PracticeMode::Dictation => {
+                vec![current_verb.infinitive.as_str()]
+            }
+            PracticeMode::Conjugation if self.conjugation_flipped => {
+                vec![current_verb.infinitive.as_str()]
+            }
+            PracticeMode::AllForms => Vec::new(),
+            PracticeMode::Perfect => perfect.iter().map(String::as_str).collect(),
+            PracticeMode::Conjugation => {
+                grading::variants(current_verb.form(self.conjugation_form))
+            }
+        };
+
+        let correct = match self.practice_mode {
+            PracticeMode::ReverseTranslation | PracticeMode::Dictation => {
+                grading::matches_infinitive(&self.user_answer, &current_verb.infinitive, &options)
+            }
+            PracticeMode::Conjugation if self.conjugation_flipped => {
+                grading::matches_infinitive(&self.user_answer, &current_verb.infinitive, &options)
+            }
+            PracticeMode::AllForms => {
+                let results = self.grade_all_forms(current_verb);
+                self.form_results = Some(results);
+                results.iter().all(|correct| *correct)
+            }
+            // A pronoun is part of the expected phrase, so it mustn't be stripped
+            PracticeMode::Conjugation if phrases.is_empty() => {
+                grading::matches_conjugation(&self.user_answer, &accepted, &options)
+            }
+            PracticeMode::Translation => {
+                grading::matches_english(&self.user_answer, &accepted, &options)
+            }
+            // A picked option is right or wrong as it stands, as a distractor can be a typo away
+            PracticeMode::MultipleChoice => self.user_answer == current_verb.english,
+            // The pronoun is given in the question, so it may be left out
+            PracticeMode::Perfect => {
+                let with_pronoun = format!(
+                    "{} {}",
+                    self.settings.phrase_pronoun.trim(),
+                    self.user_answer.trim()
+                );
+                grading::matches_any(&self.user_answer, &accepted, &options)
+                    || grading::matches_any(&with_pronoun, &accepted, &options)
+            }
+            _ => grading::matches_any(&self.user_answer, &accepted, &options),
+        };
+        // A pattern from the deck accepts answers the plain field doesn't list
+        let correct = correct
+            || (phrases.is_empty()
+                && grading::matches_pattern(
+                    &self.user_answer,
+                    self.answer_pattern(current_verb),
+                    &options,
+                ));
+        // Multiple choice questions are decided by the first pick
+        if correct || self.practice_mode == PracticeMode::MultipleChoice {
+            self.answered = true;
+        } else {
+            self.attempts_used += 1;
+            self.answered = self.attempts_used > self.settings.max_retries;
+        }
+        // With tries left, the cursor goes back to the answer for another go
+        self.focus_answer = !self.answered;
+
+        // Point out an answer that belongs to another verb
+        let confused_with = if correct || !(self.settings.spot_confused_verbs || confusables_drill)
+        {
+            None
+        } else {
+            let field = match self.practice_mode {
+                PracticeMode::Translation | PracticeMode::MultipleChoice => {
+                    Some(confusion::AnswerField::English)
+                }
+                PracticeMode::ReverseTranslation | PracticeMode::Dictation => {
+                    Some(confusion::AnswerField::Infinitive)
+                }
+                PracticeMode::Conjugation if self.conjugation_flipped => {
+                    Some(confusion::AnswerField::Infinitive)
+                }
+                PracticeMode::Conjugation => {
+                    Some(confusion::AnswerField::Form(self.conjugation_form))
+                }
+                PracticeMode::AllForms | PracticeMode::Perfect => None,
+            };
+            field.and_then(|field| {
+                self.confusion
+                    .other_verb(field, &self.user_answer, &current_verb.infinitive)
+            })
+        };
+
+        // A misspelling of the right answer rather than a wrong one, which a picked option
+        // can't be
+        let near_miss = if correct || self.practice_mode == PracticeMode::MultipleChoice {
+            None
+        } else {
+            grading::near_miss(
+                &self.user_answer,
+                &accepted,
+                self.settings.near_miss_distance as usize,
+                &options,
+            )
+        };
+
+        if correct {
+            self.result_message = "Correct! 🎉".to_string();
+            self.answer_revealed = true;
+        } else if !self.answered {
+            let attempts_left = self.settings.max_retries + 1 - self.attempts_used;
+            self.result_message = format!(
+                "Try again ({} attempt{} left)",
+                attempts_left,
+                if attempts_left == 1 { "" } else { "s" }
+            );
+            if near_miss.is_some() {
+                self.result_message
+                    .insert_str(0, "Almost — watch the spelling. ");
+            }
+        } else if self.practice_mode == PracticeMode::AllForms {
+            self.result_message = format!(
+                "Incorrect. The correct forms are: {}",
+                current_verb.all_forms()
+            );
+        } else {
+            self.result_message = match near_miss {
+                Some(expected) => format!("Almost correct — watch the spelling: {expected}"),
+                None => format!("Incorrect. The correct answer is: {}", accepted.join(" / ")),
+            };
+            if !self.user_answer.trim().is_empty() {
+                self.answer_diff = grading::closest_diff(&self.user_answer, &accepted);
+            }
+        }
+        // Picking the wrong auxiliary is the usual mistake in the perfect tense
+        if !correct
+            && self.practice_mode == PracticeMode::Perfect
+            && let Some(used) =
+                grading::wrong_auxiliary(&self.user_answer, current_verb.auxiliary())
+        {
+            self.result_message += &format!(
+                "\n'{}' takes \"{}\" in the perfect tense, not \"{}\".",
+                current_verb.infinitive,
+                grading::format_variants(current_verb.auxiliary()),
+                used
+            );
+        }
+        if let Some(other) = confused_with {
+            self.result_message += &if confusables_drill {
+                format!(
+                    "\nYou mixed up '{}' with '{}': \"{}\" means {}.",
+                    current_verb.infinitive,
+                    other,
+                    other,
+                    self.verbs
+                        .iter()
+                        .find(|verb| verb.infinitive == other)
+                        .map_or("", |verb| verb.english.as_str())
+                )
+            } else {
+                format!(
+                    "\nThat's the answer for '{}' — a commonly confused verb.",
+                    other
+                )
+            };
+        }
+
+        // Only the final outcome of a question counts towards the statistics
+        if self.answered {
+            self.sync_details = true;
+            self.focus_next_button = true;
+            if let Some(cram) = &mut self.cram {
+                cram.record(&current_verb.infinitive, correct);
+            } else if let Some(pool) = &mut self.pool {
+                pool.record(&current_verb.infinitive, correct);
+            }
+
+            if self.stats.events.is_empty() {
+                self.session_started_at = Some(Instant::now());
+            }
+            self.stats.record(AttemptEvent {
+                infinitive: current_verb.infinitive.clone(),
+                mode: self.practice_mode,
+                form: match self.practice_mode {
+                    PracticeMode::Translation
+                    | PracticeMode::ReverseTranslation
+                    | PracticeMode::MultipleChoice
+                    | PracticeMode::AllForms
+                    | PracticeMode::Dictation
+                    | PracticeMode::Perfect => None,
+                    PracticeMode::Conjugation => Some(self.conjugation_form),
+                },
+                flipped: self.asks_infinitive_of_form(),
+                correct,
+                // Answers that were hinted at or revealed aren't a measure of fluency
+                response_time: (correct && self.hint_level == 0)
+                    .then(|| self.question_shown_at.elapsed()),
+                hinted: self.hint_level > 0,
+                typed_chars: match self.practice_mode {
+                    PracticeMode::MultipleChoice => 0,
+                    PracticeMode::AllForms => self
+                        .form_answers
+                        .iter()
+                        .map(|answer| answer.trim().chars().count())
+                        .sum(),
+                    _ => self.user_answer.trim().chars().count(),
+                },
+                answer: match self.practice_mode {
+                    PracticeMode::AllForms => self
+                        .form_answers
+                        .iter()
+                        .map(|answer| answer.trim())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    _ => self.user_answer.trim().to_string(),
+                },
+                expected: match self.practice_mode {
+                    PracticeMode::AllForms => current_verb.all_forms(),
+                    _ => accepted.join(" / "),
+                },
+                timestamp: stats::now(),
+            });
+
+            #[cfg(feature = "serve")]
+            if let Some(snapshot) = &self.stats_snapshot {
+                server::publish(snapshot, &self.stats);
+            }
+
+            let infinitive = current_verb.infinitive.clone();
+            // A verb is starred when it becomes a leech, so it's easy to find again
+            let lapses = self
+                .stats
+                .verbs
+                .get(&infinitive)
+                .map_or(0, |verb_stat| verb_stat.lapses);
+            if !correct && lapses == self.settings.leech_threshold {
+                self.starred_verbs.insert(infinitive.clone());
+            }
+            self.review_history.push((
+                infinitive.clone(),
+                self.schedule.get(&infinitive).copied(),
+                self.leitner_boxes.get(&infinitive).copied(),
+                self.streak,
+            ));
+            let box_number = leitner::box_of(&self.leitner_boxes, &infinitive);
+            self.leitner_boxes
+                .insert(infinitive.clone(), leitner::next_box(box_number, correct));
+            self.schedule.entry(infinitive).or_default().record(
+                schedule::Recall::new(correct, self.attempts_used == 0 && self.hint_level == 0),
+                stats::now(),
+            );
+
+            if self.streak.record(streak::today()) {
+                self.streak_lost = None;
+            }
+            // Save every answer, so a crash costs at most the last one
+            self.autosave.request();
+        }
+        self.show_result = true;
+
+        // A timed session ends once the question it ran out on is graded
+        if self.answered && self.session_time_left() == Some(Duration::ZERO) {
+            self.end_session();
+        }
+        // So does a session on a random pool once every verb in it is cleared
+        if self.pool.as_ref().is_some_and(|pool| pool.is_cleared()) {
+            self.end_session();
+        }
+    }
+
+    /// A missed answer stays covered until it's tapped, when answers are covered.
+    fn is_answer_covered(&self) -> bool {
+        self.settings.cover_answers && self.answered && !self.answer_revealed
+    }
+
+    /// Hides the forms of the verb being taught and asks the question on them.
+    fn finish_teaching(&mut self) {
+        self.teaching = false;
+        if let Some(verb) = self.verbs.get(self.current_verb_index) {
+            self.taught_verbs.insert(verb.infinitive.clone());
+        }
+        self.question_shown_at = Instant::now();
+    }
+
+    /// Phase one of teach mode: every form of the verb, to study before being asked one.
+    fn show_teaching(
+        &mut self,
+        ui: &mut egui::Ui,
+        verb: &Verb,
+        body_font: &egui::FontId,
+        accent_color: egui::Color32,
+    ) {
+        ui.label(
+            egui::RichText::new(format!("Study the forms of '{}'", verb.infinitive))
+                .font(body_font.clone())
+                .color(self.theme.text)
+                .strong(),
+        );
+        ui.label(
+            egui::RichText::new("They'll be hidden when you're quizzed on one of them")
+                .small()
+                .color(accent_color),
+        );
+        ui.add_space(10.0);
+
+        egui::Frame::new()
+            .fill(self.panel_color())
+            .stroke(egui::Stroke::new(1.0, accent_color))
+            .corner_radius(8.0)
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                egui::Grid::new("teach_paradigm")
+                    .spacing([24.0, 8.0])
+                    .show(ui, |ui| {
+                        let mut row = |name: &str, value: &str| {
+                            ui.label(egui::RichText::new(name).color(accent_color));
+                            ui.label(egui::RichText::new(value).font(body_font.clone()).strong());
+                            ui.end_row();
+                        };
+                        row("Infinitive", &verb.infinitive);
+                        for form in ConjugationForm::ALL {
+                            if verb.has_form(form) {
+                                row(
+                                    self.deck_info.form_name(form),
+                                    &grading::format_variants(verb.form(form)),
+                                );
+                            }
+                        }
+                        row("English", &verb.english);
+                        if !verb.ipa.is_empty() {
+                            row("Pronunciation", &format!("[{}]", verb.ipa));
+                        }
+                    });
+                if !verb.note.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label(egui::RichText::new(format!("📝 {}", verb.note)).italics());
+                }
+            });
+        ui.add_space(16.0);
+
+        if ui
+            .add(
+                egui::Button::new(
+                    egui::RichText::new("Got it, quiz me")
+                        .font(body_font.clone())
+                        .color(egui::Color32::WHITE),
+                )
+                .fill(accent_color)
+                .corner_radius(8.0),
+            )
+            .clicked()
+        {
+            self.finish_teaching();
+        }
+    }
+
+    /// Time left of a timed session, or `None` without a limit or before the first answer.
+    fn session_time_left(&self) -> Option<Duration> {
+        if !self.settings.time_limit {
+            return None;
+        }
+        let limit = Duration::from_secs(u64::from(self.settings.time_limit_minutes) * 60);
+        Some(limit.saturating_sub(self.session_started_at?.elapsed()))
+    }
+}
+
+impl DanishVerbsApp {
+    fn show_statistics(
+        &mut self,
+        ui: &mut egui::Ui,
+        body_font: &egui::FontId,
+        accent_color: egui::Color32,
+    ) {
+        let mut stats_frame = egui::Frame::new();
+        stats_frame = stats_frame.fill(self.panel_color());
+        stats_frame = stats_frame.stroke(egui::Stroke::new(1.0, accent_color));
+        stats_frame = stats_frame.corner_radius(8.0);
+        stats_frame = stats_frame.inner_margin(16.0);
+
+        stats_frame.show(ui, |ui| {
+            egui::CollapsingHeader::new(
+                egui::RichText::new("Statistics")
+                    .font(body_font.clone())
+                    .color(accent_color)
+                    .strong(),
+            )
+            .default_open(false)
+            .show(ui, |ui| {
+                if self.stats.events.is_empty() {
+                    ui.label("No answers checked yet this session.");
+                } else {
+                    self.show_stats_breakdown(ui);
+
+                    let mistakes = self.stats.mistakes();
+                    if self.show_mistakes(ui, "session_mistakes", &mistakes) {
+                        self.retry_mistakes(&mistakes);
+                    }
+
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("End session")
+                            .on_hover_text(
+                                "Compare this session with your best and start a new one",
+                            )
+                            .clicked()
+                        {
+                            self.end_session();
+                        }
+                        if ui
+                            .button("Undo last answer")
+                            .on_hover_text("Take the last graded answer out of the statistics")
+                            .clicked()
+                        {
+                            self.undo_last_answer();
+                        }
+                    });
+                }
+
+                if self.settings.leitner {
+                    self.show_leitner_boxes(ui, accent_color);
+                }
+                self.show_leeches(ui);
+                self.show_verb_table(ui);
+                self.show_learned_verbs(ui);
+
+                ui.add_space(8.0);
+                self.show_report_export(ui);
+            });
+        });
+    }
+
+    /// How many of the verbs being practiced are in each Leitner box, as a small bar chart.
+    fn show_leitner_boxes(&self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        let counts = leitner::distribution(
+            &self.leitner_boxes,
+            self.verbs
+                .iter()
+                .filter(|verb| self.is_active(verb))
+                .map(|verb| verb.infinitive.as_str()),
+        );
+        let most = counts.iter().copied().max().unwrap_or(0).max(1);
+
+        ui.add_space(8.0);
+        ui.strong("Leitner boxes");
+        ui.horizontal(|ui| {
+            for (index, &count) in counts.iter().enumerate() {
+                ui.vertical(|ui| {
+                    let (rect, response) =
+                        ui.allocate_exact_size(egui::vec2(36.0, 48.0), egui::Sense::hover());
+                    let height = rect.height() * count as f32 / most as f32;
+                    let bar = egui::Rect::from_min_max(
+                        egui::pos2(rect.left(), rect.bottom() - height),
+                        rect.max,
+                    );
+                    ui.painter().rect_filled(bar, 2.0, accent_color);
+                    response.on_hover_text(format!(
+                        "{} verb{} in box {}",
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        index + 1
+                    ));
+                    ui.label(egui::RichText::new(format!("{}: {}", index + 1, count)).small());
+                });
+            }
+        });
+    }
+
+    fn show_report_export(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .button("Export report…")
+            .on_hover_text("Write the session and all-time statistics as Markdown")
+            .clicked()
+            && let Some(path) = rfd::FileDialog::new()
+                .set_file_name("progress-report.md")
+                .add_filter("Markdown", &["md"])
+                .save_file()
+        {
+            let report = report::markdown_report(&self.stats, &self.deck_info, &self.streak);
+            self.report_message = Some(match fs::write(&path, report) {
+                Ok(()) => format!("Report written to {}", path.display()),
+                Err(e) => format!("Couldn't write the report: {}", e),
+            });
+        }
+        if let Some(report_message) = &self.report_message {
+            ui.label(egui::RichText::new(report_message).small());
+        }
+    }
+
+    /// Identifies the deck in the best accuracy records.
+    fn deck_key(&self) -> String {
+        self.deck_file().display().to_string()
+    }
+
+    fn undo_last_answer(&mut self) {
+        if self.stats.undo_last().is_none() {
+            return;
+        }
+        if let Some((infinitive, review, box_number, streak)) = self.review_history.pop() {
+            // Takes the answer off today's count towards the daily goal
+            self.streak = streak;
+            match box_number {
+                Some(box_number) => self.leitner_boxes.insert(infinitive.clone(), box_number),
+                None => self.leitner_boxes.remove(&infinitive),
+            };
+            match review {
+                Some(review) => self.schedule.insert(infinitive, review),
+                None => self.schedule.remove(&infinitive),
+            };
+        }
+        #[cfg(feature = "serve")]
+        if let Some(snapshot) = &self.stats_snapshot {
+            server::publish(snapshot, &self.stats);
+        }
+        self.save_state();
+    }
+
+    fn end_session(&mut self) {
+        self.session_started_at = None;
+        let tally = self.stats.total();
+        let accuracy = tally.accuracy();
+        let key = self.deck_key();
+        let comparison =
+            stats::BestComparison::new(accuracy, self.best_accuracy.get(&key).copied());
+        if matches!(
+            comparison,
+            stats::BestComparison::FirstSession | stats::BestComparison::NewBest { .. }
+        ) {
+            self.best_accuracy.insert(key, accuracy);
+        }
+
+        self.session_summary = Some((tally, comparison));
+        self.session_changes = self.stats.accuracy_changes(SESSION_CHANGES_SHOWN);
+        self.session_taught = std::mem::take(&mut self.taught_verbs).len();
+        // The pool only lasts the session
+        self.session_pool_cleared = self
+            .pool
+            .take()
+            .filter(|pool| pool.is_cleared())
+            .map(|pool| pool.verbs.len());
+        self.session_typing_speed = self.stats.typing_speed();
+        self.session_modes = PracticeMode::ALL
+            .into_iter()
+            .map(|mode| (mode, self.stats.by_mode(mode)))
+            .filter(|(_, tally)| tally.attempts > 0)
+            .collect();
+        self.session_slowest = self
+            .stats
+            .response_times_by_verb()
+            .into_iter()
+            .take(SESSION_CHANGES_SHOWN)
+            .map(|(infinitive, time)| (infinitive.to_string(), time))
+            .collect();
+        self.session_mistakes = self.stats.mistakes();
+        self.stats.start_new_session();
+        self.review_history.clear();
+        self.save_state();
+    }
+
+    /// What a question asked for, as listed next to a mistake, e.g. "past tense".
+    fn question_label(&self, attempt: &stats::AttemptEvent) -> String {
+        match attempt.form {
+            Some(form) if attempt.flipped => {
+                format!("{} → infinitive", self.deck_info.form_name(form))
+            }
+            Some(form) => self.deck_info.form_name(form).to_string(),
+            None => attempt.mode.label().to_string(),
+        }
+    }
+
+    /// Lists wrong answers next to the right ones, with a button to ask those verbs again.
+    /// Returns true when the button is pressed.
+    fn show_mistakes(&self, ui: &mut egui::Ui, id: &str, mistakes: &[stats::AttemptEvent]) -> bool {
+        if mistakes.is_empty() {
+            return false;
+        }
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(format!("Mistakes ({})", mistakes.len()))
+            .id_salt(id)
+            .show(ui, |ui| {
+                egui::Grid::new(format!("{}_grid", id))
+                    .striped(true)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.strong("Verb");
+                        ui.strong("Asked");
+                        ui.strong("Your answer");
+                        ui.strong("Correct answer");
+                        ui.end_row();
+                        for mistake in mistakes {
+                            ui.label(&mistake.infinitive);
+                            ui.label(self.question_label(mistake));
+                            let answer = if mistake.answer.is_empty() {
+                                "–"
+                            } else {
+                                &mistake.answer
+                            };
+                            ui.label(egui::RichText::new(answer).color(self.theme.wrong));
+                            ui.label(
+                                egui::RichText::new(&mistake.expected).color(self.theme.correct),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+        let verbs = stats::missed_verbs(mistakes).len();
+        ui.button(format!(
+            "Retry mistakes ({} verb{})",
+            verbs,
+            if verbs == 1 { "" } else { "s" }
+        ))
+        .on_hover_text("Ask only the verbs answered wrong, until each is answered right once")
+        .clicked()
+    }
+
+    fn show_session_summary(&mut self, ui: &mut egui::Ui, accent_color: egui::Color32) {
+        let Some((tally, comparison)) = self.session_summary else {
+            return;
+        };
+        let mistakes = self.session_mistakes.clone();
+        let mut retry = false;
+        let accuracy = tally.accuracy();
+        let verdict = match comparison {
+            stats::BestComparison::FirstSession => {
+                "First session on this deck — baseline set.".to_string()
+            }
+            stats::BestComparison::NewBest { previous } => format!(
+                "🏆 New best! {:.1} points above your previous best of {:.0}%.",
+                accuracy - previous,
+                previous
+            ),
+            stats::BestComparison::Tied => "You tied your best.".to_string(),
+            stats::BestComparison::Below { best } => format!(
+                "{:.1} points below your best of {:.0}%.",
+                best - accuracy,
+                best
+            ),
+        };
+
+        egui::Frame::new()
+            .fill(self.panel_color())
+            .stroke(egui::Stroke::new(1.0, accent_color))
+            .corner_radius(8.0)
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Session over: {}/{} correct ({:.0}%)",
+                        tally.correct, tally.attempts, accuracy
+                    ))
+                    .color(accent_color)
+                    .strong(),
+                );
+                ui.label(verdict);
+                if let Some(size) = self.session_pool_cleared {
+                    ui.label(format!("🎉 Cleared a random pool of {} verbs", size));
+                }
+                if self.settings.show_typing_speed
+                    && let Some(speed) = self.session_typing_speed
+                {
+                    ui.label(format!(
+                        "⌨ Typed your answers at {:.0} words per minute",
+                        speed
+                    ));
+                }
+                if self.session_taught > 0 {
+                    ui.label(format!(
+                        "Taught {} verb{} in teach mode",
+                        self.session_taught,
+                        if self.session_taught == 1 { "" } else { "s" }
+                    ));
+                }
+
+                if !self.session_modes.is_empty() {
+                    ui.add_space(6.0);
+                    ui.strong("By kind of question");
+                    for (mode, tally) in &self.session_modes {
+                        ui.label(format!(
+                            "{}: {}/{} ({:.0}%)",
+                            mode.label(),
+                            tally.correct,
+                            tally.attempts,
+                            tally.accuracy()
+                        ));
+                    }
+                }
+                if !self.session_slowest.is_empty() {
+                    ui.add_space(6.0);
+                    ui.strong("Slowest verbs");
+                    for (infinitive, time) in &self.session_slowest {
+                        ui.label(format!("{}: {:.1} s", infinitive, time.as_secs_f32()));
+                    }
+                }
+
+                let (improved, declined) = &self.session_changes;
+                let show_changes = |ui: &mut egui::Ui,
+                                    title: &str,
+                                    changes: &[stats::VerbChange],
+                                    color: egui::Color32| {
+                    if changes.is_empty() {
+                        return;
+                    }
+                    ui.add_space(6.0);
+                    ui.strong(title);
+                    for change in changes {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{}: {:.0}% → {:.0}% ({:+.0} points)",
+                                change.infinitive,
+                                change.before,
+                                change.session,
+                                change.points()
+                            ))
+                            .color(color),
+                        );
+                    }
+                };
+                show_changes(ui, "Most improved", improved, self.theme.correct);
+                show_changes(ui, "Slipped", declined, self.theme.wrong);
+
+                retry = self.show_mistakes(ui, "summary_mistakes", &mistakes);
+
+                if ui.small_button("Dismiss").clicked() {
+                    self.session_summary = None;
+                }
+            });
+        if retry {
+            self.session_summary = None;
+            self.retry_mistakes(&mistakes);
+        }
+        ui.add_space(20.0);
+    }
+
+    fn show_stats_breakdown(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("stats_breakdown")
+            .striped(true)
+            .spacing([24.0, 6.0])
+            .show(ui, |ui| {
+                ui.strong("Area");
+                ui.strong("Correct");
+                ui.strong("Accuracy");
+                ui.end_row();
+
+                let row = |ui: &mut egui::Ui, name: &str, tally: stats::Tally| {
+                    ui.label(name);
+                    ui.label(format!("{}/{}", tally.correct, tally.attempts));
+                    if tally.attempts == 0 {
+                        ui.label("–");
+                    } else {
+                        ui.label(format!("{:.0}%", tally.accuracy()));
+                    }
+                    ui.end_row();
+                };
+
+                row(ui, "Overall", self.stats.total());
+                for mode in PracticeMode::ALL {
+                    row(ui, mode.label(), self.stats.by_mode(mode));
+                }
+                for form in ConjugationForm::ALL {
+                    let name = self.deck_info.form_name(form);
+                    row(
+                        ui,
+                        &format!("  {}", name),
+                        self.stats.by_form_direction(form, false),
+                    );
+                    let flipped = self.stats.by_form_direction(form, true);
+                    if flipped.attempts > 0 {
+                        row(ui, &format!("  {} → infinitive", name), flipped);
+                    }
+                }
+                let hinted = self.stats.hinted();
+                if hinted.attempts > 0 {
+                    row(ui, "With a hint", hinted);
+                }
+            });
+
+        if let Some(average) = self.stats.average_response_time() {
+            ui.add_space(8.0);
+            ui.label(format!(
+                "Average response time: {:.1} s",
+                average.as_secs_f32()
+            ));
+
+            egui::CollapsingHeader::new("Response time per verb")
+                .id_salt("response_times")
+                .show(ui, |ui| {
+                    egui::Grid::new("response_times_grid")
+                        .striped(true)
+                        .spacing([24.0, 4.0])
+                        .show(ui, |ui| {
+                            for (infinitive, time) in self.stats.response_times_by_verb() {
+                                ui.label(infinitive);
+                                ui.label(format!("{:.1} s", time.as_secs_f32()));
+                                ui.end_row();
+                            }
+                        });
+                });
+        }
+    }
+
+    /// Lists verbs that keep being answered wrong, with an option to suspend them.
+    /// Every active verb with its all-time results and when it was last practiced.
+    fn show_verb_table(&mut self, ui: &mut egui::Ui) {
+        let accuracy = |verb_stat: Option<&stats::VerbStat>| {
+            verb_stat
+                .filter(|verb_stat| verb_stat.attempts > 0)
+                .map(|verb_stat| verb_stat.correct as f32 / verb_stat.attempts as f32)
+        };
+
+        let now = stats::now();
+        let mut sort = self.verb_sort;
+        ui.add_space(12.0);
+        egui::CollapsingHeader::new(format!("All verbs ({})", self.active_verb_count()))
+            .id_salt("verb_table")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (column, label) in [
+                        (VerbSort::Name, "Verb"),
+                        (VerbSort::Accuracy, "Accuracy"),
+                        (VerbSort::LastSeen, "Last seen"),
+                    ] {
+                        ui.selectable_value(&mut sort, column, label)
+                            .on_hover_text("Sort by this column");
+                    }
+                });
+
+                // Only the rows in view are laid out, so big decks scroll smoothly
+                let rows = self.verb_table_order(self.verb_sort);
+                let row_height = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+                egui::ScrollArea::vertical().max_height(240.0).show_rows(
+                    ui,
+                    row_height,
+                    rows.len(),
+                    |ui, visible| {
+                        egui::Grid::new("verb_table_grid")
+                            .striped(true)
+                            .min_col_width(120.0)
+                            .spacing([24.0, 4.0])
+                            .show(ui, |ui| {
+                                for &index in &rows[visible] {
+                                    let infinitive = &self.verbs[index].infinitive;
+                                    let verb_stat = self.stats.verbs.get(infinitive);
+                                    if self.learned_verbs.contains(infinitive) {
+                                        ui.label(format!("{} ✓", infinitive))
+                                            .on_hover_text("Marked as learned");
+                                    } else {
+                                        ui.label(infinitive);
+                                    }
+                                    match (verb_stat, accuracy(verb_stat)) {
+                                        (Some(verb_stat), Some(accuracy)) => ui
+                                            .label(format!(
+                                                "{:.0}% of {}",
+                                                accuracy * 100.0,
+                                                verb_stat.attempts
+                                            ))
+                                            .on_hover_text(recent_outcomes(&verb_stat.recent)),
+                                        _ => ui.label("–"),
+                                    };
+                                    match verb_stat.and_then(|v| v.last_seen) {
+                                        Some(last_seen) => ui.label(stats::format_ago(
+                                            now.saturating_sub(last_seen),
+                                        )),
+                                        None => ui.label("never"),
+                                    };
+                                    ui.end_row();
+                                }
+                            });
+                    },
+                );
+            });
+        self.verb_sort = sort;
+    }
+
+    /// Lists the backups of the saved state, each with an option to go back to it.
+    fn show_backups(&mut self, ui: &mut egui::Ui) {
+        let mut restore = None;
+        egui::CollapsingHeader::new("Restore from backup").show(ui, |ui| {
+            let Some(path) = state::state_path() else {
+                ui.label("There's no configuration directory to keep backups in.");
+                return;
+            };
+            let backups = state::list_backups(&path);
+            ui.label(
+                egui::RichText::new(format!(
+                    "A copy of your progress is kept each day, up to the last {}. \
+                     Restoring one backs up your current progress first.",
+                    state::BACKUPS_KEPT
+                ))
+                .small(),
+            );
+            if backups.is_empty() {
+                ui.label("No backups yet");
+            }
+            let now = stats::now();
+            for backup in backups {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} ({})",
+                        stats::format_date(backup.timestamp),
+                        stats::format_ago(now.saturating_sub(backup.timestamp))
+                    ));
+                    if ui.small_button("Restore").clicked() {
+                        restore = Some(backup);
+                    }
+                });
+            }
+            if let Some(backup_message) = &self.backup_message {
+                ui.label(egui::RichText::new(backup_message).small());
+            }
+        });
+
+        if let Some(backup) = restore {
+            self.restore_backup(&backup);
+        }
+    }
+
+    /// The verbs marked as learned, each with an option to practice it normally again.
+    fn show_learned_verbs(&mut self, ui: &mut egui::Ui) {
+        if self.learned_verbs.is_empty() {
+            return;
+        }
+        let mut learned: Vec<String> = self.learned_verbs.iter().cloned().collect();
+        learned.sort();
+
+        let mut unmark = None;
+        egui::CollapsingHeader::new(format!("Show learned ({})", learned.len()))
+            .id_salt("learned_verbs")
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new("Learned verbs are rarely asked and never count as new")
+                        .small(),
+                );
+                for infinitive in learned {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("✓ {}", infinitive));
+                        if ui
+                            .small_button("Unmark")
+                            .on_hover_text("Weight the verb normally again")
+                            .clicked()
+                        {
+                            unmark = Some(infinitive.clone());
+                        }
+                    });
+                }
+            });
+
+        if let Some(infinitive) = unmark {
+            self.toggle_learned(&infinitive);
+        }
+    }
+
+    fn show_leeches(&mut self, ui: &mut egui::Ui) {
+        let threshold = self.settings.leech_threshold;
+        let mut suspend = None;
+
+        let leeches = self.stats.leeches(threshold);
+        if leeches.is_empty() {
+            return;
+        }
+
+        ui.add_space(12.0);
+        egui::CollapsingHeader::new(format!("Leeches ({})", leeches.len()))
+            .id_salt("leeches")
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Verbs answered wrong at least {} times",
+                        threshold
+                    ))
+                    .small(),
+                );
+                for (infinitive, verb_stat) in leeches {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{} — {} wrong of {}",
+                            infinitive, verb_stat.lapses, verb_stat.attempts
+                        ));
+                        if self.disabled_verbs.contains(infinitive) {
+                            ui.label(egui::RichText::new("suspended").small());
+                        } else if ui
+                            .small_button("Suspend")
+                            .on_hover_text("Take the verb out of rotation")
+                            .clicked()
+                        {
+                            suspend = Some(infinitive.to_string());
+                        }
+                    });
+                }
+            });
+
+        if let Some(infinitive) = suspend {
+            self.disabled_verbs.insert(infinitive);
+            if !self.is_current_verb_active() {
+                self.next_verb();
+            }
+            self.save_state();
+        }
+    }
+
+    fn show_choices(
+        &mut self,
+        ui: &mut egui::Ui,
+        body_font: &egui::FontId,
+        accent_color: egui::Color32,
+    ) {
+        let mut chosen = None;
+
+        ui.vertical(|ui| {
+            ui.spacing_mut().item_spacing.y = 10.0;
+
+            for (index, choice) in self.choices.iter().enumerate() {
+                // Once answered, mark the right option and a wrong pick
+                let fill = if !self.answered {
+                    accent_color
+                } else if choice == self.correct_choice() && !self.is_answer_covered() {
+                    self.theme.correct
+                } else if *choice == self.user_answer {
+                    self.theme.wrong
+                } else {
+                    egui::Color32::from_rgb(160, 160, 180)
+                };
+
+                let mut button = egui::Button::new(
+                    egui::RichText::new(format!("{}. {}", index + 1, choice))
+                        .font(body_font.clone())
+                        .color(egui::Color32::WHITE),
+                )
+                .fill(fill)
+                .corner_radius(8.0);
+
+                if let Some((highlighted, picked_at)) = self.choice_highlight
+                    && highlighted == index
+                {
+                    let elapsed = picked_at.elapsed();
+                    if elapsed < CHOICE_HIGHLIGHT {
+                        button = button.stroke(egui::Stroke::new(3.0, self.theme.text));
+                        ui.ctx().request_repaint_after(CHOICE_HIGHLIGHT - elapsed);
+                    }
+                }
+
+                if ui
+                    .add_sized([ui.available_width().min(400.0), 44.0], button)
+                    .clicked()
+                    && !self.answered
+                {
+                    chosen = Some(index);
+                }
+            }
+        });
+
+        if let Some(index) = chosen {
+            self.choose(index);
+        }
+    }
+
+    fn correct_choice(&self) -> &str {
+        self.verbs
+            .get(self.current_verb_index)
+            .map_or("", |verb| verb.english.as_str())
+    }
+
+    /// Shows a field for the infinitive and each form, colored once they're checked.
+    fn show_form_fields(
+        &mut self,
+        ui: &mut egui::Ui,
+        font: &egui::FontId,
+        text_color: egui::Color32,
+    ) {
+        let labels = [
+            "Infinitive".to_string(),
+            self.deck_info
+                .form_name(ConjugationForm::Present)
+                .to_string(),
+            self.deck_info.form_name(ConjugationForm::Past).to_string(),
+            self.deck_info
+                .form_name(ConjugationForm::PastParticiple)
+                .to_string(),
+        ];
+        let mut submitted = false;
+
+        egui::Grid::new("all_forms_fields")
+            .num_columns(3)
+            .spacing([12.0, 8.0])
+            .show(ui, |ui| {
+                for (index, label) in labels.iter().enumerate() {
+                    let label = ui.label(
+                        egui::RichText::new(label)
+                            .font(font.clone())
+                            .color(text_color),
+                    );
+
+                    let result = self.form_results.map(|results| results[index]);
+                    let field_color = match result {
+                        Some(true) => self.theme.correct,
+                        Some(false) => self.theme.wrong,
+                        None => text_color,
+                    };
+                    let response = ui
+                        .add_sized(
+                            [ui.available_width().min(300.0), 36.0],
+                            egui::TextEdit::singleline(&mut self.form_answers[index])
+                                .font(font.clone())
+                                .text_color(field_color),
+                        )
+                        .labelled_by(label.id);
+                    if index == 0 && self.focus_answer {
+                        response.request_focus();
+                        self.focus_answer = false;
+                    }
+                    if response.gained_focus() {
+                        self.active_form_field = index;
+                    }
+                    if response.has_focus()
+                        && let Some(letter) = keyboard::letter_shortcut(ui.ctx())
+                    {
+                        keyboard::insert_at_cursor(
+                            ui.ctx(),
+                            response.id,
+                            &mut self.form_answers[index],
+                            letter,
+                        );
+                    }
+                    // Tab moves on to the next field in creation order, Enter in any
+                    // field checks all of them at once
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        submitted = true;
+                    }
+
+                    let mark = match result {
+                        Some(true) => ui.label(egui::RichText::new("✔").color(field_color)),
+                        Some(false) => ui.label(egui::RichText::new("✘").color(field_color)),
+                        None => ui.label(""),
+                    };
+                    if let Some(correct) = result {
+                        accessibility::set_name(&mark, if correct { "right" } else { "wrong" });
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if submitted {
+            self.check_answer();
+        }
+    }
+
+    fn show_settings(
+        &mut self,
+        ui: &mut egui::Ui,
+        body_font: &egui::FontId,
+        accent_color: egui::Color32,
+    ) {
+        let mut settings_frame = egui::Frame::new();
+        settings_frame = settings_frame.fill(self.panel_color());
+        settings_frame = settings_frame.stroke(egui::Stroke::new(1.0, accent_color));
+        settings_frame = settings_frame.corner_radius(8.0);
+        settings_frame = settings_frame.inner_margin(16.0);
+
+        settings_frame.show(ui, |ui| {
+            egui::CollapsingHeader::new(
+                egui::RichText::new("Settings")
+                    .font(body_font.clone())
+                    .color(accent_color)
+                    .strong(),
+            )
+            .default_open(false)
+            .show(ui, |ui| {
+                if self.settings_window_open {
+                    ui.label("The settings are open in their own window.");
+                } else {
+                    self.show_settings_contents(ui);
+                }
+            });
+        });
+    }
+
+    /// The settings in a window of their own, opened with the gear button.
+    fn show_settings_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.settings_window_open;
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .vscroll(true)
+            .default_width(480.0)
+            .show(ctx, |ui| self.show_settings_contents(ui));
+        self.settings_window_open &= open;
+    }
+
+    /// Every setting, in the settings panel or the settings window.
+    fn show_settings_contents(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        changed |= ui
+            .add(
+                egui::Slider::new(&mut self.settings.max_retries, 0..=5)
+                    .text("retries before revealing the answer"),
+            )
+            .changed();
+
+        changed |= ui
+            .add(
+                egui::Slider::new(&mut self.settings.leech_threshold, 3..=20)
+                    .text("wrong answers before a verb is a leech"),
+            )
+            .changed();
+        ui.horizontal(|ui| {
+            ui.label("A verb is mastered after");
+            changed |= ui
+                .add(egui::DragValue::new(&mut self.settings.mastery.min_attempts).range(1..=50))
+                .changed();
+            ui.label("answers with at least");
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut self.settings.mastery.min_accuracy)
+                        .range(0..=100)
+                        .suffix("%"),
+                )
+                .changed();
+            ui.label("right");
+        });
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Conjugation forms asked:");
+            let included = self.included_forms().len();
+            for form in ConjugationForm::ALL {
+                let mut asked = !self.settings.excluded_forms.contains(&form);
+                // At least one form is always asked
+                let enabled = !asked || included > 1;
+                if ui
+                    .add_enabled(
+                        enabled,
+                        egui::Checkbox::new(&mut asked, self.deck_info.form_name(form)),
+                    )
+                    .changed()
+                {
+                    if asked {
+                        self.settings.excluded_forms.retain(|other| *other != form);
+                    } else {
+                        self.settings.excluded_forms.push(form);
+                    }
+                    changed = true;
+                }
+            }
+        });
+        changed |= ui
+            .checkbox(
+                &mut self.settings.hide_english_in_conjugation,
+                "Hide the English meaning in conjugation questions until answered",
+            )
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.lock_details,
+                "Lock the verb details until the question is answered",
+            )
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.cover_answers,
+                "Cover the correct answer after a miss until it's tapped",
+            )
+            .on_hover_text("Keeps the answer out of sight of anyone watching")
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.show_ipa_in_question,
+                "Show pronunciation beneath the question",
+            )
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.note_identical_forms,
+                "Point out forms that are spelled the same",
+            )
+            .on_hover_text("E.g. when a verb's past and present tense are identical")
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.show_typing_speed,
+                "Show typing speed at the end of a session",
+            )
+            .on_hover_text("Words per minute over the answers typed right without a hint")
+            .changed();
+
+        changed |= self.show_grading_settings(ui);
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.present_phrase,
+                "Answer the present tense with a full phrase (\"jeg spiser\")",
+            )
+            .changed();
+
+        if self.settings.present_phrase {
+            ui.horizontal(|ui| {
+                ui.label("Pronoun for verbs without a phrase:");
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.settings.phrase_pronoun)
+                            .desired_width(60.0),
+                    )
+                    .on_hover_text("Leave empty to grade those verbs by the bare form")
+                    .changed();
+            });
+        }
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.spot_confused_verbs,
+                "Point out answers that belong to another verb",
+            )
+            .changed();
+
+        let groups = self.confusable_groups();
+        egui::CollapsingHeader::new(format!("Confusable verbs ({})", groups.len()))
+            .id_salt("confusable_groups")
+            .show(ui, |ui| {
+                if groups.is_empty() {
+                    ui.label(
+                        "None yet. List verbs under \"confusables\" in the deck \
+                         to drill them against each other.",
+                    );
+                }
+                for group in &groups {
+                    ui.label(group.join(" / "));
+                }
+            });
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.auto_hint,
+                "Show the first letter when a question goes unanswered",
+            )
+            .changed();
+        if self.settings.auto_hint {
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut self.settings.auto_hint_after_secs, 5..=60)
+                        .suffix(" s")
+                        .text("before the hint"),
+                )
+                .changed();
+        }
+
+        changed |= ui
+            .add(
+                egui::Slider::new(&mut self.settings.daily_goal, 0..=200)
+                    .text("answers as a daily goal"),
+            )
+            .on_hover_text("Shown as a progress bar over the question. 0 turns it off")
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.time_limit,
+                "End the session after a set time",
+            )
+            .changed();
+        if self.settings.time_limit {
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut self.settings.time_limit_minutes, 1..=60)
+                        .suffix(" min")
+                        .text("per session"),
+                )
+                .changed();
+        }
+
+        changed |= ui
+            .add(
+                egui::Slider::new(&mut self.settings.cram_size, 3..=30)
+                    .text("verbs in a cram session"),
+            )
+            .changed();
+        changed |= ui
+            .add(
+                egui::Slider::new(&mut self.settings.pool_size, 5..=50)
+                    .text("verbs in a random pool"),
+            )
+            .changed();
+
+        changed |= ui
+            .checkbox(&mut self.settings.unseen_first, "Ask unseen verbs first")
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut self.settings.review_due_first,
+                "Ask verbs that are due for review first",
+            )
+            .on_hover_text(
+                "Each answer schedules the verb's next review, further off the better \
+                 you know it",
+            )
+            .changed();
+        changed |= ui
+            .checkbox(&mut self.settings.leitner, "Study with Leitner boxes")
+            .on_hover_text(
+                "A right answer moves a verb up one of five boxes and a wrong one back to \
+                 the first. The lower its box, the more often a verb is asked, and due \
+                 reviews no longer go first",
+            )
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.introduce_with_translation,
+                "Ask new verbs as translations before conjugating them",
+            )
+            .changed();
+        if self.settings.introduce_with_translation {
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut self.settings.introduction_questions, 1..=5)
+                        .text("translation questions first"),
+                )
+                .changed();
+        }
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.teach_mode,
+                "Teach mode: study a verb's forms, then get quizzed on one",
+            )
+            .on_hover_text("Each verb is taught the first time it comes up in a session")
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.prioritize_common_verbs,
+                "Prioritize common verbs, and verbs you often miss",
+            )
+            .on_hover_text("Uses the \"frequency\" of each verb in the deck, if it has one")
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.balance_new_and_review,
+                "Balance new and review verbs",
+            )
+            .changed();
+        if self.settings.balance_new_and_review {
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut self.settings.new_verb_percent, 0..=100).suffix("% new"),
+                )
+                .changed();
+
+            let new = self.unseen_verb_count();
+            let review = self.active_verb_count() - new;
+            let effective = match (new, review) {
+                (0, _) => 0,
+                (_, 0) => 100,
+                _ => self.settings.new_verb_percent,
+            };
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} new and {} review verbs, so about {}% of questions will be new",
+                    new, review, effective
+                ))
+                .small(),
+            );
+        }
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.touch_mode,
+                "Touch mode (on-screen keyboard and larger buttons)",
+            )
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.multiple_choice,
+                "Include multiple choice questions",
+            )
+            .changed();
+
+        ui.horizontal(|ui| {
+            ui.label("Multiple choice difficulty:");
+            for difficulty in multiple_choice::Difficulty::ALL {
+                changed |= ui
+                    .radio_value(
+                        &mut self.settings.distractor_difficulty,
+                        difficulty,
+                        difficulty.label(),
+                    )
+                    .changed();
+            }
+        });
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.all_forms,
+                "Mix in English → all forms questions",
+            )
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.perfect,
+                "Mix in perfect tense questions (har or er)",
+            )
+            .changed();
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.dictation,
+                "Mix in dictation questions (listen, then type the verb)",
+            )
+            .changed();
+        if self.settings.dictation && !self.can_dictate() {
+            let reason = if cfg!(feature = "tts") {
+                format!("No {} voice was found for speech", self.deck_info.language)
+            } else {
+                "This build can't read aloud".to_string()
+            };
+            ui.label(
+                egui::RichText::new(format!("{}, so dictation questions are skipped", reason))
+                    .small()
+                    .color(self.theme.warning),
+            );
+        }
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.translate_both_ways,
+                "Translate both ways (Danish → English and English → Danish)",
+            )
+            .changed();
+        egui::CollapsingHeader::new("How often each kind of question is asked")
+            .id_salt("mode_weights")
+            .show(ui, |ui| {
+                egui::Grid::new("mode_weights_grid").show(ui, |ui| {
+                    for mode in PracticeMode::ALL {
+                        let mixed_in = self.is_mixed_in(mode);
+                        ui.label(mode.label());
+                        changed |= ui
+                            .add_enabled(
+                                mixed_in,
+                                egui::Slider::new(self.settings.mode_weights.get_mut(mode), 0..=5),
+                            )
+                            .on_disabled_hover_text("Not mixed in")
+                            .changed();
+                        ui.end_row();
+                    }
+                });
+            });
+
+        ui.horizontal(|ui| {
+            ui.label("Conjugations asked from the form back to the infinitive:");
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut self.settings.flipped_conjugation_percent, 0..=100)
+                        .suffix("%"),
+                )
+                .changed();
+        });
+
+        if ui
+            .checkbox(
+                &mut self.settings.preserve_deck_order,
+                "Preserve deck order (study verbs in file order)",
+            )
+            .changed()
+        {
+            self.apply_deck_order();
+            changed = true;
+        }
+
+        changed |= ui
+            .checkbox(
+                &mut self.settings.reshuffle_each_pass,
+                "Reshuffle and start another pass after the last verb",
+            )
+            .changed();
+
+        let text_size = ui.add(
+            egui::Slider::new(&mut self.settings.text_size_percent, 75..=200)
+                .step_by(5.0)
+                .suffix("%")
+                .text("text size"),
+        );
+        // Resizing the text while the slider is dragged would move it from under the pointer
+        if text_size.drag_stopped() || (text_size.changed() && !text_size.dragged()) {
+            self.fonts_loaded = false;
+            changed = true;
+        }
+
+        ui.add_space(8.0);
+        let mut theme_changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Theme:");
+            for name in settings::ThemeName::ALL {
+                theme_changed |= ui
+                    .selectable_value(&mut self.settings.theme, name, name.label())
+                    .changed();
+            }
+        });
+        if self.settings.theme == settings::ThemeName::Custom {
+            ui.horizontal(|ui| {
+                ui.label("Colors:");
+                let colors = &mut self.settings.colors;
+                for (name, rgb) in [
+                    ("accent", &mut colors.accent),
+                    ("background", &mut colors.background),
+                    ("text", &mut colors.text),
+                ] {
+                    let mut color = theme::to_color32(*rgb);
+                    if ui.color_edit_button_srgba(&mut color).changed() {
+                        *rgb = theme::to_rgb(color);
+                        theme_changed = true;
+                    }
+                    ui.label(name);
+                }
+                if ui.small_button("Reset to defaults").clicked() {
+                    *colors = settings::ThemeColors::default();
+                    theme_changed = true;
+                }
+            });
+        }
+        if theme_changed {
+            self.theme = theme::Theme::new(self.settings.theme, self.settings.colors);
+            // The widgets switch between egui's light and dark visuals with the theme
+            self.fonts_loaded = false;
+            changed = true;
+        }
+
+        ui.add_space(8.0);
+        ui.label(format!(
+            "Practicing {} of {} verbs",
+            self.active_verb_count(),
+            self.verbs.len()
+        ));
+
+        let mut group_changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Only practice");
+            let selected = self
+                .settings
+                .only_group
+                .map_or("every group", conjugator::Group::label);
+            egui::ComboBox::from_id_salt("only_group")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    group_changed |= ui
+                        .selectable_value(&mut self.settings.only_group, None, "every group")
+                        .changed();
+                    for group in conjugator::Group::ALL {
+                        group_changed |= ui
+                            .selectable_value(
+                                &mut self.settings.only_group,
+                                Some(group),
+                                group.label(),
+                            )
+                            .changed();
+                    }
+                });
+        });
+
+        if self.show_range_settings(ui) || group_changed {
+            if !self.is_current_verb_active() {
+                self.next_verb();
+            }
+            changed = true;
+        }
+
+        self.show_backups(ui);
+
+        if !self.disabled_verbs.is_empty() {
+            egui::CollapsingHeader::new("Disabled verbs").show(ui, |ui| {
+                let mut disabled: Vec<String> = self.disabled_verbs.iter().cloned().collect();
+                disabled.sort();
+
+                for infinitive in disabled {
+                    ui.horizontal(|ui| {
+                        ui.label(&infinitive);
+                        if ui.small_button("Enable").clicked() {
+                            self.disabled_verbs.remove(&infinitive);
+                            if !self.is_current_verb_active() {
+                                self.next_verb();
+                            }
+                            changed = true;
+                        }
+                    });
+                }
+            });
+        }
+
+        if changed {
+            self.save_state();
+        }
+    }
+}
+
+impl eframe::App for DanishVerbsApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_state();
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.show(ctx);
+    }
+}
+
+impl DanishVerbsApp {
+    /// Draws one frame, apart from `update` so it also runs without a native window.
+    fn show(&mut self, ctx: &egui::Context) {
+        // Load custom fonts if not done yet
+        self.load_fonts(ctx);
+
+        // Keep the title on the verbs being practiced, as sessions and decks change
+        let title = self.window_title();
+        if title != self.shown_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.shown_title = title;
+        }
+
+        // While cramming, move on by itself shortly after each answer
+        if let Some(advance_at) = self.cram.as_ref().and_then(|cram| cram.advance_at) {
+            let now = Instant::now();
+            if now >= advance_at {
+                self.next_verb();
+            } else {
+                ctx.request_repaint_after(advance_at - now);
+            }
+        }
+
+        // Write the answers given since the last save, once the autosave interval allows
+        match self.autosave.due_in(Instant::now()) {
+            Some(Duration::ZERO) => self.save_state(),
+            Some(wait) => ctx.request_repaint_after(wait),
+            None => {}
+        }
+
+        // Give away the first letter when a question has gone unanswered for a while
+        if self.settings.auto_hint
+            && !self.answered
+            && self.hint_level == 0
+            && self.view == View::Practice
+            && self.hint_answer().is_some()
+        {
+            let delay = Duration::from_secs(self.settings.auto_hint_after_secs.into());
+            let elapsed = self.question_shown_at.elapsed();
+            if elapsed >= delay {
+                self.reveal_hint();
+            } else {
+                ctx.request_repaint_after(delay - elapsed);
+            }
+        }
+
+        // Number keys pick a multiple choice option on the practice view, as long as no text
+        // field has focus
+        if self.view == View::Practice
+            && self.practice_mode == PracticeMode::MultipleChoice
+            && !self.answered
+            && ctx.memory(|memory| memory.focused().is_none())
+        {
+            let keys = [
+                egui::Key::Num1,
+                egui::Key::Num2,
+                egui::Key::Num3,
+                egui::Key::Num4,
+            ];
+            if let Some(index) = keys
+                .iter()
+                .position(|key| ctx.input(|input| input.key_pressed(*key)))
+            {
+                self.choose(index);
+            }
+        }
+
+        if ctx.input_mut(|input| input.consume_key(egui::Modifiers::COMMAND, egui::Key::Comma)) {
+            self.settings_window_open = !self.settings_window_open;
         }
-        self.show_result = true;
-    }
-}
 
-impl eframe::App for DanishVerbsApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Load custom fonts if not done yet
-        self.load_fonts(ctx);
+        if self.view == View::Practice {
+            self.handle_practice_keys(ctx);
+        }
+        self.show_shortcuts_window(ctx);
+        self.show_settings_window(ctx);
+        self.show_csv_import(ctx);
+        self.show_onboarding(ctx);
 
         // Store font references for later use to avoid borrowing issues
         let heading_font = self.heading_font.clone();
         let body_font = self.body_font.clone();
-        let accent_color = self.accent_color;
-        let text_color = self.text_color;
-        let background_color = self.background_color;
+        let theme = self.theme;
+        let accent_color = theme.accent;
+        let text_color = theme.text;
+        let background_color = theme.background;
 
         // Get current verb info for display
-        let current_verb = self.verbs[self.current_verb_index].clone();
+        let current_verb = self
+            .verbs
+            .get(self.current_verb_index)
+            .filter(|verb| self.is_active(verb))
+            .cloned();
         let practice_mode = self.practice_mode;
         let conjugation_form = self.conjugation_form;
         let show_result = self.show_result;
@@ -155,194 +3925,990 @@ impl eframe::App for DanishVerbsApp {
         egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
             ui.visuals_mut().override_text_color = Some(text_color);
 
+            if !(self.settings.focus_mode && self.view == View::Practice) {
+                self.paint_score_badge(ui, text_color);
+            }
+
             // App title with styled heading
             ui.vertical_centered(|ui| {
                 ui.add(egui::Label::new(
-                    egui::RichText::new("Danish Verbs Practice")
+                    egui::RichText::new(format!("{} Verbs Practice", self.deck_info.language))
                         .font(heading_font.as_ref().unwrap().clone())
                         .color(accent_color)
                         .strong(),
                 ));
             });
 
-            ui.add_space(30.0);
+            ui.add_space(10.0);
+
+            self.show_streak_banner(ui, accent_color);
+
+            let focus_toggled = ui
+                .horizontal(|ui| {
+                    ui.menu_button("File", |ui| self.show_file_menu(ui));
+                    ui.selectable_value(&mut self.view, View::Practice, "Practice");
+                    ui.selectable_value(&mut self.view, View::Browse, "Browse");
+                    ui.selectable_value(&mut self.view, View::Verbs, "Verbs");
+                    ui.separator();
+                    ui.toggle_value(&mut self.settings_window_open, "⚙")
+                        .on_hover_text("Settings");
+                    ui.toggle_value(&mut self.settings.focus_mode, "🎯 Focus")
+                        .on_hover_text("Hide everything but the question and your answer")
+                        .changed()
+                })
+                .inner;
+            if focus_toggled {
+                self.save_state();
+            }
+            let focus_mode = self.settings.focus_mode && self.view == View::Practice;
+
+            ui.add_space(20.0);
+
+            if let Some(load_error) = &self.load_error {
+                egui::Frame::new()
+                    .fill(self.theme.tint(self.theme.wrong))
+                    .stroke(egui::Stroke::new(1.0, self.theme.wrong))
+                    .corner_radius(8.0)
+                    .inner_margin(16.0)
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new(load_error)
+                                .color(self.theme.wrong),
+                        );
+                    });
+                ui.add_space(20.0);
+            }
+
+            if self.view == View::Practice {
+                self.show_session_summary(ui, accent_color);
+            }
+
+            if let Some(save_error) = &self.save_error {
+                ui.label(
+                    egui::RichText::new(format!("⚠ {}", save_error))
+                        .small()
+                        .color(self.theme.warning),
+                );
+                ui.add_space(10.0);
+            }
+
+            if let Some(load_warning) = &self.load_warning {
+                egui::Frame::new()
+                    .fill(self.theme.tint(self.theme.warning))
+                    .stroke(egui::Stroke::new(1.0, self.theme.warning))
+                    .corner_radius(8.0)
+                    .inner_margin(16.0)
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new(load_warning)
+                                .color(self.theme.warning),
+                        );
+                    });
+                ui.add_space(20.0);
+            }
+
+            if self.view == View::Browse {
+                self.show_browser(
+                    ui,
+                    body_font.as_ref().unwrap(),
+                    heading_font.as_ref().unwrap(),
+                    accent_color,
+                );
+                return;
+            }
+            if self.view == View::Verbs {
+                self.show_verb_list(ui, body_font.as_ref().unwrap());
+                return;
+            }
+
+            let Some(current_verb) = current_verb else {
+                let message = if self.verbs.is_empty() {
+                    "No verbs were loaded."
+                } else if self.deck_range().is_some_and(|range| range.is_empty()) {
+                    "The chosen range of the deck is empty. Change it in the settings to keep practicing."
+                } else if self.settings.only_group.is_some() {
+                    "There are no verbs to practice in the chosen group. Choose another in the settings to keep practicing."
+                } else {
+                    "All verbs are disabled. Re-enable some in the settings to keep practicing."
+                };
+                ui.label(
+                    egui::RichText::new(message)
+                        .font(body_font.as_ref().unwrap().clone())
+                        .color(text_color),
+                );
+                ui.add_space(20.0);
+                self.show_settings(ui, body_font.as_ref().unwrap(), accent_color);
+                return;
+            };
+
+            if self.pass_finished {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "🎉 You've been through every verb. Passes completed: {}",
+                        self.passes_completed
+                    ))
+                    .font(body_font.as_ref().unwrap().clone())
+                    .color(text_color),
+                );
+                ui.add_space(10.0);
+                if ui.button("Start another pass").clicked() {
+                    self.pass_finished = false;
+                    self.next_verb();
+                }
+                return;
+            }
+
+            self.show_clipboard_controls(ui, accent_color);
+            self.show_cram_controls(ui, accent_color);
+            self.show_pool_controls(ui, accent_color);
+            self.show_drill_controls(ui, accent_color);
+            ui.add_space(10.0);
+
+            if !focus_mode {
+                self.show_mastery(ui, accent_color);
+            }
+
+            if !focus_mode && self.cram.is_none() && self.pool.is_none() {
+                let active = self.active_verb_count();
+                let seen = self.pass_seen.len().min(active);
+                ui.add(
+                    egui::ProgressBar::new(seen as f32 / active.max(1) as f32)
+                        .desired_width(300.0)
+                        .text(format!(
+                            "Pass {}: {}/{} verbs",
+                            self.passes_completed + 1,
+                            seen,
+                            active
+                        )),
+                );
+                ui.add_space(10.0);
+            }
+
+            if self.settings.time_limit {
+                let text = match self.session_time_left() {
+                    Some(left) => {
+                        // Count down while the session runs
+                        ctx.request_repaint_after(Duration::from_secs(1));
+                        let seconds = left.as_secs();
+                        if seconds == 0 {
+                            "⏱ Time's up — the session ends with this answer".to_string()
+                        } else {
+                            format!("⏱ {}:{:02} left", seconds / 60, seconds % 60)
+                        }
+                    }
+                    None => format!(
+                        "⏱ {} minute session, starting with the first answer",
+                        self.settings.time_limit_minutes
+                    ),
+                };
+                ui.label(egui::RichText::new(text).small().color(accent_color));
+                ui.add_space(6.0);
+            }
+
+            if self.teaching {
+                self.show_teaching(
+                    ui,
+                    &current_verb,
+                    body_font.as_ref().unwrap(),
+                    accent_color,
+                );
+                return;
+            }
 
             let question_text = match practice_mode {
                 PracticeMode::Translation => {
                     format!("Translate to English: {}", current_verb.infinitive)
                 }
+                PracticeMode::ReverseTranslation => {
+                    format!(
+                        "Translate to {}: {}",
+                        self.deck_info.language, current_verb.english
+                    )
+                }
+                PracticeMode::MultipleChoice => {
+                    format!("Choose the English meaning of: {}", current_verb.infinitive)
+                }
+                PracticeMode::AllForms => {
+                    format!(
+                        "Give every {} form of: {}",
+                        self.deck_info.language, current_verb.english
+                    )
+                }
+                PracticeMode::Dictation => {
+                    format!("Type the {} verb you hear", self.deck_info.language)
+                }
+                PracticeMode::Perfect => {
+                    let pronoun = self.settings.phrase_pronoun.trim();
+                    if pronoun.is_empty() {
+                        format!(
+                            "Put '{}' in the perfect tense, with har or er",
+                            current_verb.infinitive
+                        )
+                    } else {
+                        format!(
+                            "Put '{}' in the perfect tense, starting with '{}'",
+                            current_verb.infinitive, pronoun
+                        )
+                    }
+                }
+                // The form is named, as it may be spelled like another form of the verb
+                PracticeMode::Conjugation if self.conjugation_flipped => {
+                    format!(
+                        "Give the infinitive of '{}' ({})",
+                        grading::format_variants(current_verb.form(conjugation_form)),
+                        self.deck_info.form_name(conjugation_form)
+                    )
+                }
                 PracticeMode::Conjugation => {
-                    let form_name = match conjugation_form {
-                        ConjugationForm::Present => "present tense",
-                        ConjugationForm::Past => "past tense",
-                        ConjugationForm::PastParticiple => "past participle",
+                    let phrase_hint = if self.expected_phrases(&current_verb).is_empty() {
+                        ""
+                    } else {
+                        " as a full phrase"
                     };
-                    format!("Conjugate '{}' in {}", current_verb.infinitive, form_name)
+                    format!(
+                        "Conjugate '{}' in {}{}",
+                        current_verb.infinitive,
+                        self.deck_info.form_name(conjugation_form),
+                        phrase_hint
+                    )
                 }
             };
 
-            // Display the question in a styled box
-            ui.add(egui::Label::new(
-                egui::RichText::new(question_text)
-                    .font(body_font.as_ref().unwrap().clone())
-                    .color(text_color)
-                    .strong(),
-            ));
+            if !focus_mode
+                && self
+                    .stats
+                    .is_leech(&current_verb.infinitive, self.settings.leech_threshold)
+            {
+                ui.label(
+                    egui::RichText::new("🐛 Leech")
+                        .small()
+                        .color(self.theme.warning),
+                )
+                .on_hover_text("You often get this verb wrong");
+            }
+            if self.introducing && !focus_mode {
+                ui.label(
+                    egui::RichText::new("✨ New verb")
+                        .small()
+                        .color(accent_color),
+                )
+                .on_hover_text("New verbs are asked as translations a few times first");
+            }
+            if self.settings.teach_mode
+                && !focus_mode
+                && self.taught_verbs.contains(&current_verb.infinitive)
+            {
+                ui.label(
+                    egui::RichText::new("📖 Taught this session")
+                        .small()
+                        .color(accent_color),
+                )
+                .on_hover_text("You studied this verb's forms earlier in the session");
+            }
 
-            ui.add_space(20.0);
+            if self.settings.review_due_first && !focus_mode {
+                let due = self.due_verb_count();
+                if due > 0 {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "🔁 {} verb{} due for review",
+                            due,
+                            if due == 1 { "" } else { "s" }
+                        ))
+                        .small()
+                        .color(accent_color),
+                    );
+                }
+            }
 
-            // User input field
-            ui.horizontal(|ui| {
-                ui.add(egui::Label::new(
-                    egui::RichText::new("Your answer:")
+            if self.settings.unseen_first && !focus_mode {
+                let unseen = self.unseen_verb_count();
+                let progress = if unseen == 0 {
+                    "All verbs seen — reviewing".to_string()
+                } else {
+                    format!("{} unseen verbs left", unseen)
+                };
+                ui.label(egui::RichText::new(progress).small().color(accent_color));
+            }
+
+            // Display the question in a styled box
+            ui.horizontal_wrapped(|ui| {
+                let question = ui.add(egui::Label::new(
+                    egui::RichText::new(question_text)
                         .font(body_font.as_ref().unwrap().clone())
-                        .color(text_color),
+                        .color(text_color)
+                        .strong(),
                 ));
+                // Read out each new question, multiple choice ones have no field to focus
+                accessibility::announce_changes(&question);
 
-                let response = ui.add_sized(
-                    [ui.available_width() - 120.0, 40.0],
-                    egui::TextEdit::singleline(&mut self.user_answer)
-                        .font(body_font.as_ref().unwrap().clone())
-                        .hint_text("Type your answer here"),
+                let starred = self.starred_verbs.contains(&current_verb.infinitive);
+                let star = ui
+                    .small_button(if starred { "★" } else { "☆" })
+                    .on_hover_text("Star this verb (S)");
+                accessibility::set_name(
+                    &star,
+                    if starred {
+                        "Unstar this verb"
+                    } else {
+                        "Star this verb"
+                    },
                 );
-
-                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    self.check_answer();
+                if star.clicked() {
+                    self.toggle_star();
                 }
+                let learned = self.learned_verbs.contains(&current_verb.infinitive);
+                if learned {
+                    ui.label(
+                        egui::RichText::new("✓ Learned")
+                            .small()
+                            .color(self.theme.correct),
+                    );
+                }
+                if ui
+                    .small_button(if learned { "Unmark learned" } else { "Mark learned" })
+                    .on_hover_text("Learned verbs are rarely asked, whatever their results")
+                    .clicked()
+                {
+                    let infinitive = current_verb.infinitive.clone();
+                    self.toggle_learned(&infinitive);
+                }
+                let speaker = if practice_mode == PracticeMode::Dictation {
+                    "🔊 Replay"
+                } else {
+                    "🔊"
+                };
+                let pronounce = ui
+                    .add_enabled(self.can_pronounce(), egui::Button::new(speaker).small())
+                    .on_hover_text("Pronounce the verb (P)")
+                    .on_disabled_hover_text("Available once the question is answered");
+                accessibility::set_name(
+                    &pronounce,
+                    if practice_mode == PracticeMode::Dictation {
+                        "Replay the verb"
+                    } else {
+                        "Pronounce the verb"
+                    },
+                );
+                if pronounce.clicked() {
+                    self.pronounce();
+                }
+                let help = ui.menu_button("?", |ui| {
+                    if ui.button("Keyboard shortcuts").clicked() {
+                        self.show_shortcuts = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Show the walkthrough").clicked() {
+                        self.start_onboarding();
+                        ui.close_menu();
+                    }
+                });
+                accessibility::set_name(&help.response.on_hover_text("Help"), "Help");
             });
 
+            if let Some(speech_error) = &self.speech_error {
+                ui.label(
+                    egui::RichText::new(speech_error)
+                        .small()
+                        .color(self.theme.warning),
+                );
+            }
+
+            // The pronunciation would give away a dictation question
+            if self.settings.show_ipa_in_question
+                && !current_verb.ipa.is_empty()
+                && practice_mode != PracticeMode::Dictation
+            {
+                ui.label(
+                    egui::RichText::new(format!("[{}]", current_verb.ipa))
+                        .text_style(egui::TextStyle::Monospace)
+                        .color(text_color),
+                );
+            }
+
             ui.add_space(20.0);
 
-            // Buttons with improved styling
-            ui.horizontal(|ui| {
-                ui.spacing_mut().item_spacing.x = 20.0;
+            if practice_mode == PracticeMode::MultipleChoice {
+                self.show_choices(ui, body_font.as_ref().unwrap(), accent_color);
+            } else if practice_mode == PracticeMode::AllForms {
+                self.show_form_fields(ui, body_font.as_ref().unwrap(), text_color);
+            } else {
+                // User input field, stacked under its label in narrow windows
+                let narrow = ui.available_width() < NARROW_LAYOUT_WIDTH;
+                let layout = if narrow {
+                    egui::Layout::top_down(egui::Align::Min)
+                } else {
+                    egui::Layout::left_to_right(egui::Align::Center)
+                };
+                ui.with_layout(layout, |ui| {
+                    let label = ui.add(egui::Label::new(
+                        egui::RichText::new("Your answer:")
+                            .font(body_font.as_ref().unwrap().clone())
+                            .color(text_color),
+                    ));
 
-                let check_button = ui.add_sized(
-                    [150.0, 50.0],
-                    egui::Button::new(
-                        egui::RichText::new("Check")
+                    // The touch keyboard has its own keys for the Danish letters
+                    let letter_buttons = !self.settings.touch_mode;
+                    let width = if narrow {
+                        ui.available_width()
+                    } else if letter_buttons {
+                        ui.available_width() - 260.0
+                    } else {
+                        ui.available_width() - 120.0
+                    };
+                    let response = ui.add_sized(
+                        [width.max(MIN_ANSWER_WIDTH), 40.0],
+                        egui::TextEdit::singleline(&mut self.user_answer)
                             .font(body_font.as_ref().unwrap().clone())
-                            .color(egui::Color32::WHITE),
+                            .hint_text("Type your answer here"),
                     )
-                    .fill(accent_color)
-                    .corner_radius(8.0),
-                );
+                    .labelled_by(label.id);
+                    if self.focus_answer {
+                        response.request_focus();
+                        self.focus_answer = false;
+                    }
+
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        self.check_answer();
+                    }
+
+                    let mut letter = response
+                        .has_focus()
+                        .then(|| keyboard::letter_shortcut(ui.ctx()))
+                        .flatten();
+                    if letter_buttons {
+                        ui.horizontal(|ui| {
+                            if let Some(clicked) =
+                                keyboard::show_letter_buttons(ui, body_font.as_ref().unwrap())
+                            {
+                                letter = Some(clicked);
+                                // Clicking took the focus from the field, so give it back
+                                response.request_focus();
+                            }
+                        });
+                    }
+                    if let Some(letter) = letter {
+                        keyboard::insert_at_cursor(
+                            ui.ctx(),
+                            response.id,
+                            &mut self.user_answer,
+                            letter,
+                        );
+                    }
+                });
+            }
+
+            if self.settings.touch_mode && practice_mode != PracticeMode::MultipleChoice {
+                ui.add_space(12.0);
+                match keyboard::show(ui, body_font.as_ref().unwrap(), accent_color) {
+                    Some(keyboard::KeyPress::Char(ch)) => self.answer_field_mut().push(ch),
+                    Some(keyboard::KeyPress::Backspace) => {
+                        self.answer_field_mut().pop();
+                    }
+                    // Enter grades the answer, or moves on once it's graded
+                    Some(keyboard::KeyPress::Enter) => {
+                        if self.answered {
+                            self.next_verb();
+                        } else {
+                            self.check_answer();
+                        }
+                    }
+                    None => {}
+                }
+            }
+
+            ui.add_space(20.0);
+
+            // Buttons with improved styling, larger for touch screens
+            let action_button_size = if self.settings.touch_mode {
+                [200.0, 70.0]
+            } else {
+                [150.0, 50.0]
+            };
+            ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 20.0;
+
+                if practice_mode != PracticeMode::MultipleChoice {
+                    // A graded question is moved on from rather than checked again
+                    let check_button = ui.add_enabled(
+                        !self.answered,
+                        egui::Button::new(
+                            egui::RichText::new("Check")
+                                .font(body_font.as_ref().unwrap().clone())
+                                .color(egui::Color32::WHITE),
+                        )
+                        .fill(accent_color)
+                        .min_size(action_button_size.into())
+                        .corner_radius(8.0),
+                    );
 
-                if check_button.clicked() {
-                    self.check_answer();
+                    if check_button.clicked() {
+                        self.check_answer();
+                    }
+
+                    let hint_button = ui.add_enabled(
+                        !self.answered && self.hint_level < hint::LEVELS,
+                        egui::Button::new(
+                            egui::RichText::new("Hint").font(body_font.as_ref().unwrap().clone()),
+                        )
+                        .min_size(action_button_size.into())
+                        .corner_radius(8.0),
+                    );
+                    if hint_button
+                        .on_hover_text(
+                            "Show the first letter, then the length, then every other letter",
+                        )
+                        .clicked()
+                    {
+                        self.reveal_hint();
+                    }
                 }
 
                 let next_button = ui.add_sized(
-                    [150.0, 50.0],
+                    action_button_size,
                     egui::Button::new(
                         egui::RichText::new("Next verb")
                             .font(body_font.as_ref().unwrap().clone())
                             .color(egui::Color32::WHITE),
                     )
-                    .fill(egui::Color32::from_rgb(76, 175, 80))
+                    .fill(self.theme.correct)
                     .corner_radius(8.0),
                 );
 
+                // Once a question is over, Enter or Space on the button moves on
+                if self.focus_next_button {
+                    next_button.request_focus();
+                    self.focus_next_button = false;
+                }
                 if next_button.clicked() {
                     self.next_verb();
                 }
             });
 
+            if !self.answered
+                && let Some(hint) = self.hint_text()
+            {
+                ui.add_space(10.0);
+                let hint = ui.label(
+                    egui::RichText::new(format!("Hint: {}", hint))
+                        .font(body_font.as_ref().unwrap().clone())
+                        .color(self.theme.warning),
+                );
+                accessibility::announce_changes(&hint);
+            }
+
             // Result message
             if show_result {
                 ui.add_space(20.0);
 
                 let text_color = if result_message.starts_with("Correct") {
-                    egui::Color32::from_rgb(76, 175, 80) // Green
+                    self.theme.correct
+                } else if result_message.starts_with("Try again")
+                    || result_message.starts_with("Almost")
+                {
+                    self.theme.warning
                 } else {
-                    egui::Color32::from_rgb(211, 47, 47) // Red
+                    self.theme.wrong
                 };
 
-                let result_text = egui::RichText::new(&result_message)
-                    .font(body_font.as_ref().unwrap().clone())
-                    .color(text_color)
-                    .strong();
+                let covered = self.is_answer_covered();
+                let result_text = egui::RichText::new(if covered {
+                    "Incorrect."
+                } else {
+                    &result_message
+                })
+                .font(body_font.as_ref().unwrap().clone())
+                .color(text_color)
+                .strong();
+
+                let result = ui.add(egui::Label::new(result_text));
+                accessibility::announce_changes(&result);
+
+                if covered {
+                    ui.add_space(8.0);
+                    let cover = egui::Button::new(
+                        egui::RichText::new("••••••••  Tap to reveal")
+                            .font(body_font.as_ref().unwrap().clone())
+                            .color(egui::Color32::WHITE),
+                    )
+                    .fill(egui::Color32::from_rgb(160, 160, 180))
+                    .corner_radius(8.0);
+                    let cover = ui.add(cover).on_hover_text("Show the correct answer");
+                    accessibility::set_name(&cover, "Reveal the correct answer");
+                    if cover.clicked() {
+                        self.answer_revealed = true;
+                    }
+                } else if !self.answer_diff.is_empty() {
+                    ui.add_space(8.0);
+                    show_answer_diff(
+                        ui,
+                        &self.answer_diff,
+                        body_font.as_ref().unwrap(),
+                        &self.theme,
+                    );
+                }
+
+                // An expected form that looks like another one would otherwise seem like a mistake
+                if self.answered
+                    && !covered
+                    && practice_mode == PracticeMode::Conjugation
+                    && !self.conjugation_flipped
+                    && self.settings.note_identical_forms
+                {
+                    let same = current_verb.forms_spelled_like(conjugation_form);
+                    if !same.is_empty() {
+                        let others: Vec<&str> = same
+                            .into_iter()
+                            .map(|form| self.deck_info.form_name(form))
+                            .collect();
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Note: this verb's {} and {} are spelled the same",
+                                self.deck_info.form_name(conjugation_form),
+                                others.join(" and ")
+                            ))
+                            .small()
+                            .italics()
+                            .color(self.theme.text),
+                        );
+                    }
+                }
+
+                // The note could give the answer away, so it waits until the question is over
+                if self.answered && !covered && !current_verb.note.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label(
+                        egui::RichText::new(format!("📝 {}", current_verb.note))
+                            .font(body_font.as_ref().unwrap().clone())
+                            .color(self.theme.text)
+                            .italics(),
+                    );
+                }
+            }
 
-                ui.add(egui::Label::new(result_text));
+            // Focus mode stops here, the answer is still revealed above once it's graded
+            if focus_mode {
+                return;
             }
 
             ui.add_space(30.0);
 
             // Verb details section with improved styling
             let mut detail_frame = egui::Frame::new();
-            detail_frame = detail_frame.fill(egui::Color32::from_rgb(230, 230, 250));
+            detail_frame = detail_frame.fill(self.panel_color());
             detail_frame = detail_frame.stroke(egui::Stroke::new(1.0, accent_color));
             detail_frame = detail_frame.corner_radius(8.0);
             detail_frame = detail_frame.inner_margin(16.0);
 
-            detail_frame.show(ui, |ui| {
-                egui::CollapsingHeader::new(
-                    egui::RichText::new("Verb details")
-                        .font(body_font.as_ref().unwrap().clone())
-                        .color(accent_color)
-                        .strong(),
-                )
-                .default_open(false)
+            // The details give the answer away, so they stay shut until the question is over
+            // and reopen afterwards if they were open after the last question
+            let live = !self.answered;
+            let locked = (live && self.settings.lock_details) || self.is_answer_covered();
+            let open = if locked {
+                Some(false)
+            } else if self.sync_details {
+                self.sync_details = false;
+                Some(!live && self.details_open)
+            } else {
+                None
+            };
+
+            let details = detail_frame
                 .show(ui, |ui| {
-                    ui.spacing_mut().item_spacing.y = 8.0;
+                    ui.add_enabled_ui(!locked, |ui| {
+                        egui::CollapsingHeader::new(
+                            egui::RichText::new("Verb details")
+                                .font(body_font.as_ref().unwrap().clone())
+                                .color(accent_color)
+                                .strong(),
+                        )
+                        .default_open(false)
+                        .open(open)
+                        .show(ui, |ui| {
+                            ui.spacing_mut().item_spacing.y = 8.0;
 
-                    // Clone TextStyle for reuse
-                    let verb_details_style = egui::TextStyle::Body;
+                            // Clone TextStyle for reuse
+                            let verb_details_style = egui::TextStyle::Body;
 
-                    ui.label(
-                        egui::RichText::new(format!("Infinitive: {}", current_verb.infinitive))
-                            .font(body_font.as_ref().unwrap().clone())
-                            .text_style(verb_details_style.clone()),
-                    );
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Infinitive: {}",
+                                    current_verb.infinitive
+                                ))
+                                .font(body_font.as_ref().unwrap().clone())
+                                .text_style(verb_details_style.clone()),
+                            );
 
-                    ui.label(
-                        egui::RichText::new(format!("Present: {}", current_verb.present))
-                            .font(body_font.as_ref().unwrap().clone())
-                            .text_style(verb_details_style.clone()),
-                    );
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Present: {}",
+                                    grading::format_variants(&current_verb.present)
+                                ))
+                                .font(body_font.as_ref().unwrap().clone())
+                                .text_style(verb_details_style.clone()),
+                            );
 
-                    ui.label(
-                        egui::RichText::new(format!("Past: {}", current_verb.past))
-                            .font(body_font.as_ref().unwrap().clone())
-                            .text_style(verb_details_style.clone()),
-                    );
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Past: {}",
+                                    grading::format_variants(&current_verb.past)
+                                ))
+                                .font(body_font.as_ref().unwrap().clone())
+                                .text_style(verb_details_style.clone()),
+                            );
 
-                    ui.label(
-                        egui::RichText::new(format!(
-                            "Past participle: {}",
-                            current_verb.past_participle
-                        ))
-                        .font(body_font.as_ref().unwrap().clone())
-                        .text_style(verb_details_style.clone()),
-                    );
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Past participle: {}",
+                                    grading::format_variants(&current_verb.past_participle)
+                                ))
+                                .font(body_font.as_ref().unwrap().clone())
+                                .text_style(verb_details_style.clone()),
+                            );
 
-                    ui.label(
-                        egui::RichText::new(format!("English: {}", current_verb.english))
-                            .font(body_font.as_ref().unwrap().clone())
-                            .text_style(verb_details_style.clone()),
-                    );
-                });
-            });
+                            for (name, value) in [
+                                ("Imperative", &current_verb.imperative),
+                                ("Present participle", &current_verb.present_participle),
+                            ] {
+                                if !value.is_empty() {
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "{}: {}",
+                                            name,
+                                            grading::format_variants(value)
+                                        ))
+                                        .font(body_font.as_ref().unwrap().clone())
+                                        .text_style(verb_details_style.clone()),
+                                    );
+                                }
+                            }
+
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Perfect: {} {}",
+                                    grading::format_variants(current_verb.auxiliary()),
+                                    grading::format_variants(&current_verb.past_participle)
+                                ))
+                                .font(body_font.as_ref().unwrap().clone())
+                                .text_style(verb_details_style.clone()),
+                            );
+
+                            let hide_english = self.settings.hide_english_in_conjugation
+                                && practice_mode == PracticeMode::Conjugation
+                                && !self.answered;
+                            let english = if hide_english {
+                                "hidden until you answer"
+                            } else {
+                                current_verb.english.as_str()
+                            };
+                            ui.label(
+                                egui::RichText::new(format!("English: {}", english))
+                                    .font(body_font.as_ref().unwrap().clone())
+                                    .text_style(verb_details_style.clone()),
+                            );
+
+                            if !current_verb.ipa.is_empty() {
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        egui::RichText::new("Pronunciation:")
+                                            .font(body_font.as_ref().unwrap().clone())
+                                            .text_style(verb_details_style.clone()),
+                                    );
+                                    ui.label(
+                                        egui::RichText::new(format!("[{}]", current_verb.ipa))
+                                            .text_style(egui::TextStyle::Monospace),
+                                    );
+                                });
+                            }
+
+                            let verb_tally = self.stats.by_verb(&current_verb.infinitive);
+                            if verb_tally.attempts > 0 {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "This session: {}/{} correct",
+                                        verb_tally.correct, verb_tally.attempts
+                                    ))
+                                    .font(body_font.as_ref().unwrap().clone())
+                                    .text_style(verb_details_style.clone()),
+                                );
+                            }
+
+                            if let Some(verb_stat) = self.stats.verbs.get(&current_verb.infinitive)
+                            {
+                                let hinted = if verb_stat.hinted > 0 {
+                                    format!(", {} with a hint", verb_stat.hinted)
+                                } else {
+                                    String::new()
+                                };
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "All time: {}/{} correct{}",
+                                        verb_stat.correct, verb_stat.attempts, hinted
+                                    ))
+                                    .font(body_font.as_ref().unwrap().clone())
+                                    .text_style(verb_details_style.clone()),
+                                );
+                            }
+
+                            // A memory hook usually gives the answer away as well
+                            if self.answered {
+                                ui.label(
+                                    egui::RichText::new("Note:")
+                                        .font(body_font.as_ref().unwrap().clone())
+                                        .text_style(verb_details_style.clone()),
+                                );
+                                if let Some(verb) = self.verbs.get_mut(self.current_verb_index) {
+                                    ui.add(
+                                        egui::TextEdit::multiline(&mut verb.note)
+                                            .desired_rows(2)
+                                            .hint_text("A memory hook for this verb"),
+                                    );
+                                }
+                                ui.horizontal(|ui| {
+                                    // Verbs of a temporary session have no deck file to save to
+                                    if ui
+                                        .add_enabled(
+                                            self.saved_deck.is_none(),
+                                            egui::Button::new("Save note"),
+                                        )
+                                        .clicked()
+                                    {
+                                        self.save_note();
+                                    }
+                                    if let Some(note_message) = &self.note_message {
+                                        ui.label(egui::RichText::new(note_message).small());
+                                    }
+                                });
+                            } else {
+                                ui.label(
+                                    egui::RichText::new("Note: hidden until you answer")
+                                        .font(body_font.as_ref().unwrap().clone())
+                                        .text_style(verb_details_style.clone()),
+                                );
+                            }
+
+                            if ui
+                        .button("Disable this verb")
+                        .on_hover_text(
+                            "Stop practicing this verb. It can be re-enabled in the settings.",
+                        )
+                        .clicked()
+                    {
+                        self.disable_current_verb();
+                    }
+                        })
+                    })
+                    .inner
+                })
+                .inner;
+            details
+                .header_response
+                .on_disabled_hover_text("Answer the question to see the details");
+            if open.is_none() && !live {
+                self.details_open = details.openness > 0.5;
+            }
+
+            ui.add_space(20.0);
+
+            self.show_statistics(ui, body_font.as_ref().unwrap(), accent_color);
+
+            ui.add_space(20.0);
+
+            self.show_settings(ui, body_font.as_ref().unwrap(), accent_color);
         });
     }
 }
 
-fn load_verbs() -> Vec<Verb> {
-    let verbs_path = Path::new("src/verbs.json");
+/// Shows the answer and the correct answer with the differing characters highlighted.
+fn show_answer_diff(
+    ui: &mut egui::Ui,
+    diff: &[grading::DiffChar],
+    font: &egui::FontId,
+    theme: &theme::Theme,
+) {
+    let highlight = theme.wrong;
+    let mut answer_job = egui::text::LayoutJob::default();
+    let mut expected_job = egui::text::LayoutJob::default();
+    let plain = egui::TextFormat::simple(font.clone(), theme.text);
 
-    match fs::read_to_string(verbs_path) {
-        Ok(data) => match serde_json::from_str(&data) {
-            Ok(verbs) => verbs,
-            Err(e) => {
-                eprintln!("Error parsing verb data: {}", e);
-                vec![]
+    answer_job.append("Your answer: ", 0.0, plain.clone());
+    expected_job.append("Correct: ", 0.0, plain.clone());
+
+    for diff_char in diff {
+        let ch = diff_char.ch.to_string();
+        match diff_char.kind {
+            grading::DiffKind::Same => {
+                answer_job.append(&ch, 0.0, plain.clone());
+                expected_job.append(&ch, 0.0, plain.clone());
+            }
+            grading::DiffKind::Extra => {
+                answer_job.append(
+                    &ch,
+                    0.0,
+                    egui::TextFormat {
+                        color: highlight,
+                        strikethrough: egui::Stroke::new(1.5, highlight),
+                        ..plain.clone()
+                    },
+                );
+            }
+            grading::DiffKind::Missing => {
+                expected_job.append(
+                    &ch,
+                    0.0,
+                    egui::TextFormat {
+                        color: highlight,
+                        underline: egui::Stroke::new(1.5, highlight),
+                        ..plain.clone()
+                    },
+                );
             }
-        },
-        Err(e) => {
-            eprintln!("Error reading verb file: {}", e);
-            vec![]
         }
     }
+
+    ui.label(answer_job);
+    ui.label(expected_job);
+}
+
+/// A verb's latest outcomes as marks, e.g. "Latest answers: ✔ ✔ ✘ ✔".
+fn recent_outcomes(recent: &[bool]) -> String {
+    let marks: Vec<&str> = recent
+        .iter()
+        .map(|&correct| if correct { "✔" } else { "✘" })
+        .collect();
+    format!("Latest answers: {}", marks.join(" "))
+}
+
+/// The user's deck in the configuration directory, or in the working directory without one.
+fn default_deck_path() -> PathBuf {
+    state::deck_path().unwrap_or_else(|| PathBuf::from(DEFAULT_DECK_PATH))
+}
+
+fn built_in_deck() -> deck::Deck {
+    let mut deck = deck::parse_deck(BUILT_IN_DECK).expect("the built-in deck is valid");
+    for (position, verb) in deck.verbs.iter_mut().enumerate() {
+        verb.position = position;
+    }
+    deck
+}
+
+fn load_deck(deck_path: &Path) -> Result<deck::Deck, String> {
+    let bytes = fs::read(deck_path)
+        .map_err(|e| format!("Error reading verb file {}: {}", deck_path.display(), e))?;
+    let (data, fallback) = deck::decode_deck(bytes);
+    let mut deck = deck::parse_deck(&data)
+        .map_err(|e| format!("Error parsing {}: {}", deck_path.display(), e))?;
+
+    if fallback {
+        deck.warning = Some(format!(
+            "{} isn't valid UTF-8, so it was read as Windows-1252. Check that letters like æ, ø and å look right, and save the file as UTF-8 to silence this warning.",
+            deck_path.display()
+        ));
+    }
+    for (position, verb) in deck.verbs.iter_mut().enumerate() {
+        verb.position = position;
+    }
+    Ok(deck)
 }
 
 fn main() -> eframe::Result<()> {
@@ -353,9 +4919,243 @@ fn main() -> eframe::Result<()> {
         ..Default::default()
     };
 
+    // An optional deck file can be passed as an argument, along with `--serve PORT`
+    let mut deck_path = None;
+    let mut serve_port: Option<u16> = None;
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--serve" {
+            match args.next().and_then(|port| port.to_str()?.parse().ok()) {
+                Some(port) => serve_port = Some(port),
+                None => eprintln!("--serve needs a port number"),
+            }
+        } else {
+            deck_path = Some(PathBuf::from(arg));
+        }
+    }
+
     eframe::run_native(
-        "Danish Verbs Practice",
+        APP_TITLE,
         options,
-        Box::new(|_cc| Ok(Box::new(DanishVerbsApp::new()))),
+        Box::new(|_cc| {
+            let mut app = DanishVerbsApp::new(deck_path);
+            if let Some(port) = serve_port {
+                app.serve_stats(port);
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_and_draws_a_question_with_a_large_deck() {
+        let verbs: Vec<serde_json::Value> = (0..50_000)
+            .map(|index| {
+                serde_json::json!({
+                    "infinitive": format!("at verbe{}", index),
+                    "present": format!("verber{}", index),
+                    "past": format!("verbede{}", index),
+                    "past_participle": format!("verbet{}", index),
+                    "english": format!("to verb {}", index),
+                })
+            })
+            .collect();
+        let path = std::env::temp_dir().join(format!("large-deck-{}.json", std::process::id()));
+        fs::write(&path, serde_json::to_string(&verbs).unwrap()).unwrap();
+
+        let started = Instant::now();
+        let mut app = DanishVerbsApp::with_state(Some(path.clone()), AppState::default());
+        let ctx = egui::Context::default();
+        for _ in 0..3 {
+            let _ = ctx.run(egui::RawInput::default(), |ctx| app.show(ctx));
+        }
+        let elapsed = started.elapsed();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(app.verbs.len(), 50_000);
+        assert!(app.verbs.get(app.current_verb_index).is_some());
+        assert!(
+            elapsed < Duration::from_secs(5),
+            "starting up took {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn clears_the_answer_and_opens_the_settings_from_the_keyboard() {
+        let verbs = serde_json::json!([{
+            "infinitive": "at gå",
+            "present": "går",
+            "past": "gik",
+            "past_participle": "gået",
+            "english": "to go",
+        }]);
+        let path = std::env::temp_dir().join(format!("keys-deck-{}.json", std::process::id()));
+        fs::write(&path, verbs.to_string()).unwrap();
+        let mut app = DanishVerbsApp::with_state(Some(path.clone()), AppState::default());
+        let _ = fs::remove_file(&path);
+        app.practice_mode = PracticeMode::Translation;
+
+        let ctx = egui::Context::default();
+        let press = |app: &mut DanishVerbsApp, key, modifiers| {
+            let input = egui::RawInput {
+                events: vec![egui::Event::Key {
+                    key,
+                    physical_key: None,
+                    pressed: true,
+                    repeat: false,
+                    modifiers,
+                }],
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| app.show(ctx));
+        };
+
+        app.user_answer = "at gaa".to_string();
+        press(&mut app, egui::Key::Escape, egui::Modifiers::NONE);
+        assert!(app.user_answer.is_empty());
+
+        press(&mut app, egui::Key::Comma, egui::Modifiers::COMMAND);
+        assert!(app.settings_window_open);
+        press(&mut app, egui::Key::Comma, egui::Modifiers::COMMAND);
+        assert!(!app.settings_window_open);
+    }
+
+    #[test]
+    fn recounts_the_deck_when_a_different_verb_is_disabled() {
+        let verbs = serde_json::json!([
+            {"infinitive": "at gå", "present": "går", "past": "gik",
+             "past_participle": "gået", "english": "to go"},
+            {"infinitive": "at se", "present": "ser", "past": "så",
+             "past_participle": "set", "english": "to see"},
+        ]);
+        let path = std::env::temp_dir().join(format!("cache-deck-{}.json", std::process::id()));
+        fs::write(&path, verbs.to_string()).unwrap();
+        let mut state = AppState::default();
+        state.disabled_verbs.insert("at gå".to_string());
+        let mut app = DanishVerbsApp::with_state(Some(path.clone()), state);
+        let _ = fs::remove_file(&path);
+        let listed = |app: &DanishVerbsApp| {
+            let order = app.verb_table_order(VerbSort::Name);
+            order
+                .iter()
+                .map(|&index| app.verbs[index].infinitive.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(listed(&app), ["at se"]);
+
+        // The same number of verbs is disabled, but not the same one
+        app.disabled_verbs.clear();
+        app.disabled_verbs.insert("at se".to_string());
+        assert_eq!(listed(&app), ["at gå"]);
+    }
+
+    #[test]
+    fn undoing_an_answer_takes_it_off_the_daily_goal() {
+        let verbs = serde_json::json!([{
+            "infinitive": "at gå",
+            "present": "går",
+            "past": "gik",
+            "past_participle": "gået",
+            "english": "to go",
+        }]);
+        let path = std::env::temp_dir().join(format!("undo-deck-{}.json", std::process::id()));
+        fs::write(&path, verbs.to_string()).unwrap();
+        let mut app = DanishVerbsApp::with_state(Some(path.clone()), AppState::default());
+        let _ = fs::remove_file(&path);
+        app.practice_mode = PracticeMode::Translation;
+        app.current_verb_index = 0;
+
+        app.user_answer = "to go".to_string();
+        app.check_answer();
+        assert_eq!(app.streak.answered_today(streak::today()), 1);
+        app.undo_last_answer();
+        assert_eq!(app.streak.answered_today(streak::today()), 0);
+        assert_eq!(app.streak.days, 0);
+    }
+
+    #[test]
+    fn names_the_deck_or_the_temporary_session_in_the_title() {
+        let verbs = serde_json::json!([{
+            "infinitive": "at gå",
+            "present": "går",
+            "past": "gik",
+            "past_participle": "gået",
+            "english": "to go",
+        }]);
+        let path = std::env::temp_dir().join(format!("title-deck-{}.json", std::process::id()));
+        fs::write(&path, verbs.to_string()).unwrap();
+        let mut app = DanishVerbsApp::with_state(Some(path.clone()), AppState::default());
+        let _ = fs::remove_file(&path);
+        let stem = path.file_stem().unwrap().to_string_lossy().to_string();
+        assert_eq!(
+            app.window_title(),
+            format!("{} — {} (1 verbs)", APP_TITLE, stem)
+        );
+
+        let clipboard =
+            import::parse_rows("at se, ser, så, set, to see\nat tage, tager, tog, taget, to take");
+        app.start_temporary_session(clipboard.verbs, "the clipboard".to_string());
+        assert_eq!(
+            app.window_title(),
+            format!("{} — the clipboard (2 temporary verbs)", APP_TITLE)
+        );
+        app.end_clipboard_session();
+        assert!(app.window_title().contains(&stem));
+    }
+
+    #[test]
+    fn a_deck_that_fails_to_load_is_never_saved_over() {
+        let path = std::env::temp_dir().join(format!("broken-deck-{}.json", std::process::id()));
+        fs::write(&path, "[{\"infinitive\": ").unwrap();
+        let mut app = DanishVerbsApp::with_state(Some(path.clone()), AppState::default());
+        assert!(app.load_error.is_some());
+        assert!(app.saved_deck.is_some());
+
+        app.end_clipboard_session();
+        assert!(app.saved_deck.is_some());
+        assert!(app.write_deck().is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[{\"infinitive\": ");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn the_built_in_deck_loads() {
+        let deck = built_in_deck();
+        assert!(!deck.verbs.is_empty());
+        assert_eq!(deck.verbs[1].position, 1);
+        for verb in &deck.verbs {
+            assert!(
+                grading::variants(verb.auxiliary())
+                    .iter()
+                    .all(|auxiliary| ["har", "er"].contains(auxiliary)),
+                "{}",
+                verb.infinitive
+            );
+        }
+        let go = deck.verbs.iter().find(|verb| verb.infinitive == "at gå");
+        assert_eq!(go.map(Verb::auxiliary), Some("er"));
+        assert_eq!(deck.verbs[1].auxiliary(), "har");
+    }
+
+    #[test]
+    fn finds_forms_spelled_the_same() {
+        let verb: Verb = serde_json::from_value(serde_json::json!({
+            "infinitive": "at sætte",
+            "present": "sætter",
+            "past": "satte",
+            "past_participle": "Sat/sætter",
+            "english": "to put",
+        }))
+        .unwrap();
+        assert_eq!(
+            verb.forms_spelled_like(ConjugationForm::Present),
+            [ConjugationForm::PastParticiple]
+        );
+        assert!(verb.forms_spelled_like(ConjugationForm::Past).is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+use crate::editor::infinitive_key;
+use crate::{DanishVerbsApp, Verb, load_deck};
+use eframe::egui;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// What to do when more than one deck has a verb with the same infinitive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Conflict {
+    /// Keep the verb from the deck listed first
+    #[default]
+    KeepFirst,
+    /// Keep the verb from the deck listed last
+    KeepLast,
+    /// Keep the first verb, filling in its empty fields from the later ones
+    MergeFields,
+}
+
+impl Conflict {
+    pub const ALL: [Conflict; 3] = [
+        Conflict::KeepFirst,
+        Conflict::KeepLast,
+        Conflict::MergeFields,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Conflict::KeepFirst => "Keep the first",
+            Conflict::KeepLast => "Keep the last",
+            Conflict::MergeFields => "Merge their fields",
+        }
+    }
+}
+
+/// The verbs of several decks combined into one.
+#[derive(Debug, Default)]
+pub struct Merged {
+    pub verbs: Vec<Verb>,
+    /// Verbs dropped because an earlier deck already had their infinitive
+    pub duplicates: usize,
+}
+
+/// Concatenates the decks in order, keeping one verb per infinitive.
+///
+/// Infinitives are compared like in the verb editor, ignoring case and a leading "at". A
+/// verb keeps the place of its first appearance, and the positions are numbered afresh.
+pub fn merge_verbs(decks: impl IntoIterator<Item = Vec<Verb>>, conflict: Conflict) -> Merged {
+    let mut merged = Merged::default();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for verb in decks.into_iter().flatten() {
+        let key = infinitive_key(&verb.infinitive);
+        let Some(&index) = seen.get(&key) else {
+            seen.insert(key, merged.verbs.len());
+            merged.verbs.push(verb);
+            continue;
+        };
+        merged.duplicates += 1;
+        let kept = &mut merged.verbs[index];
+        match conflict {
+            Conflict::KeepFirst => {}
+            Conflict::KeepLast => *kept = verb,
+            Conflict::MergeFields => fill_in(kept, verb),
+        }
+    }
+
+    for (position, verb) in merged.verbs.iter_mut().enumerate() {
+        verb.position = position;
+    }
+    merged
+}
+
+/// Fills the fields `verb` leaves empty from `other`.
+fn fill_in(verb: &mut Verb, other: Verb) {
+    for (field, value) in [
+        (&mut verb.present, other.present),
+        (&mut verb.past, other.past),
+        (&mut verb.past_participle, other.past_participle),
+        (&mut verb.imperative, other.imperative),
+        (&mut verb.present_participle, other.present_participle),
+        (&mut verb.auxiliary, other.auxiliary),
+        (&mut verb.english, other.english),
+        (&mut verb.ipa, other.ipa),
+        (&mut verb.present_phrase, other.present_phrase),
+        (&mut verb.note, other.note),
+    ] {
+        if field.trim().is_empty() {
+            *field = value;
+        }
+    }
+    if verb.frequency == 0.0 {
+        verb.frequency = other.frequency;
+    }
+    for confusable in other.confusables {
+        if !verb.confusables.contains(&confusable) {
+            verb.confusables.push(confusable);
+        }
+    }
+    let patterns = &mut verb.answer_pattern;
+    let other_patterns = other.answer_pattern;
+    for (pattern, other_pattern) in [
+        (&mut patterns.english, other_patterns.english),
+        (&mut patterns.infinitive, other_patterns.infinitive),
+        (&mut patterns.present, other_patterns.present),
+        (&mut patterns.past, other_patterns.past),
+        (
+            &mut patterns.past_participle,
+            other_patterns.past_participle,
+        ),
+        (&mut patterns.imperative, other_patterns.imperative),
+        (
+            &mut patterns.present_participle,
+            other_patterns.present_participle,
+        ),
+    ] {
+        if pattern.is_none() {
+            *pattern = other_pattern;
+        }
+    }
+}
+
+impl DanishVerbsApp {
+    /// Paths of decks to combine, typed in or dropped on the window, and the button to
+    /// practice them together.
+    pub(crate) fn show_deck_merge(&mut self, ui: &mut egui::Ui) {
+        // Files dropped on the window are added to the list
+        let dropped: Vec<PathBuf> = ui.ctx().input(|input| {
+            input
+                .raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        for path in dropped {
+            if !self.merge_paths.trim().is_empty() && !self.merge_paths.ends_with('\n') {
+                self.merge_paths.push('\n');
+            }
+            self.merge_paths += &path.display().to_string();
+        }
+
+        let mut merge = false;
+        egui::CollapsingHeader::new("Merge decks")
+            .id_salt("deck_merge")
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "One deck file per line, or drop the files on the window. \
+                         Their verbs are practiced together, without changing the files.",
+                    )
+                    .small(),
+                );
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.merge_paths)
+                        .hint_text("lesson1.json\nlesson2.json")
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Verbs in more than one deck:");
+                    egui::ComboBox::from_id_salt("merge_conflict")
+                        .selected_text(self.merge_conflict.label())
+                        .show_ui(ui, |ui| {
+                            for conflict in Conflict::ALL {
+                                ui.selectable_value(
+                                    &mut self.merge_conflict,
+                                    conflict,
+                                    conflict.label(),
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    let any = !self.merge_paths.trim().is_empty();
+                    if ui.add_enabled(any, egui::Button::new("Merge")).clicked() {
+                        merge = true;
+                    }
+                    if let Some(merge_message) = &self.merge_message {
+                        ui.label(egui::RichText::new(merge_message).small());
+                    }
+                });
+            });
+
+        if merge {
+            self.merge_decks();
+        }
+    }
+
+    /// Reads every deck in the list and starts a temporary session on their verbs combined.
+    fn merge_decks(&mut self) {
+        let paths: Vec<PathBuf> = self
+            .merge_paths
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect();
+
+        let mut decks = Vec::new();
+        let mut other_languages = Vec::new();
+        for path in &paths {
+            match load_deck(path) {
+                Ok(deck) => {
+                    if deck.info.language != self.deck_info.language {
+                        other_languages.push(format!(
+                            "{} ({})",
+                            path.display(),
+                            deck.info.language
+                        ));
+                    }
+                    decks.push(deck.verbs);
+                }
+                Err(e) => {
+                    self.merge_message = Some(e);
+                    return;
+                }
+            }
+        }
+
+        let merged = merge_verbs(decks, self.merge_conflict);
+        let mut message = format!(
+            "Merged {} verbs from {} deck{}, resolved {} duplicate{}",
+            merged.verbs.len(),
+            paths.len(),
+            if paths.len() == 1 { "" } else { "s" },
+            merged.duplicates,
+            if merged.duplicates == 1 { "" } else { "s" }
+        );
+        if !other_languages.is_empty() {
+            message += &format!(
+                ". Not in {}: {}",
+                self.deck_info.language,
+                other_languages.join(", ")
+            );
+        }
+        self.merge_message = Some(message);
+        if merged.verbs.is_empty() {
+            return;
+        }
+
+        let source = format!("{} merged decks", paths.len());
+        self.start_temporary_session(merged.verbs, source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::AnswerPatterns;
+
+    fn verb(infinitive: &str, past: &str, english: &str) -> Verb {
+        Verb {
+            infinitive: infinitive.to_string(),
+            present: "ligger".to_string(),
+            past: past.to_string(),
+            past_participle: "ligget".to_string(),
+            imperative: String::new(),
+            present_participle: String::new(),
+            auxiliary: String::new(),
+            english: english.to_string(),
+            ipa: String::new(),
+            present_phrase: String::new(),
+            note: String::new(),
+            frequency: 0.0,
+            confusables: Vec::new(),
+            answer_pattern: AnswerPatterns::default(),
+            group: None,
+            position: 0,
+        }
+    }
+
+    fn decks() -> Vec<Vec<Verb>> {
+        vec![
+            vec![
+                verb("at ligge", "", "to lie"),
+                verb("at være", "var", "to be"),
+            ],
+            vec![
+                verb("Ligge", "lå", "to lie down"),
+                verb("at gå", "gik", "to go"),
+            ],
+        ]
+    }
+
+    #[test]
+    fn keeps_one_verb_per_infinitive() {
+        let first = merge_verbs(decks(), Conflict::KeepFirst);
+        assert_eq!(first.duplicates, 1);
+        let infinitives: Vec<&str> = first.verbs.iter().map(|v| v.infinitive.as_str()).collect();
+        assert_eq!(infinitives, ["at ligge", "at være", "at gå"]);
+        assert_eq!(first.verbs[0].english, "to lie");
+        assert_eq!(first.verbs[2].position, 2);
+
+        let last = merge_verbs(decks(), Conflict::KeepLast);
+        assert_eq!(last.verbs[0].english, "to lie down");
+        assert_eq!(last.verbs[0].position, 0);
+    }
+
+    #[test]
+    fn merging_fills_in_empty_fields() {
+        let merged = merge_verbs(decks(), Conflict::MergeFields);
+        assert_eq!(merged.duplicates, 1);
+        assert_eq!(merged.verbs[0].english, "to lie");
+        assert_eq!(merged.verbs[0].past, "lå");
+    }
+}
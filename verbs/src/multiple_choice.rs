@@ -0,0 +1,102 @@
+use crate::grading;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How similar the wrong options are to the correct answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum Difficulty {
+    /// Distractors that look as different from the answer as possible
+    Easy,
+    /// Distractors picked at random
+    #[default]
+    Normal,
+    /// Distractors that are spelled most like the answer
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+/// Picks up to `count` wrong options for `correct` out of `candidates`.
+///
+/// Candidates are compared after normalizing, so a verb sharing its meaning with the
+/// answer is never offered as a second correct option, and no option appears twice.
+pub fn pick_distractors<R: Rng>(
+    correct: &str,
+    candidates: &[&str],
+    count: usize,
+    difficulty: Difficulty,
+    rng: &mut R,
+) -> Vec<String> {
+    let correct_key = grading::normalize(correct);
+    let mut pool: Vec<&str> = candidates
+        .iter()
+        .copied()
+        .filter(|candidate| grading::normalize(candidate) != correct_key)
+        .collect();
+    pool.sort_by_cached_key(|candidate| grading::normalize(candidate));
+    pool.dedup_by_key(|candidate| grading::normalize(candidate));
+
+    if difficulty != Difficulty::Normal {
+        // Rank by spelling distance and sample from the best few, so the options still vary
+        pool.sort_by_cached_key(|candidate| grading::levenshtein(candidate, correct));
+        if difficulty == Difficulty::Easy {
+            pool.reverse();
+        }
+        pool.truncate(count * 2);
+    }
+
+    pool.choose_multiple(rng, count)
+        .map(|candidate| candidate.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn never_offers_a_second_correct_option() {
+        // "at være" and "at eksistere" are both given as "to be" in this deck
+        let candidates = [
+            "to be", "To be ", "to have", "to go", "to eat", "to see", "to have",
+        ];
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for difficulty in Difficulty::ALL {
+                let distractors = pick_distractors("to be", &candidates, 3, difficulty, &mut rng);
+                assert_eq!(distractors.len(), 3);
+
+                let mut keys: Vec<String> =
+                    distractors.iter().map(|d| grading::normalize(d)).collect();
+                assert!(!keys.contains(&"to be".to_string()));
+                keys.sort();
+                keys.dedup();
+                assert_eq!(keys.len(), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn returns_fewer_options_when_the_pool_runs_out() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let distractors = pick_distractors(
+            "to be",
+            &["to be", "to go", "TO GO"],
+            3,
+            Difficulty::Normal,
+            &mut rng,
+        );
+        assert_eq!(distractors, vec!["to go".to_string()]);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::DanishVerbsApp;
+use eframe::egui;
+
+/// Title and text of each step of the walkthrough.
+const STEPS: [(&str, &str); 5] = [
+    (
+        "Welcome",
+        "This app drills Danish verbs: what they mean and how they're conjugated. \
+         Here's a quick tour of how a question works.",
+    ),
+    (
+        "Answering",
+        "Each question asks for a translation or for one form of a verb. Type your answer \
+         and press \"Check\" or Enter. A wrong answer can be tried again a couple of times \
+         before the correct one is shown.",
+    ),
+    (
+        "Hints and the answer",
+        "Stuck? \"Hint\" gives away the answer a letter at a time. Once a question is over, \
+         the correct answer is shown and \"Verb details\" below it lists every form of the verb.",
+    ),
+    (
+        "Moving on",
+        "\"Next verb\" goes on to the next question. \"Browse\" at the top is for studying \
+         the verbs without being graded, and the settings at the bottom choose which kinds \
+         of questions are asked.",
+    ),
+    (
+        "Keeping track",
+        "Your results are kept between runs, and \"End session\" compares the session with \
+         your best. The \"?\" next to each question has the keyboard shortcuts and this tour.",
+    ),
+];
+
+/// The walkthrough shown on first launch, and whenever it's asked for again.
+#[derive(Debug, Default)]
+pub struct Onboarding {
+    step: usize,
+    dont_show_again: bool,
+}
+
+impl DanishVerbsApp {
+    pub(crate) fn start_onboarding(&mut self) {
+        self.onboarding = Some(Onboarding::default());
+    }
+
+    /// Draws the current step of the walkthrough over the app, if it's running.
+    pub(crate) fn show_onboarding(&mut self, ctx: &egui::Context) {
+        let Some(onboarding) = &mut self.onboarding else {
+            return;
+        };
+        let (title, text) = STEPS[onboarding.step];
+        let last = onboarding.step + 1 == STEPS.len();
+        let mut close = false;
+        let mut finished = false;
+
+        let modal = egui::Modal::new(egui::Id::new("onboarding")).show(ctx, |ui| {
+            ui.set_max_width(420.0);
+            ui.label(
+                egui::RichText::new(title)
+                    .heading()
+                    .color(self.theme.accent),
+            );
+            ui.label(
+                egui::RichText::new(format!("Step {} of {}", onboarding.step + 1, STEPS.len()))
+                    .small(),
+            );
+            ui.add_space(8.0);
+            ui.label(text);
+            ui.add_space(12.0);
+            ui.checkbox(&mut onboarding.dont_show_again, "Don't show this again");
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("Skip").clicked() {
+                    close = true;
+                }
+                if onboarding.step > 0 && ui.button("Back").clicked() {
+                    onboarding.step -= 1;
+                }
+                if last {
+                    if ui.button("Done").clicked() {
+                        close = true;
+                        finished = true;
+                    }
+                } else if ui.button("Next").clicked() {
+                    onboarding.step += 1;
+                }
+            });
+        });
+        // Escape or a click outside counts as skipping
+        close |= modal.should_close();
+
+        if close {
+            if finished || onboarding.dont_show_again {
+                self.seen_onboarding = true;
+                self.save_state();
+            }
+            self.onboarding = None;
+        }
+    }
+}
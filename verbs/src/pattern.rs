@@ -0,0 +1,136 @@
+use crate::ConjugationForm;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A pattern an answer can match besides the plain field, e.g. "lig(ger|gede)?" or
+/// "to (lie|be lying)( down)?".
+///
+/// Patterns are regular expressions that must match the whole normalized answer. A
+/// pattern is compiled once when the deck is read, and an invalid one makes the deck fail
+/// to load.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AnswerPattern {
+    source: String,
+    regex: Regex,
+}
+
+impl PartialEq for AnswerPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+/// Patterns for the fields of one verb. Fields without one are only compared literally.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AnswerPatterns {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub english: Option<AnswerPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub infinitive: Option<AnswerPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub present: Option<AnswerPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub past: Option<AnswerPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub past_participle: Option<AnswerPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imperative: Option<AnswerPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub present_participle: Option<AnswerPattern>,
+}
+
+impl AnswerPatterns {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The pattern for a conjugation form, if the deck gives one.
+    pub fn form(&self, form: ConjugationForm) -> Option<&AnswerPattern> {
+        match form {
+            ConjugationForm::Present => self.present.as_ref(),
+            ConjugationForm::Past => self.past.as_ref(),
+            ConjugationForm::PastParticiple => self.past_participle.as_ref(),
+            ConjugationForm::Imperative => self.imperative.as_ref(),
+            ConjugationForm::PresentParticiple => self.present_participle.as_ref(),
+        }
+    }
+}
+
+/// Why a pattern couldn't be compiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    pub pattern: String,
+    pub reason: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid answer pattern \"{}\": {}",
+            self.pattern, self.reason
+        )
+    }
+}
+
+impl AnswerPattern {
+    pub fn new(source: &str) -> Result<Self, PatternError> {
+        let regex = Regex::new(&format!("^(?:{})$", source)).map_err(|e| PatternError {
+            pattern: source.to_string(),
+            // A syntax error ends with a line naming the problem, after a diagram of where it
+            // is in the anchored pattern
+            reason: match e {
+                regex::Error::Syntax(message) => message
+                    .lines()
+                    .last()
+                    .unwrap_or_default()
+                    .trim_start_matches("error: ")
+                    .to_string(),
+                e => e.to_string(),
+            },
+        })?;
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    /// True if the pattern matches all of `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+impl TryFrom<String> for AnswerPattern {
+    type Error = PatternError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::new(&source)
+    }
+}
+
+impl From<AnswerPattern> for String {
+    fn from(pattern: AnswerPattern) -> Self {
+        pattern.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let patterns: AnswerPatterns = serde_json::from_str(r#"{"past": "lå|l[aæ]g"}"#).unwrap();
+        assert!(patterns.past.as_ref().unwrap().is_match("læg"));
+        assert_eq!(
+            serde_json::to_string(&patterns).unwrap(),
+            r#"{"past":"lå|l[aæ]g"}"#
+        );
+        let error = serde_json::from_str::<AnswerPatterns>(r#"{"past": "(lå"}"#).unwrap_err();
+        assert!(error.to_string().contains("invalid answer pattern \"(lå\""));
+    }
+}
@@ -0,0 +1,106 @@
+use rand::Rng;
+use rand::seq::{IteratorRandom, SliceRandom};
+use std::collections::HashSet;
+
+/// A random set of verbs fixed for the whole session, each asked until it's answered
+/// right once.
+#[derive(Debug)]
+pub struct PoolSession {
+    /// Infinitives in the pool, in the shuffled order they're asked
+    pub verbs: Vec<String>,
+    /// Verbs answered right at least once
+    pub cleared: HashSet<String>,
+    /// Made of the verbs answered wrong in a session, rather than drawn at random
+    pub mistakes: bool,
+    next: usize,
+}
+
+impl PoolSession {
+    /// Draws `count` of the infinitives at random, in random order.
+    pub fn draw<'a>(
+        infinitives: impl Iterator<Item = &'a str>,
+        count: usize,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let mut verbs: Vec<String> = infinitives
+            .choose_multiple(rng, count)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        // `choose_multiple` doesn't promise a random order
+        verbs.shuffle(rng);
+        Self {
+            verbs,
+            cleared: HashSet::new(),
+            mistakes: false,
+            next: 0,
+        }
+    }
+
+    /// A pool of the verbs answered wrong, asked in the order they were missed.
+    pub fn of_mistakes(verbs: Vec<String>) -> Self {
+        Self {
+            verbs,
+            cleared: HashSet::new(),
+            mistakes: true,
+            next: 0,
+        }
+    }
+
+    /// The next verb that isn't cleared yet, going round the pool in order.
+    pub fn next_verb(&mut self) -> Option<&str> {
+        for _ in 0..self.verbs.len() {
+            let index = self.next % self.verbs.len();
+            self.next = index + 1;
+            if !self.cleared.contains(&self.verbs[index]) {
+                return Some(&self.verbs[index]);
+            }
+        }
+        None
+    }
+
+    pub fn record(&mut self, infinitive: &str, correct: bool) {
+        if correct && self.verbs.iter().any(|verb| verb == infinitive) {
+            self.cleared.insert(infinitive.to_string());
+        }
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        self.cleared.len() >= self.verbs.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn asks_each_verb_until_it_is_answered_right() {
+        let deck = ["at være", "at have", "at gå", "at se", "at tage"];
+        let mut pool = PoolSession::draw(deck.into_iter(), 3, &mut StdRng::seed_from_u64(7));
+        assert_eq!(pool.verbs.len(), 3);
+        assert!(pool.verbs.iter().all(|verb| deck.contains(&verb.as_str())));
+
+        let first = pool.next_verb().unwrap().to_string();
+        pool.record(&first, true);
+        let second = pool.next_verb().unwrap().to_string();
+        pool.record(&second, false);
+        let third = pool.next_verb().unwrap().to_string();
+        pool.record(&third, true);
+        // The verb answered wrong comes round again, the cleared ones don't
+        assert_eq!(pool.next_verb(), Some(second.as_str()));
+        assert!(!pool.is_cleared());
+        pool.record(&second, true);
+        assert!(pool.is_cleared());
+        assert_eq!(pool.next_verb(), None);
+    }
+
+    #[test]
+    fn verbs_outside_the_pool_are_not_recorded() {
+        let mut pool = PoolSession::draw(["at være"].into_iter(), 5, &mut StdRng::seed_from_u64(1));
+        pool.record("at have", true);
+        assert!(!pool.is_cleared());
+    }
+}
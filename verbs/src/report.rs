@@ -0,0 +1,176 @@
+use crate::deck::DeckInfo;
+use crate::stats::{Stats, Tally};
+use crate::streak::Streak;
+use crate::{ConjugationForm, PracticeMode};
+use std::fmt::Write;
+
+/// Number of verbs listed in the hardest verbs table
+const HARDEST_VERBS: usize = 10;
+
+/// Summarizes the session and all-time results as Markdown tables.
+pub fn markdown_report(stats: &Stats, info: &DeckInfo, streak: &Streak) -> String {
+    let mut report = format!("# {} Verbs Progress Report\n\n", info.language);
+
+    report.push_str("## This session\n\n");
+    if stats.events.is_empty() {
+        report.push_str("No answers checked this session.\n\n");
+    } else {
+        report.push_str("| Area | Correct | Accuracy |\n| --- | --- | --- |\n");
+        report.push_str(&tally_row("Overall", stats.total()));
+        for mode in PracticeMode::ALL {
+            report.push_str(&tally_row(mode.label(), stats.by_mode(mode)));
+        }
+        for form in ConjugationForm::ALL {
+            report.push_str(&tally_row(info.form_name(form), stats.by_form(form)));
+        }
+        report.push('\n');
+    }
+
+    report.push_str("## All time\n\n");
+    let mut all_time = Tally::default();
+    for verb_stat in stats.verbs.values() {
+        all_time.attempts += verb_stat.attempts as usize;
+        all_time.correct += verb_stat.correct as usize;
+    }
+    report.push_str("| Area | Correct | Accuracy |\n| --- | --- | --- |\n");
+    report.push_str(&tally_row("Overall", all_time));
+    report.push('\n');
+
+    let mut hardest: Vec<(&String, Tally, u32)> = stats
+        .verbs
+        .iter()
+        .filter(|(_, verb_stat)| verb_stat.attempts > 0)
+        .map(|(infinitive, verb_stat)| {
+            let tally = Tally {
+                attempts: verb_stat.attempts as usize,
+                correct: verb_stat.correct as usize,
+            };
+            (infinitive, tally, verb_stat.lapses)
+        })
+        .collect();
+    hardest.sort_by(|a, b| {
+        a.1.accuracy()
+            .total_cmp(&b.1.accuracy())
+            .then(b.2.cmp(&a.2))
+            .then(a.0.cmp(b.0))
+    });
+
+    if !hardest.is_empty() {
+        report.push_str("## Hardest verbs\n\n");
+        report.push_str("| Verb | Correct | Lapses | Accuracy |\n| --- | --- | --- | --- |\n");
+        for (infinitive, tally, lapses) in hardest.into_iter().take(HARDEST_VERBS) {
+            let _ = writeln!(
+                report,
+                "| {} | {}/{} | {} | {:.0}% |",
+                escape(infinitive),
+                tally.correct,
+                tally.attempts,
+                lapses,
+                tally.accuracy()
+            );
+        }
+        report.push('\n');
+    }
+
+    report.push_str("## Streak\n\n");
+    let _ = writeln!(
+        report,
+        "Current streak: {} day{}",
+        streak.days,
+        if streak.days == 1 { "" } else { "s" }
+    );
+    report
+}
+
+fn tally_row(name: &str, tally: Tally) -> String {
+    let accuracy = if tally.attempts == 0 {
+        "–".to_string()
+    } else {
+        format!("{:.0}%", tally.accuracy())
+    };
+    format!(
+        "| {} | {}/{} | {} |\n",
+        escape(name),
+        tally.correct,
+        tally.attempts,
+        accuracy
+    )
+}
+
+/// Keeps a "|" in a verb from splitting its table cell.
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{AttemptEvent, VerbStat};
+    use std::collections::HashMap;
+
+    fn attempt(infinitive: &str, mode: PracticeMode, correct: bool) -> AttemptEvent {
+        AttemptEvent {
+            infinitive: infinitive.to_string(),
+            mode,
+            form: None,
+            flipped: false,
+            correct,
+            response_time: None,
+            hinted: false,
+            typed_chars: 0,
+            answer: String::new(),
+            expected: String::new(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn writes_session_breakdown_rows() {
+        let mut stats = Stats::new(HashMap::new());
+        stats.record(attempt("at være", PracticeMode::Translation, true));
+        stats.record(attempt("at have", PracticeMode::Translation, false));
+        stats.record(attempt("at gå", PracticeMode::MultipleChoice, true));
+
+        let report = markdown_report(&stats, &DeckInfo::default(), &Streak::default());
+        assert!(report.contains("| Overall | 2/3 | 67% |\n"));
+        assert!(report.contains("| Translation | 1/2 | 50% |\n"));
+        assert!(report.contains("| Multiple choice | 1/1 | 100% |\n"));
+        assert!(report.contains("| past tense | 0/0 | – |\n"));
+        assert!(report.contains("Current streak: 0 days"));
+    }
+
+    #[test]
+    fn lists_the_hardest_verbs_first() {
+        let verb_stat = |attempts, correct| VerbStat {
+            attempts,
+            correct,
+            lapses: attempts - correct,
+            hinted: 0,
+            last_seen: None,
+            recent: Vec::new(),
+        };
+        let stats = Stats::new(HashMap::from([
+            ("at være".to_string(), verb_stat(4, 4)),
+            ("at have".to_string(), verb_stat(4, 1)),
+            ("at gå".to_string(), verb_stat(2, 1)),
+        ]));
+
+        let report = markdown_report(&stats, &DeckInfo::default(), &Streak::default());
+        assert!(report.contains("No answers checked this session."));
+        assert!(report.contains("| Overall | 6/10 | 60% |\n"));
+
+        let hardest = report.split("## Hardest verbs").nth(1).unwrap();
+        let rows: Vec<&str> = hardest
+            .lines()
+            .filter(|line| line.starts_with("| at"))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                "| at have | 1/4 | 3 | 25% |",
+                "| at gå | 1/2 | 1 | 50% |",
+                "| at være | 4/4 | 0 | 100% |",
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+const DAY_SECS: u64 = 86_400;
+/// The ease a verb starts with, and the least it can drop to
+const START_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+
+/// How well a question was answered, on the 0 to 5 scale of SM-2. Anything below 3 counts
+/// as forgotten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recall {
+    /// Answered right at the first try without a hint
+    Perfect = 5,
+    /// Answered right, but only after a wrong try or a hint
+    Hesitant = 3,
+    Forgotten = 1,
+}
+
+impl Recall {
+    pub fn new(correct: bool, unaided: bool) -> Self {
+        match (correct, unaided) {
+            (true, true) => Recall::Perfect,
+            (true, false) => Recall::Hesitant,
+            (false, _) => Recall::Forgotten,
+        }
+    }
+}
+
+/// When a verb is next due for review, worked out with the SM-2 algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Review {
+    /// How quickly the interval grows with each successful review
+    pub ease: f64,
+    pub interval_days: u32,
+    /// Successful reviews in a row
+    pub repetitions: u32,
+    /// When the verb is due, in seconds since the Unix epoch
+    pub due: u64,
+}
+
+impl Default for Review {
+    fn default() -> Self {
+        Self {
+            ease: START_EASE,
+            interval_days: 0,
+            repetitions: 0,
+            due: 0,
+        }
+    }
+}
+
+impl Review {
+    /// Updates the schedule after an answer given at `now`.
+    pub fn record(&mut self, recall: Recall, now: u64) {
+        if recall == Recall::Forgotten {
+            // Start over, asking the verb again the next day
+            self.repetitions = 0;
+            self.interval_days = 1;
+        } else {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval_days) * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        }
+        let q = 5.0 - recall as u8 as f64;
+        self.ease = (self.ease + 0.1 - q * (0.08 + q * 0.02)).max(MIN_EASE);
+        self.due = now + u64::from(self.interval_days) * DAY_SECS;
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.due <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_grow_with_each_remembered_review() {
+        let mut review = Review::default();
+        let mut intervals = Vec::new();
+        for _ in 0..4 {
+            review.record(Recall::Perfect, 0);
+            intervals.push(review.interval_days);
+        }
+        // The ease grows by 0.1 with each perfect answer: 6 × 2.7 and 16 × 2.8
+        assert_eq!(intervals, [1, 6, 16, 45]);
+        assert!((review.ease - 2.9).abs() < 1e-9);
+        assert_eq!(review.due, 45 * DAY_SECS);
+        assert!(!review.is_due(44 * DAY_SECS));
+        assert!(review.is_due(45 * DAY_SECS));
+    }
+
+    #[test]
+    fn forgetting_starts_the_intervals_over() {
+        let mut review = Review::default();
+        review.record(Recall::Perfect, 0);
+        review.record(Recall::Perfect, 0);
+        review.record(Recall::Forgotten, 10 * DAY_SECS);
+        assert_eq!(review.repetitions, 0);
+        assert_eq!(review.interval_days, 1);
+        assert_eq!(review.due, 11 * DAY_SECS);
+        // 2.5 + 0.1 + 0.1 - 0.54
+        assert!((review.ease - 2.16).abs() < 1e-9);
+
+        // The ease never drops below its minimum
+        for _ in 0..10 {
+            review.record(Recall::Forgotten, 0);
+        }
+        assert_eq!(review.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn hesitant_answers_lower_the_ease() {
+        let mut review = Review::default();
+        review.record(Recall::Hesitant, 0);
+        assert_eq!(review.interval_days, 1);
+        assert!((review.ease - 2.36).abs() < 1e-9);
+        assert_eq!(Recall::new(true, false), Recall::Hesitant);
+        assert_eq!(Recall::new(false, true), Recall::Forgotten);
+    }
+}
@@ -0,0 +1,102 @@
+//! A tiny HTTP server exposing the session statistics as JSON on `GET /stats`.
+//!
+//! It only binds to 127.0.0.1, so the stats are readable by anything running on
+//! this machine but not from the network. There is no authentication.
+
+use crate::stats::Stats;
+use crate::{ConjugationForm, PracticeMode};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How long a client may take to send its request before it's dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The latest stats as a JSON document, shared between the UI and the server thread.
+pub type SharedSnapshot = Arc<Mutex<String>>;
+
+/// Starts serving on `127.0.0.1:port` in a background thread.
+pub fn start(port: u16, stats: &Stats) -> std::io::Result<SharedSnapshot> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    let snapshot = Arc::new(Mutex::new(snapshot_json(stats)));
+
+    let server_snapshot = Arc::clone(&snapshot);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // Each client gets a thread of its own, so a slow one can't hold up the rest
+            let snapshot = Arc::clone(&server_snapshot);
+            thread::spawn(move || {
+                if let Err(e) = handle(stream, &snapshot) {
+                    eprintln!("Error serving stats: {}", e);
+                }
+            });
+        }
+    });
+
+    Ok(snapshot)
+}
+
+/// Replaces the served stats with the current ones.
+pub fn publish(snapshot: &SharedSnapshot, stats: &Stats) {
+    let json = snapshot_json(stats);
+    if let Ok(mut current) = snapshot.lock() {
+        *current = json;
+    }
+}
+
+fn snapshot_json(stats: &Stats) -> String {
+    let tally_json = |tally: crate::stats::Tally| {
+        serde_json::json!({
+            "attempts": tally.attempts,
+            "correct": tally.correct,
+            "accuracy": tally.accuracy(),
+        })
+    };
+
+    let modes: serde_json::Map<String, serde_json::Value> = PracticeMode::ALL
+        .iter()
+        .map(|mode| (mode.label().to_string(), tally_json(stats.by_mode(*mode))))
+        .collect();
+    let forms: serde_json::Map<String, serde_json::Value> = ConjugationForm::ALL
+        .iter()
+        .map(|form| (form.label().to_string(), tally_json(stats.by_form(*form))))
+        .collect();
+
+    serde_json::json!({
+        "session": tally_json(stats.total()),
+        "modes": modes,
+        "forms": forms,
+        "verbs": stats.verbs,
+    })
+    .to_string()
+}
+
+fn handle(mut stream: TcpStream, snapshot: &SharedSnapshot) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Read the headers up to the blank line, so closing the socket doesn't reset it
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+        header.clear();
+    }
+
+    let (status, body) = if request_line.starts_with("GET /stats ") {
+        // Copy the snapshot so the lock isn't held while writing to the socket
+        let body = snapshot.lock().map(|json| json.clone()).unwrap_or_default();
+        ("200 OK", body)
+    } else {
+        ("404 Not Found", "{\"error\":\"not found\"}".to_string())
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
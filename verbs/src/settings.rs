@@ -0,0 +1,302 @@
+use crate::conjugator::Group;
+use crate::grading::{GradingOptions, GradingPreset};
+use crate::multiple_choice::Difficulty;
+use crate::stats::MasteryRule;
+use crate::{ConjugationForm, PracticeMode};
+use serde::{Deserialize, Serialize};
+
+/// User-adjustable options for a practice session.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    /// How many wrong guesses are allowed before the answer is revealed.
+    pub max_retries: u32,
+    /// Wrong answers at most this many letters off are called almost correct, or 0 for never.
+    pub near_miss_distance: u32,
+    /// Show the IPA transcription under the question, when the verb has one.
+    pub show_ipa_in_question: bool,
+    /// Point out, once graded, when the form asked for is spelled like another form.
+    pub note_identical_forms: bool,
+    /// Keep the verbs in the order of the deck file instead of shuffling them.
+    pub preserve_deck_order: bool,
+    /// Only practice the verbs of this conjugation group.
+    pub only_group: Option<Group>,
+    /// Only practice the verbs from `range_start` to `range_end` in deck file order.
+    pub limit_to_range: bool,
+    /// First verb of the range, counting from 1.
+    pub range_start: usize,
+    /// Last verb of the range, counting from 1.
+    pub range_end: usize,
+    /// Reshuffle and carry on when every verb has been asked, instead of stopping.
+    pub reshuffle_each_pass: bool,
+    /// Mix English → Danish questions in with the Danish → English ones.
+    pub translate_both_ways: bool,
+    /// Share of conjugation questions that give the form and ask for the infinitive, in percent.
+    pub flipped_conjugation_percent: u32,
+    /// Ask verbs that have never been answered before any others.
+    pub unseen_first: bool,
+    /// Ask verbs whose spaced repetition review is due before the others.
+    pub review_due_first: bool,
+    /// Draw verbs by their Leitner box, more often the lower the box.
+    pub leitner: bool,
+    /// Ask verbs that have never been answered as translations until they're introduced.
+    pub introduce_with_translation: bool,
+    /// Answers after which a new verb counts as introduced.
+    pub introduction_questions: u32,
+    /// Show every form of a verb the first time it comes up in a session, then ask one.
+    pub teach_mode: bool,
+    /// Pick verbs at random, weighted by how common they are and how often they're missed.
+    pub prioritize_common_verbs: bool,
+    /// Mix new and review verbs in the ratio of `new_verb_percent`.
+    pub balance_new_and_review: bool,
+    /// Share of questions about verbs never answered before, in percent.
+    pub new_verb_percent: u32,
+    /// Mix multiple choice questions in with the typed ones.
+    pub multiple_choice: bool,
+    /// Mix in questions asking for the infinitive and every form from the English.
+    pub all_forms: bool,
+    /// Mix in questions where the infinitive is only read aloud and typed as heard.
+    pub dictation: bool,
+    /// Mix in questions asking for the perfect tense with its auxiliary, e.g. "jeg er gået".
+    pub perfect: bool,
+    /// How often each kind of question that's mixed in is asked.
+    pub mode_weights: ModeWeights,
+    /// How close the wrong options in multiple choice are to the answer.
+    pub distractor_difficulty: Difficulty,
+    /// Show an on-screen keyboard and enlarge the buttons for tablets.
+    pub touch_mode: bool,
+    /// Wrong answers after which a verb is flagged as a leech.
+    pub leech_threshold: u32,
+    /// When a verb counts towards the deck's mastery percentage.
+    pub mastery: MasteryRule,
+    /// Settings saved before there were themes only have colors, so they keep them as a
+    /// custom theme.
+    #[serde(default = "ThemeName::custom")]
+    pub theme: ThemeName,
+    /// The colors of the custom theme.
+    pub colors: ThemeColors,
+    pub grading: GradingOptions,
+    /// The preset last picked, or `Custom` once an option is changed by hand.
+    pub grading_preset: GradingPreset,
+    /// Questions to answer each day, shown as a progress bar. Zero for no goal.
+    pub daily_goal: u32,
+    /// End the session `time_limit_minutes` after its first answer.
+    pub time_limit: bool,
+    pub time_limit_minutes: u32,
+    /// Number of verbs picked for a cram session.
+    pub cram_size: usize,
+    /// Number of verbs drawn for a random pool session.
+    pub pool_size: usize,
+    /// Expect present tense answers as a full phrase with a subject.
+    pub present_phrase: bool,
+    /// Subject put in front of the present tense of verbs without a `present_phrase`, and in
+    /// front of perfect tense answers.
+    pub phrase_pronoun: String,
+    /// Conjugation forms that are never asked for.
+    pub excluded_forms: Vec<ConjugationForm>,
+    /// Size of all text, in percent of the default.
+    pub text_size_percent: u32,
+    /// Leave the English meaning out of conjugation questions until they're graded.
+    pub hide_english_in_conjugation: bool,
+    /// Keep the verb details shut while a question is being answered.
+    pub lock_details: bool,
+    /// Cover the correct answer after a miss until it's deliberately uncovered.
+    pub cover_answers: bool,
+    /// Show the typing speed of the session's answers when it ends.
+    pub show_typing_speed: bool,
+    /// Hide the verb details, statistics and settings while practicing.
+    pub focus_mode: bool,
+    /// Say so when a wrong answer is right for another verb in the deck.
+    pub spot_confused_verbs: bool,
+    /// Show the first letter of the answer when a question goes unanswered for a while.
+    pub auto_hint: bool,
+    /// Seconds to wait before the automatic hint.
+    pub auto_hint_after_secs: u32,
+}
+
+/// How often each kind of question is asked, relative to the others. A kind that isn't
+/// mixed in is never asked, whatever its weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ModeWeights {
+    pub translation: u32,
+    pub reverse_translation: u32,
+    pub multiple_choice: u32,
+    pub conjugation: u32,
+    pub all_forms: u32,
+    pub dictation: u32,
+    pub perfect: u32,
+}
+
+impl ModeWeights {
+    pub fn get(&self, mode: PracticeMode) -> u32 {
+        match mode {
+            PracticeMode::Translation => self.translation,
+            PracticeMode::ReverseTranslation => self.reverse_translation,
+            PracticeMode::MultipleChoice => self.multiple_choice,
+            PracticeMode::Conjugation => self.conjugation,
+            PracticeMode::AllForms => self.all_forms,
+            PracticeMode::Dictation => self.dictation,
+            PracticeMode::Perfect => self.perfect,
+        }
+    }
+
+    pub fn get_mut(&mut self, mode: PracticeMode) -> &mut u32 {
+        match mode {
+            PracticeMode::Translation => &mut self.translation,
+            PracticeMode::ReverseTranslation => &mut self.reverse_translation,
+            PracticeMode::MultipleChoice => &mut self.multiple_choice,
+            PracticeMode::Conjugation => &mut self.conjugation,
+            PracticeMode::AllForms => &mut self.all_forms,
+            PracticeMode::Dictation => &mut self.dictation,
+            PracticeMode::Perfect => &mut self.perfect,
+        }
+    }
+}
+
+impl Default for ModeWeights {
+    fn default() -> Self {
+        Self {
+            translation: 2,
+            reverse_translation: 1,
+            multiple_choice: 1,
+            conjugation: 2,
+            all_forms: 1,
+            dictation: 1,
+            perfect: 1,
+        }
+    }
+}
+
+/// The three theme colors as RGB triples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThemeColors {
+    pub accent: [u8; 3],
+    pub background: [u8; 3],
+    pub text: [u8; 3],
+}
+
+impl ThemeColors {
+    pub const LIGHT: ThemeColors = ThemeColors {
+        accent: [66, 135, 245],      // Blue
+        background: [240, 240, 255], // Light blue-gray
+        text: [40, 40, 60],          // Dark blue-gray
+    };
+    pub const DARK: ThemeColors = ThemeColors {
+        accent: [110, 165, 255],  // Soft blue
+        background: [28, 30, 38], // Near black
+        text: [222, 224, 235],    // Light gray
+    };
+    pub const HIGH_CONTRAST: ThemeColors = ThemeColors {
+        accent: [255, 230, 0], // Yellow
+        background: [0, 0, 0],
+        text: [255, 255, 255],
+    };
+}
+
+impl Default for ThemeColors {
+    fn default() -> Self {
+        Self::LIGHT
+    }
+}
+
+/// The color themes to pick from, or the user's own colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum ThemeName {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+    Custom,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 4] = [
+        ThemeName::Light,
+        ThemeName::Dark,
+        ThemeName::HighContrast,
+        ThemeName::Custom,
+    ];
+
+    fn custom() -> Self {
+        ThemeName::Custom
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeName::Light => "Light",
+            ThemeName::Dark => "Dark",
+            ThemeName::HighContrast => "High contrast",
+            ThemeName::Custom => "Custom",
+        }
+    }
+
+    /// The theme's colors, which are `custom` for the custom theme.
+    pub fn colors(self, custom: ThemeColors) -> ThemeColors {
+        match self {
+            ThemeName::Light => ThemeColors::LIGHT,
+            ThemeName::Dark => ThemeColors::DARK,
+            ThemeName::HighContrast => ThemeColors::HIGH_CONTRAST,
+            ThemeName::Custom => custom,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            near_miss_distance: 1,
+            show_ipa_in_question: false,
+            note_identical_forms: true,
+            preserve_deck_order: false,
+            reshuffle_each_pass: true,
+            only_group: None,
+            limit_to_range: false,
+            range_start: 1,
+            range_end: 20,
+            translate_both_ways: false,
+            flipped_conjugation_percent: 0,
+            unseen_first: false,
+            review_due_first: true,
+            leitner: false,
+            introduce_with_translation: false,
+            introduction_questions: 2,
+            teach_mode: false,
+            multiple_choice: false,
+            prioritize_common_verbs: false,
+            balance_new_and_review: false,
+            new_verb_percent: 20,
+            all_forms: false,
+            dictation: false,
+            perfect: false,
+            mode_weights: ModeWeights::default(),
+            distractor_difficulty: Difficulty::Normal,
+            touch_mode: false,
+            leech_threshold: 8,
+            mastery: MasteryRule::default(),
+            theme: ThemeName::Light,
+            colors: ThemeColors::default(),
+            grading: GradingOptions::default(),
+            grading_preset: GradingPreset::Normal,
+            cram_size: 10,
+            pool_size: 15,
+            daily_goal: 30,
+            time_limit: false,
+            time_limit_minutes: 10,
+            show_typing_speed: true,
+            focus_mode: false,
+            spot_confused_verbs: true,
+            auto_hint: false,
+            auto_hint_after_secs: 15,
+            lock_details: true,
+            cover_answers: false,
+            excluded_forms: Vec::new(),
+            text_size_percent: 100,
+            hide_english_in_conjugation: false,
+            present_phrase: false,
+            phrase_pronoun: "jeg".to_string(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Text to speech with the platform's speech command, behind the `tts` feature.
+//!
+//! Without the feature nothing can be read aloud, so dictation questions are skipped.
+
+use std::io;
+#[cfg(feature = "tts")]
+use std::process::{Command, Stdio};
+#[cfg(feature = "tts")]
+use std::thread;
+
+/// Reads text aloud with the platform's speech command, without waiting for it to finish.
+///
+/// A Danish voice is asked for when the deck is Danish, otherwise the default voice is used.
+#[cfg(feature = "tts")]
+pub fn speak(text: &str, language: &str) -> io::Result<()> {
+    let danish = language == "Danish";
+
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("say");
+        if danish {
+            command.args(["-v", "Sara"]);
+        }
+        command.arg(text);
+        command
+    } else if cfg!(target_os = "windows") {
+        // The text is passed through the environment so it can't break out of the script
+        let mut command = Command::new("powershell");
+        command
+            .args([
+                "-NoProfile",
+                "-Command",
+                "Add-Type -AssemblyName System.Speech; \
+                 $synth = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+                 $voice = $synth.GetInstalledVoices() | \
+                 Where-Object { $_.VoiceInfo.Culture.Name -eq $env:SPEAK_CULTURE } | \
+                 Select-Object -First 1; \
+                 if ($voice) { $synth.SelectVoice($voice.VoiceInfo.Name) }; \
+                 $synth.Speak($env:SPEAK_TEXT)",
+            ])
+            .env("SPEAK_TEXT", text);
+        if danish {
+            command.env("SPEAK_CULTURE", "da-DK");
+        }
+        command
+    } else {
+        let mut command = Command::new("espeak-ng");
+        if danish {
+            command.args(["-v", "da"]);
+        }
+        command.arg("--").arg(text);
+        command
+    };
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(not(feature = "tts"))]
+pub fn speak(_text: &str, _language: &str) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "built without the \"tts\" feature",
+    ))
+}
+
+/// Checks whether the platform's speech command can read the deck's language.
+///
+/// For a Danish deck this means a Danish voice is installed. This runs the speech
+/// command and waits for it, so the result should be kept rather than asked for every frame.
+#[cfg(feature = "tts")]
+pub fn has_voice(language: &str) -> bool {
+    let danish = language == "Danish";
+
+    let (mut command, marker) = if cfg!(target_os = "macos") {
+        let mut command = Command::new("say");
+        command.args(["-v", "?"]);
+        (command, "da_DK")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            "Add-Type -AssemblyName System.Speech; \
+             (New-Object System.Speech.Synthesis.SpeechSynthesizer).GetInstalledVoices() | \
+             ForEach-Object { $_.VoiceInfo.Culture.Name }",
+        ]);
+        (command, "da-DK")
+    } else {
+        let mut command = Command::new("espeak-ng");
+        command.arg("--voices=da");
+        (command, " da ")
+    };
+
+    let Ok(output) = command.stdin(Stdio::null()).stderr(Stdio::null()).output() else {
+        return false;
+    };
+    output.status.success() && (!danish || String::from_utf8_lossy(&output.stdout).contains(marker))
+}
+
+#[cfg(not(feature = "tts"))]
+pub fn has_voice(_language: &str) -> bool {
+    false
+}
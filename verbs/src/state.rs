@@ -0,0 +1,398 @@
+use crate::schedule::Review;
+use crate::settings::Settings;
+use crate::stats::{self, VerbStat};
+use crate::streak::Streak;
+use crate::{ConjugationForm, PracticeMode};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Everything that is remembered between runs of the app.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AppState {
+    pub settings: Settings,
+    /// Infinitives of verbs the user has taken out of rotation
+    pub disabled_verbs: HashSet<String>,
+    /// Infinitives of verbs the user has starred
+    pub starred_verbs: HashSet<String>,
+    /// Infinitives of verbs the user has marked as learned
+    pub learned_verbs: HashSet<String>,
+    /// All-time results keyed by infinitive
+    pub verb_stats: HashMap<String, VerbStat>,
+    /// When each verb answered so far is next due for review, keyed by infinitive
+    pub schedule: HashMap<String, Review>,
+    /// The Leitner box each verb answered so far is in, keyed by infinitive
+    pub leitner_boxes: HashMap<String, u8>,
+    pub current_question: Option<SavedQuestion>,
+    pub streak: Streak,
+    /// Best session accuracy in percent, keyed by deck path
+    pub best_accuracy: HashMap<String, f32>,
+    /// The first-launch walkthrough was finished or turned off
+    pub seen_onboarding: bool,
+}
+
+/// The question that was on screen when the app was closed.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedQuestion {
+    /// The verb is stored by infinitive, as the deck is reshuffled on every start
+    pub infinitive: String,
+    pub practice_mode: PracticeMode,
+    pub conjugation_form: ConjugationForm,
+    /// The form was given and the infinitive asked for
+    #[serde(default)]
+    pub conjugation_flipped: bool,
+    pub user_answer: String,
+}
+
+/// Least time between autosaves, so answering quickly doesn't write the state file each time.
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Keeps track of when progress is autosaved after an answer.
+///
+/// An answer given within `AUTOSAVE_INTERVAL` of the last save waits, along with any that
+/// follow it, until the interval is up, when they are written together.
+#[derive(Debug, Default)]
+pub struct Autosave {
+    last_save: Option<Instant>,
+    pending: bool,
+}
+
+impl Autosave {
+    /// Asks for the state to be saved, as soon as the interval allows.
+    pub fn request(&mut self) {
+        self.pending = true;
+    }
+
+    /// Notes that the state was just saved, whether by an autosave or not.
+    pub fn saved(&mut self, now: Instant) {
+        self.pending = false;
+        self.last_save = Some(now);
+    }
+
+    /// How long until the requested save is due, zero once it is, or none if there's
+    /// nothing to save.
+    pub fn due_in(&self, now: Instant) -> Option<Duration> {
+        if !self.pending {
+            return None;
+        }
+        Some(self.last_save.map_or(Duration::ZERO, |last| {
+            AUTOSAVE_INTERVAL.saturating_sub(now.saturating_duration_since(last))
+        }))
+    }
+}
+
+/// Number of backups of the state file that are kept.
+pub const BACKUPS_KEPT: usize = 5;
+/// A backup is made before the first save of a day, so a bad save or an accidental reset
+/// can't push every good copy out at once.
+const BACKUP_INTERVAL_SECS: u64 = 86_400;
+
+/// A copy of the state file, as it was before a save.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// When the backup was made, in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// The platform configuration directory, e.g. `~/.config` on Linux.
+fn config_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }
+}
+
+/// Where the deck is kept when none is given on the command line.
+pub fn deck_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("dansk-uddannelse").join("verbs.json"))
+}
+
+pub fn state_path() -> Option<PathBuf> {
+    // Tests mustn't read or overwrite the user's own progress
+    if cfg!(test) {
+        return None;
+    }
+    config_dir().map(|dir| dir.join("dansk-uddannelse").join("state.json"))
+}
+
+pub fn load_state() -> AppState {
+    match state_path() {
+        Some(path) => load_state_from(&path),
+        None => AppState::default(),
+    }
+}
+
+fn load_state_from(path: &Path) -> AppState {
+    match fs::read_to_string(path) {
+        Ok(data) => match serde_json::from_str(&data) {
+            Ok(state) => state,
+            Err(e) => {
+                eprintln!("Error parsing saved state: {}", e);
+                AppState::default()
+            }
+        },
+        // Nothing has been saved yet
+        Err(e) if e.kind() == io::ErrorKind::NotFound => AppState::default(),
+        Err(e) => {
+            eprintln!("Error reading saved state: {}", e);
+            AppState::default()
+        }
+    }
+}
+
+/// Where the state file is written to, so that failing writes can be simulated.
+pub trait StateWriter {
+    fn write(&self, path: &Path, data: &str) -> io::Result<()>;
+}
+
+/// Writes the state file to disk with `write_atomically`.
+pub struct FileWriter;
+
+impl StateWriter for FileWriter {
+    fn write(&self, path: &Path, data: &str) -> io::Result<()> {
+        write_atomically(path, data)
+    }
+}
+
+/// Writes a file, creating its directory first.
+///
+/// The data goes to a temporary file next to it first, which is flushed to disk and then
+/// renamed over it, so the file is never left half written if the app is killed mid-save.
+pub fn write_atomically(path: &Path, data: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(data.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)
+}
+
+pub fn save_state(state: &AppState) -> io::Result<()> {
+    let path = state_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no configuration directory"))?;
+    save_state_with(state, &path, &FileWriter)
+}
+
+/// Writes the new state over the state file, backing up the file as it was first if the
+/// day's backup hasn't been made yet.
+pub fn save_state_with(state: &AppState, path: &Path, writer: &impl StateWriter) -> io::Result<()> {
+    let data = serde_json::to_string_pretty(state).map_err(io::Error::other)?;
+    let now = stats::now();
+    // A missing backup shouldn't stop the state itself from being saved
+    if is_backup_due(path, now)
+        && let Err(e) = back_up_state(path, now, BACKUPS_KEPT)
+    {
+        eprintln!("Error backing up state: {}", e);
+    }
+    writer.write(path, &data)
+}
+
+fn backup_dir(path: &Path) -> PathBuf {
+    path.with_file_name("backups")
+}
+
+/// Backups of the state file at `path`, newest first.
+pub fn list_backups(path: &Path) -> Vec<Backup> {
+    let Ok(entries) = fs::read_dir(backup_dir(path)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let timestamp = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("state-")?
+                .strip_suffix(".json")?
+                .parse()
+                .ok()?;
+            Some(Backup { path, timestamp })
+        })
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.timestamp));
+    backups
+}
+
+fn is_backup_due(path: &Path, now: u64) -> bool {
+    list_backups(path)
+        .first()
+        .is_none_or(|newest| now.saturating_sub(newest.timestamp) >= BACKUP_INTERVAL_SECS)
+}
+
+/// Copies the state file into the backup directory, then deletes all but the newest `keep`
+/// backups. Does nothing if nothing has been saved yet.
+pub fn back_up_state(path: &Path, now: u64, keep: usize) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let dir = backup_dir(path);
+    fs::create_dir_all(&dir)?;
+    fs::copy(path, dir.join(format!("state-{}.json", now)))?;
+    for backup in list_backups(path).into_iter().skip(keep) {
+        fs::remove_file(backup.path)?;
+    }
+    Ok(())
+}
+
+/// Reads a backup to put in place of the current state, which is backed up first so the
+/// restore can be undone.
+pub fn restore_backup(backup: &Backup) -> io::Result<AppState> {
+    let data = fs::read_to_string(&backup.path)?;
+    let state = serde_json::from_str(&data).map_err(io::Error::other)?;
+    if let Some(path) = state_path() {
+        back_up_state(&path, stats::now(), BACKUPS_KEPT)?;
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ReadOnlyWriter;
+
+    impl StateWriter for ReadOnlyWriter {
+        fn write(&self, _path: &Path, _data: &str) -> io::Result<()> {
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "read-only file system",
+            ))
+        }
+    }
+
+    fn temp_state_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "dansk-uddannelse-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("state.json")
+    }
+
+    #[test]
+    fn keeps_the_previous_state_after_an_interrupted_write() {
+        let path = temp_state_path("interrupted");
+        let mut state = AppState::default();
+        state.disabled_verbs.insert("at være".to_string());
+        save_state_with(&state, &path, &FileWriter).unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+
+        // A save that was killed part way leaves a truncated temporary file behind
+        fs::write(
+            path.with_extension("json.tmp"),
+            "{\"disabled_verbs\": [\"at h",
+        )
+        .unwrap();
+
+        let loaded = load_state_from(&path);
+        assert!(loaded.disabled_verbs.contains("at være"));
+
+        // The next save replaces the leftover
+        save_state_with(&AppState::default(), &path, &FileWriter).unwrap();
+        assert!(load_state_from(&path).disabled_verbs.is_empty());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_backups() {
+        let path = temp_state_path("backups");
+        // Nothing to back up before the first save
+        back_up_state(&path, 0, 3).unwrap();
+        assert!(list_backups(&path).is_empty());
+
+        save_state_with(&AppState::default(), &path, &FileWriter).unwrap();
+        assert!(list_backups(&path).is_empty());
+        for day in 1..=7 {
+            let now = day * BACKUP_INTERVAL_SECS;
+            assert!(is_backup_due(&path, now));
+            back_up_state(&path, now, 3).unwrap();
+            assert!(!is_backup_due(&path, now + 60));
+        }
+
+        let timestamps: Vec<u64> = list_backups(&path)
+            .iter()
+            .map(|backup| backup.timestamp / BACKUP_INTERVAL_SECS)
+            .collect();
+        assert_eq!(timestamps, [7, 6, 5]);
+        assert_eq!(fs::read_dir(backup_dir(&path)).unwrap().count(), 3);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn saving_backs_up_the_previous_state_once_a_day() {
+        let path = temp_state_path("save-backup");
+        let mut state = AppState::default();
+        state.disabled_verbs.insert("at være".to_string());
+        save_state_with(&state, &path, &FileWriter).unwrap();
+        save_state_with(&AppState::default(), &path, &FileWriter).unwrap();
+        // Later saves that day leave the backup of the earlier state alone
+        for _ in 0..BACKUPS_KEPT + 1 {
+            save_state_with(&AppState::default(), &path, &FileWriter).unwrap();
+        }
+
+        let backups = list_backups(&path);
+        assert_eq!(backups.len(), 1);
+        let backed_up: AppState =
+            serde_json::from_str(&fs::read_to_string(&backups[0].path).unwrap()).unwrap();
+        assert!(backed_up.disabled_verbs.contains("at være"));
+        assert!(load_state_from(&path).disabled_verbs.is_empty());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn rapid_autosaves_coalesce() {
+        let path = temp_state_path("autosave");
+        let start = Instant::now();
+        let mut autosave = Autosave::default();
+        let mut writes = 0;
+        let mut save_if_due = |autosave: &mut Autosave, now: Instant| {
+            if autosave.due_in(now) == Some(Duration::ZERO) {
+                save_state_with(&AppState::default(), &path, &FileWriter).unwrap();
+                autosave.saved(now);
+                writes += 1;
+            }
+        };
+
+        // Ten answers a tenth of a second apart
+        for answer in 0..10 {
+            let now = start + Duration::from_millis(100 * answer);
+            autosave.request();
+            save_if_due(&mut autosave, now);
+        }
+        // The first answer is written straight away, the rest wait for the interval
+        let last_answer = start + Duration::from_millis(900);
+        assert_eq!(
+            autosave.due_in(last_answer),
+            Some(Duration::from_millis(100))
+        );
+        save_if_due(&mut autosave, start + AUTOSAVE_INTERVAL);
+        assert_eq!(autosave.due_in(start + AUTOSAVE_INTERVAL), None);
+        assert_eq!(writes, 2);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn reports_write_failures_instead_of_panicking() {
+        let result = save_state_with(
+            &AppState::default(),
+            Path::new("/locked/state.json"),
+            &ReadOnlyWriter,
+        );
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(error.to_string(), "read-only file system");
+    }
+}
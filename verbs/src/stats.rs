@@ -0,0 +1,814 @@
+use crate::{ConjugationForm, PracticeMode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A single graded answer, recorded once per question.
+///
+/// The session's statistics are worked out from the log of these events, so taking
+/// one back is just removing it from the log.
+#[derive(Debug, Clone)]
+pub struct AttemptEvent {
+    pub infinitive: String,
+    pub mode: PracticeMode,
+    pub form: Option<ConjugationForm>,
+    /// The form was given and the infinitive asked for, instead of the other way round
+    pub flipped: bool,
+    pub correct: bool,
+    /// When the answer was graded, in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Time from showing the question to the final answer, if it was answered right unaided
+    pub response_time: Option<Duration>,
+    /// Part of the answer was given away by a hint
+    pub hinted: bool,
+    /// Characters in the typed answer, 0 when an option was picked instead
+    pub typed_chars: usize,
+    /// The answer given, with the fields of an all forms question joined by ", "
+    pub answer: String,
+    /// The accepted answers, as shown when the question is missed
+    pub expected: String,
+}
+
+/// Number of attempts and how many of them were correct.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tally {
+    pub attempts: usize,
+    pub correct: usize,
+}
+
+impl Tally {
+    fn add(&mut self, correct: bool) {
+        self.attempts += 1;
+        if correct {
+            self.correct += 1;
+        }
+    }
+
+    pub fn accuracy(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.attempts as f32 * 100.0
+        }
+    }
+}
+
+/// Number of a verb's latest outcomes kept in its history.
+pub const RECENT_KEPT: usize = 10;
+
+/// All-time results for one verb, kept between runs.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VerbStat {
+    pub attempts: u32,
+    pub correct: u32,
+    /// Number of times the verb was answered wrong
+    pub lapses: u32,
+    /// Number of the right answers that were helped by a hint
+    pub hinted: u32,
+    /// When the verb was last answered, in seconds since the Unix epoch
+    pub last_seen: Option<u64>,
+    /// Whether each of the latest answers was right, oldest first, up to `RECENT_KEPT`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recent: Vec<bool>,
+}
+
+/// When a verb counts as mastered: answered often enough, and right often enough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MasteryRule {
+    pub min_attempts: u32,
+    /// Accuracy in percent
+    pub min_accuracy: u32,
+}
+
+impl Default for MasteryRule {
+    fn default() -> Self {
+        Self {
+            min_attempts: 5,
+            min_accuracy: 90,
+        }
+    }
+}
+
+impl MasteryRule {
+    /// Right answers that were helped by a hint count as half right.
+    pub fn is_met(&self, verb_stat: &VerbStat) -> bool {
+        let hinted = verb_stat.hinted.min(verb_stat.correct);
+        let right_halves = u64::from(verb_stat.correct) * 2 - u64::from(hinted);
+        verb_stat.attempts > 0
+            && verb_stat.attempts >= self.min_attempts
+            && right_halves * 100
+                >= u64::from(self.min_accuracy) * 2 * u64::from(verb_stat.attempts)
+    }
+}
+
+/// A verb's accuracy this session next to its accuracy in earlier sessions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerbChange {
+    pub infinitive: String,
+    pub session: f32,
+    pub before: f32,
+}
+
+impl VerbChange {
+    /// Change in accuracy, in percentage points.
+    pub fn points(&self) -> f32 {
+        self.session - self.before
+    }
+}
+
+/// Answers a verb needs, this session and before it, for a change to count.
+const MIN_SESSION_ATTEMPTS: u32 = 2;
+const MIN_EARLIER_ATTEMPTS: u32 = 3;
+
+/// How a session's accuracy compares with the best earlier session on the same deck.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BestComparison {
+    /// No earlier session to compare with
+    FirstSession,
+    NewBest {
+        previous: f32,
+    },
+    Tied,
+    Below {
+        best: f32,
+    },
+}
+
+impl BestComparison {
+    /// Accuracies are compared to a tenth of a percentage point.
+    pub fn new(accuracy: f32, best: Option<f32>) -> Self {
+        let Some(best) = best else {
+            return BestComparison::FirstSession;
+        };
+        let round = |accuracy: f32| (accuracy * 10.0).round() as i32;
+        match round(accuracy).cmp(&round(best)) {
+            std::cmp::Ordering::Greater => BestComparison::NewBest { previous: best },
+            std::cmp::Ordering::Equal => BestComparison::Tied,
+            std::cmp::Ordering::Less => BestComparison::Below { best },
+        }
+    }
+}
+
+/// The bucket, such as a kind of question, with the lowest accuracy among those answered
+/// at least `min_attempts` times, so a couple of unlucky answers don't decide it. Ties go
+/// to the bucket listed first.
+pub fn weakest<T>(
+    buckets: impl IntoIterator<Item = (T, Tally)>,
+    min_attempts: usize,
+) -> Option<(T, Tally)> {
+    buckets
+        .into_iter()
+        .filter(|(_, tally)| tally.attempts > 0 && tally.attempts >= min_attempts)
+        // Compared as fractions, so equal accuracies are equal whatever the rounding
+        .min_by(|(_, a), (_, b)| (a.correct * b.attempts).cmp(&(b.correct * a.attempts)))
+}
+
+/// The verbs of the given answers, each once, in the order they first came up.
+pub fn missed_verbs(mistakes: &[AttemptEvent]) -> Vec<String> {
+    let mut verbs: Vec<String> = Vec::new();
+    for mistake in mistakes {
+        if !verbs.contains(&mistake.infinitive) {
+            verbs.push(mistake.infinitive.clone());
+        }
+    }
+    verbs
+}
+
+#[derive(Debug, Default)]
+pub struct Stats {
+    /// Append-only log of the answers given in this session
+    pub events: Vec<AttemptEvent>,
+    /// All-time results keyed by infinitive, including this session's events
+    pub verbs: HashMap<String, VerbStat>,
+    /// All-time results from before this session
+    earlier: HashMap<String, VerbStat>,
+    /// Bumped on every change, so figures worked out from the stats know to be redone
+    revision: u64,
+}
+
+impl Stats {
+    pub fn new(mut verbs: HashMap<String, VerbStat>) -> Self {
+        // Results saved before lapses were counted have one for each wrong answer
+        for verb_stat in verbs.values_mut() {
+            let wrong = verb_stat.attempts.saturating_sub(verb_stat.correct);
+            verb_stat.lapses = verb_stat.lapses.max(wrong);
+        }
+        Self {
+            events: Vec::new(),
+            earlier: verbs.clone(),
+            verbs,
+            revision: 0,
+        }
+    }
+
+    pub fn record(&mut self, event: AttemptEvent) {
+        apply(&mut self.verbs, &event);
+        self.events.push(event);
+        self.revision += 1;
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Takes back the last answer of the session and works the all-time results out again.
+    pub fn undo_last(&mut self) -> Option<AttemptEvent> {
+        let event = self.events.pop()?;
+        self.revision += 1;
+        self.verbs = self.earlier.clone();
+        for event in &self.events {
+            apply(&mut self.verbs, event);
+        }
+        Some(event)
+    }
+
+    /// Forgets this session's answers, keeping the all-time results.
+    pub fn start_new_session(&mut self) {
+        self.events.clear();
+        self.earlier = self.verbs.clone();
+        self.revision += 1;
+    }
+
+    /// Moves a verb's results over to its new infinitive after it was edited.
+    pub fn rename(&mut self, from: &str, to: &str) {
+        for verbs in [&mut self.verbs, &mut self.earlier] {
+            if let Some(verb_stat) = verbs.remove(from) {
+                verbs.insert(to.to_string(), verb_stat);
+            }
+        }
+        for event in &mut self.events {
+            if event.infinitive == from {
+                event.infinitive = to.to_string();
+            }
+        }
+        self.revision += 1;
+    }
+
+    /// A leech is a verb that keeps being answered wrong.
+    pub fn is_leech(&self, infinitive: &str, threshold: u32) -> bool {
+        self.verbs
+            .get(infinitive)
+            .is_some_and(|verb_stat| verb_stat.lapses >= threshold)
+    }
+
+    /// True if the verb's all-time results meet the mastery rule.
+    pub fn is_mastered(&self, infinitive: &str, rule: &MasteryRule) -> bool {
+        self.verbs
+            .get(infinitive)
+            .is_some_and(|verb_stat| rule.is_met(verb_stat))
+    }
+
+    /// Infinitives of all leeches, worst first.
+    pub fn leeches(&self, threshold: u32) -> Vec<(&str, &VerbStat)> {
+        let mut leeches: Vec<(&str, &VerbStat)> = self
+            .verbs
+            .iter()
+            .filter(|(_, verb_stat)| verb_stat.lapses >= threshold)
+            .map(|(infinitive, verb_stat)| (infinitive.as_str(), verb_stat))
+            .collect();
+        leeches.sort_by(|a, b| b.1.lapses.cmp(&a.1.lapses).then(a.0.cmp(b.0)));
+        leeches
+    }
+
+    /// True if the verb has never been answered, in this or an earlier session.
+    pub fn is_unseen(&self, infinitive: &str) -> bool {
+        self.verbs
+            .get(infinitive)
+            .is_none_or(|verb_stat| verb_stat.attempts == 0)
+    }
+
+    /// True once a verb has been answered `questions` times, so it's no longer new.
+    pub fn is_introduced(&self, infinitive: &str, questions: u32) -> bool {
+        self.verbs
+            .get(infinitive)
+            .is_some_and(|verb_stat| verb_stat.attempts >= questions)
+    }
+
+    pub fn average_response_time(&self) -> Option<Duration> {
+        average(self.events.iter().filter_map(|a| a.response_time))
+    }
+
+    /// Average response time per verb this session, slowest first.
+    pub fn response_times_by_verb(&self) -> Vec<(&str, Duration)> {
+        let mut times: HashMap<&str, Vec<Duration>> = HashMap::new();
+        for attempt in &self.events {
+            if let Some(time) = attempt.response_time {
+                times.entry(&attempt.infinitive).or_default().push(time);
+            }
+        }
+
+        let mut averages: Vec<(&str, Duration)> = times
+            .into_iter()
+            .filter_map(|(infinitive, times)| Some((infinitive, average(times.into_iter())?)))
+            .collect();
+        averages.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        averages
+    }
+
+    /// Typing speed over this session's answers in words per minute, counting five
+    /// characters as a word. Only answers typed right without a hint count; ones with
+    /// no measurable time are left out rather than counted as infinitely fast.
+    pub fn typing_speed(&self) -> Option<f64> {
+        let (chars, time) = self
+            .events
+            .iter()
+            .filter(|a| a.correct && a.mode != PracticeMode::MultipleChoice && a.typed_chars > 0)
+            .filter_map(|a| Some((a.typed_chars, a.response_time?)))
+            .filter(|(_, time)| !time.is_zero())
+            .fold((0, Duration::ZERO), |(chars, total), (typed, time)| {
+                (chars + typed, total + time)
+            });
+        let minutes = time.as_secs_f64() / 60.0;
+        (minutes > 0.0).then(|| chars as f64 / 5.0 / minutes)
+    }
+
+    /// This session's wrong answers, in the order they were given.
+    pub fn mistakes(&self) -> Vec<AttemptEvent> {
+        self.events
+            .iter()
+            .filter(|attempt| !attempt.correct)
+            .cloned()
+            .collect()
+    }
+
+    pub fn total(&self) -> Tally {
+        let mut tally = Tally::default();
+        for attempt in &self.events {
+            tally.add(attempt.correct);
+        }
+        tally
+    }
+
+    pub fn by_verb(&self, infinitive: &str) -> Tally {
+        let mut tally = Tally::default();
+        for attempt in self.events.iter().filter(|a| a.infinitive == infinitive) {
+            tally.add(attempt.correct);
+        }
+        tally
+    }
+
+    pub fn by_mode(&self, mode: PracticeMode) -> Tally {
+        let mut tally = Tally::default();
+        for attempt in self.events.iter().filter(|a| a.mode == mode) {
+            tally.add(attempt.correct);
+        }
+        tally
+    }
+
+    /// Results of conjugation questions on a form, asked in either direction.
+    pub fn by_form(&self, form: ConjugationForm) -> Tally {
+        let mut tally = Tally::default();
+        for attempt in self.events.iter().filter(|a| a.form == Some(form)) {
+            tally.add(attempt.correct);
+        }
+        tally
+    }
+
+    /// Results of conjugation questions on a form asked in one direction, from the
+    /// infinitive to the form or, when `flipped`, from the form to the infinitive.
+    pub fn by_form_direction(&self, form: ConjugationForm, flipped: bool) -> Tally {
+        let mut tally = Tally::default();
+        for attempt in self
+            .events
+            .iter()
+            .filter(|a| a.form == Some(form) && a.flipped == flipped)
+        {
+            tally.add(attempt.correct);
+        }
+        tally
+    }
+
+    /// Results per verb in this session.
+    pub fn session_by_verb(&self) -> HashMap<&str, Tally> {
+        let mut tallies: HashMap<&str, Tally> = HashMap::new();
+        for attempt in &self.events {
+            tallies
+                .entry(&attempt.infinitive)
+                .or_default()
+                .add(attempt.correct);
+        }
+        tallies
+    }
+
+    /// The verbs that improved most and those that slipped most compared with earlier
+    /// sessions, at most `count` of each, biggest change first.
+    ///
+    /// Verbs with too few answers this session or before it are left out.
+    pub fn accuracy_changes(&self, count: usize) -> (Vec<VerbChange>, Vec<VerbChange>) {
+        let mut changes: Vec<VerbChange> = self
+            .session_by_verb()
+            .into_iter()
+            .filter_map(|(infinitive, session)| {
+                let earlier = self.earlier.get(infinitive)?;
+                if (session.attempts as u32) < MIN_SESSION_ATTEMPTS
+                    || earlier.attempts < MIN_EARLIER_ATTEMPTS
+                {
+                    return None;
+                }
+                Some(VerbChange {
+                    infinitive: infinitive.to_string(),
+                    session: session.accuracy(),
+                    before: earlier.correct as f32 / earlier.attempts as f32 * 100.0,
+                })
+            })
+            .collect();
+        changes.sort_by(|a, b| {
+            b.points()
+                .total_cmp(&a.points())
+                .then_with(|| a.infinitive.cmp(&b.infinitive))
+        });
+
+        let improved: Vec<VerbChange> = changes
+            .iter()
+            .filter(|change| change.points() > 0.0)
+            .take(count)
+            .cloned()
+            .collect();
+        let declined: Vec<VerbChange> = changes
+            .iter()
+            .rev()
+            .filter(|change| change.points() < 0.0)
+            .take(count)
+            .cloned()
+            .collect();
+        (improved, declined)
+    }
+
+    /// Questions answered after part of the answer was given away.
+    pub fn hinted(&self) -> Tally {
+        let mut tally = Tally::default();
+        for attempt in self.events.iter().filter(|a| a.hinted) {
+            tally.add(attempt.correct);
+        }
+        tally
+    }
+}
+
+/// Adds one answer to the all-time results.
+fn apply(verbs: &mut HashMap<String, VerbStat>, event: &AttemptEvent) {
+    let verb_stat = verbs.entry(event.infinitive.clone()).or_default();
+    verb_stat.attempts += 1;
+    if event.correct {
+        verb_stat.correct += 1;
+        if event.hinted {
+            verb_stat.hinted += 1;
+        }
+    } else {
+        verb_stat.lapses += 1;
+    }
+    verb_stat.last_seen = Some(event.timestamp);
+    verb_stat.recent.push(event.correct);
+    if verb_stat.recent.len() > RECENT_KEPT {
+        verb_stat.recent.remove(0);
+    }
+}
+
+/// The current time in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Describes how long ago something happened, e.g. "3 days ago".
+pub fn format_ago(seconds: u64) -> String {
+    const UNITS: [(u64, &str); 5] = [
+        (365 * 86_400, "year"),
+        (30 * 86_400, "month"),
+        (86_400, "day"),
+        (3_600, "hour"),
+        (60, "minute"),
+    ];
+    for (unit, name) in UNITS {
+        let count = seconds / unit;
+        if count > 0 {
+            return format!(
+                "{} {}{} ago",
+                count,
+                name,
+                if count == 1 { "" } else { "s" }
+            );
+        }
+    }
+    "just now".to_string()
+}
+
+/// Formats a time in seconds since the Unix epoch as e.g. "2026-10-14 09:30 UTC".
+pub fn format_date(seconds: u64) -> String {
+    i64::try_from(seconds)
+        .ok()
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+        .map_or_else(String::new, |time| {
+            time.format("%Y-%m-%d %H:%M UTC").to_string()
+        })
+}
+
+fn average(times: impl Iterator<Item = Duration>) -> Option<Duration> {
+    let (sum, count) = times.fold((Duration::ZERO, 0u32), |(sum, count), time| {
+        (sum + time, count + 1)
+    });
+    (count > 0).then(|| sum / count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_relative_times() {
+        assert_eq!(format_ago(30), "just now");
+        assert_eq!(format_ago(60), "1 minute ago");
+        assert_eq!(format_ago(2 * 3_600 + 59), "2 hours ago");
+        assert_eq!(format_ago(3 * 86_400), "3 days ago");
+        assert_eq!(format_ago(400 * 86_400), "1 year ago");
+    }
+
+    #[test]
+    fn mastery_needs_both_attempts_and_accuracy() {
+        let rule = MasteryRule::default();
+        let stat = |attempts, correct| VerbStat {
+            attempts,
+            correct,
+            ..VerbStat::default()
+        };
+        assert!(rule.is_met(&stat(10, 9)));
+        assert!(!rule.is_met(&stat(10, 8)));
+        // Right every time, but not answered often enough yet
+        assert!(!rule.is_met(&stat(4, 4)));
+        assert!(!rule.is_met(&stat(0, 0)));
+        // Hinted answers count as half right
+        let hinted = |hinted| VerbStat {
+            hinted,
+            ..stat(10, 10)
+        };
+        assert!(rule.is_met(&hinted(2)));
+        assert!(!rule.is_met(&hinted(4)));
+        assert!(
+            MasteryRule {
+                min_attempts: 0,
+                min_accuracy: 0
+            }
+            .is_met(&stat(1, 0))
+        );
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01 00:00 UTC");
+        assert_eq!(
+            format_date(951_782_400 + 3_600 + 120),
+            "2000-02-29 01:02 UTC"
+        );
+        assert_eq!(format_date(1_791_987_000), "2026-10-14 14:10 UTC");
+    }
+
+    fn answer(stats: &mut Stats, infinitive: &str, correct: bool) {
+        stats.record(AttemptEvent {
+            infinitive: infinitive.to_string(),
+            mode: PracticeMode::Translation,
+            form: None,
+            flipped: false,
+            correct,
+            timestamp: now(),
+            response_time: None,
+            hinted: false,
+            typed_chars: 0,
+            answer: String::new(),
+            expected: String::new(),
+        });
+    }
+
+    #[test]
+    fn lists_the_mistakes_and_the_verbs_missed() {
+        let mut stats = Stats::default();
+        answer(&mut stats, "at gå", false);
+        answer(&mut stats, "at se", true);
+        answer(&mut stats, "at tage", false);
+        answer(&mut stats, "at gå", false);
+        let mistakes = stats.mistakes();
+        assert_eq!(mistakes.len(), 3);
+        assert_eq!(missed_verbs(&mistakes), ["at gå", "at tage"]);
+    }
+
+    #[test]
+    fn picks_the_weakest_bucket_with_enough_attempts() {
+        let tally = |correct, attempts| Tally { attempts, correct };
+        let buckets = [
+            ("present", tally(9, 10)),
+            ("past", tally(6, 10)),
+            // Lower, but too few answers to count
+            ("translation", tally(0, 2)),
+            // As weak as the past tense, which is listed first
+            ("past participle", tally(12, 20)),
+        ];
+        let (name, weakest_tally) = weakest(buckets, 5).unwrap();
+        assert_eq!(name, "past");
+        assert_eq!(weakest_tally.attempts, 10);
+        assert_eq!(weakest(buckets, 1).unwrap().0, "translation");
+        assert!(weakest(buckets, 50).is_none());
+    }
+
+    #[test]
+    fn keeps_the_latest_outcomes_of_a_verb() {
+        let mut stats = Stats::default();
+        answer(&mut stats, "at være", false);
+        for _ in 0..RECENT_KEPT {
+            answer(&mut stats, "at være", true);
+        }
+        let verb_stat = &stats.verbs["at være"];
+        assert_eq!(verb_stat.recent, [true; RECENT_KEPT]);
+        assert_eq!((verb_stat.attempts, verb_stat.lapses), (11, 1));
+
+        // Taking an answer back takes it out of the history too
+        answer(&mut stats, "at være", false);
+        stats.undo_last();
+        assert_eq!(stats.verbs["at være"].recent, [true; RECENT_KEPT]);
+
+        // Verbs saved before the history was kept load without one
+        let old: VerbStat = serde_json::from_str(r#"{"attempts": 3, "correct": 2}"#).unwrap();
+        assert!(old.recent.is_empty());
+    }
+
+    #[test]
+    fn keeps_lapses_between_runs() {
+        let mut stats = Stats::default();
+        answer(&mut stats, "at gå", false);
+        answer(&mut stats, "at gå", false);
+        let saved = serde_json::to_string(&stats.verbs).unwrap();
+        let loaded = Stats::new(serde_json::from_str(&saved).unwrap());
+        assert_eq!(loaded.verbs["at gå"].lapses, 2);
+
+        // Results saved before lapses were counted get them from the wrong answers
+        let old = serde_json::from_str(r#"{"at gå": {"attempts": 9, "correct": 1}}"#).unwrap();
+        assert!(Stats::new(old).is_leech("at gå", 8));
+    }
+
+    #[test]
+    fn tallies_each_direction_of_a_form() {
+        let mut stats = Stats::default();
+        for (flipped, correct) in [(false, true), (true, false), (true, true)] {
+            stats.record(AttemptEvent {
+                infinitive: "at ligge".to_string(),
+                mode: PracticeMode::Conjugation,
+                form: Some(ConjugationForm::Past),
+                flipped,
+                correct,
+                timestamp: 0,
+                response_time: None,
+                hinted: false,
+                typed_chars: 0,
+                answer: String::new(),
+                expected: String::new(),
+            });
+        }
+        let forward = stats.by_form_direction(ConjugationForm::Past, false);
+        let flipped = stats.by_form_direction(ConjugationForm::Past, true);
+        assert_eq!((forward.attempts, forward.correct), (1, 1));
+        assert_eq!((flipped.attempts, flipped.correct), (2, 1));
+        assert_eq!(stats.by_form(ConjugationForm::Past).attempts, 3);
+        assert_eq!(stats.by_form(ConjugationForm::Present).attempts, 0);
+    }
+
+    #[test]
+    fn works_out_typing_speed() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.typing_speed(), None);
+        let typed = |stats: &mut Stats, mode, correct, secs, typed_chars| {
+            stats.record(AttemptEvent {
+                infinitive: "at være".to_string(),
+                mode,
+                form: None,
+                flipped: false,
+                correct,
+                timestamp: 0,
+                response_time: Some(Duration::from_secs(secs)),
+                hinted: false,
+                typed_chars,
+                answer: String::new(),
+                expected: String::new(),
+            });
+        };
+        // Answered in no measurable time, so left out rather than infinitely fast
+        typed(&mut stats, PracticeMode::Translation, true, 0, 10);
+        assert_eq!(stats.typing_speed(), None);
+        typed(&mut stats, PracticeMode::Translation, true, 6, 10);
+        typed(&mut stats, PracticeMode::Conjugation, true, 6, 20);
+        // Picked or missed answers don't count
+        typed(&mut stats, PracticeMode::MultipleChoice, true, 1, 0);
+        typed(&mut stats, PracticeMode::Translation, false, 1, 40);
+        // 30 characters are 6 words, in 12 seconds
+        assert_eq!(stats.typing_speed(), Some(30.0));
+    }
+
+    #[test]
+    fn ranks_improved_and_declined_verbs() {
+        let mut stats = Stats::default();
+        for (infinitive, earlier) in [
+            ("at være", [false, false, false, true]),
+            ("at have", [true, true, true, true]),
+            ("at gå", [true, true, false, false]),
+            ("at se", [false, false, false, false]),
+        ] {
+            for correct in earlier {
+                answer(&mut stats, infinitive, correct);
+            }
+        }
+        stats.start_new_session();
+        for (infinitive, session) in [
+            ("at være", [true, true]),
+            ("at have", [false, true]),
+            ("at gå", [true, true]),
+        ] {
+            for correct in session {
+                answer(&mut stats, infinitive, correct);
+            }
+        }
+        // Only one answer this session, so it's left out
+        answer(&mut stats, "at se", true);
+
+        let (improved, declined) = stats.accuracy_changes(3);
+        let names = |changes: &[VerbChange]| {
+            changes
+                .iter()
+                .map(|change| change.infinitive.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&improved), ["at være", "at gå"]);
+        assert_eq!(names(&declined), ["at have"]);
+        assert_eq!(improved[0].before, 25.0);
+        assert_eq!(declined[0].points(), -50.0);
+    }
+
+    #[test]
+    fn undo_takes_back_the_last_answer() {
+        let mut stats = Stats::new(HashMap::from([(
+            "at være".to_string(),
+            VerbStat {
+                attempts: 2,
+                correct: 1,
+                lapses: 1,
+                hinted: 0,
+                last_seen: Some(100),
+                recent: vec![true, false],
+            },
+        )]));
+        answer(&mut stats, "at være", true);
+        answer(&mut stats, "at være", false);
+        answer(&mut stats, "at have", false);
+
+        let undone = stats.undo_last().unwrap();
+        assert_eq!(undone.infinitive, "at have");
+        assert!(!stats.verbs.contains_key("at have"));
+        assert_eq!(stats.total().attempts, 2);
+
+        stats.undo_last();
+        stats.undo_last();
+        let verb_stat = &stats.verbs["at være"];
+        assert_eq!((verb_stat.attempts, verb_stat.correct), (2, 1));
+        assert_eq!(verb_stat.last_seen, Some(100));
+        assert!(stats.undo_last().is_none());
+    }
+
+    #[test]
+    fn renaming_keeps_a_verbs_results() {
+        let mut stats = Stats::default();
+        answer(&mut stats, "at laese", true);
+        stats.start_new_session();
+        answer(&mut stats, "at laese", false);
+        stats.rename("at laese", "at læse");
+        assert!(!stats.verbs.contains_key("at laese"));
+        assert_eq!(stats.verbs["at læse"].attempts, 2);
+        assert_eq!(stats.events[0].infinitive, "at læse");
+        // Undoing works the results out from the renamed earlier ones
+        stats.undo_last();
+        assert_eq!(stats.verbs["at læse"].attempts, 1);
+    }
+
+    #[test]
+    fn needs_earlier_answers_to_compare() {
+        let mut stats = Stats::default();
+        for _ in 0..3 {
+            answer(&mut stats, "at være", true);
+        }
+        assert_eq!(stats.accuracy_changes(3), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn compares_with_the_previous_best() {
+        assert_eq!(
+            BestComparison::new(80.0, None),
+            BestComparison::FirstSession
+        );
+        assert_eq!(
+            BestComparison::new(85.0, Some(80.0)),
+            BestComparison::NewBest { previous: 80.0 }
+        );
+        assert_eq!(BestComparison::new(80.01, Some(80.0)), BestComparison::Tied);
+        assert_eq!(
+            BestComparison::new(75.0, Some(80.0)),
+            BestComparison::Below { best: 80.0 }
+        );
+    }
+}
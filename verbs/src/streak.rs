@@ -0,0 +1,104 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// Consecutive days with at least one answered question.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Streak {
+    pub days: u32,
+    /// Day of the last answered question, counted in days since the Unix epoch
+    pub last_practiced: Option<u64>,
+    /// Questions answered on the day of `last_practiced`
+    pub answered: u32,
+}
+
+/// Today in the local time zone, as days since the Unix epoch.
+pub fn today() -> u64 {
+    let days = Local::now()
+        .date_naive()
+        .signed_duration_since(DateTime::UNIX_EPOCH.date_naive())
+        .num_days();
+    u64::try_from(days).unwrap_or(0)
+}
+
+impl Streak {
+    /// Resets a streak that wasn't kept up, returning how many days it had lasted.
+    pub fn check(&mut self, today: u64) -> Option<u32> {
+        let last = self.last_practiced?;
+        if today > last + 1 && self.days > 0 {
+            let lost = self.days;
+            self.days = 0;
+            Some(lost)
+        } else {
+            None
+        }
+    }
+
+    /// Counts an answered question, and today as practiced. Returns true the first time
+    /// it's called on a day.
+    pub fn record(&mut self, today: u64) -> bool {
+        match self.last_practiced {
+            Some(last) if last == today => {
+                self.answered += 1;
+                return false;
+            }
+            Some(last) if last + 1 == today => self.days += 1,
+            _ => self.days = 1,
+        }
+        self.last_practiced = Some(today);
+        self.answered = 1;
+        true
+    }
+
+    /// Questions answered today.
+    pub fn answered_today(&self, today: u64) -> u32 {
+        if self.practiced_today(today) {
+            self.answered
+        } else {
+            0
+        }
+    }
+
+    pub fn practiced_today(&self, today: u64) -> bool {
+        self.last_practiced == Some(today)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_on_consecutive_days() {
+        let mut streak = Streak::default();
+        assert!(streak.record(100));
+        assert!(!streak.record(100));
+        assert!(streak.record(101));
+        assert_eq!(streak.check(102), None);
+        assert_eq!(streak.days, 2);
+    }
+
+    #[test]
+    fn counts_the_answers_of_the_day() {
+        let mut streak = Streak::default();
+        assert_eq!(streak.answered_today(100), 0);
+        streak.record(100);
+        streak.record(100);
+        streak.record(100);
+        assert_eq!(streak.answered_today(100), 3);
+        assert_eq!(streak.answered_today(101), 0);
+        streak.record(101);
+        assert_eq!(streak.answered_today(101), 1);
+    }
+
+    #[test]
+    fn resets_after_a_missed_day() {
+        let mut streak = Streak::default();
+        streak.record(100);
+        streak.record(101);
+        assert_eq!(streak.check(103), Some(2));
+        assert_eq!(streak.days, 0);
+        streak.record(103);
+        assert_eq!(streak.days, 1);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::settings::{ThemeColors, ThemeName};
+use eframe::egui::{self, Color32};
+
+/// The colors the app draws with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub accent: Color32,
+    pub background: Color32,
+    pub text: Color32,
+    /// Right answers
+    pub correct: Color32,
+    /// Wrong answers and errors
+    pub wrong: Color32,
+    /// Almost right answers and warnings
+    pub warning: Color32,
+    /// Whether egui's dark widgets suit the background
+    pub dark: bool,
+}
+
+impl Theme {
+    /// The theme called `name`, using the `custom` colors for the custom theme.
+    pub fn new(name: ThemeName, custom: ThemeColors) -> Self {
+        let colors = name.colors(custom);
+        let background = to_color32(colors.background);
+        let dark = luminance(background) < 0.5;
+        let (correct, wrong, warning) = match name {
+            ThemeName::HighContrast => (
+                Color32::from_rgb(0, 255, 100),
+                Color32::from_rgb(255, 80, 80),
+                Color32::from_rgb(255, 170, 0),
+            ),
+            _ if dark => (
+                Color32::from_rgb(129, 199, 132),
+                Color32::from_rgb(239, 83, 80),
+                Color32::from_rgb(255, 167, 38),
+            ),
+            _ => (
+                Color32::from_rgb(76, 175, 80),
+                Color32::from_rgb(211, 47, 47),
+                Color32::from_rgb(245, 124, 0),
+            ),
+        };
+        Self {
+            accent: to_color32(colors.accent),
+            background,
+            text: to_color32(colors.text),
+            correct,
+            wrong,
+            warning,
+            dark,
+        }
+    }
+
+    /// Fill of the framed sections, a tint of the background towards the accent color.
+    pub fn panel(&self) -> Color32 {
+        self.background.lerp_to_gamma(self.accent, 0.08)
+    }
+
+    /// Fill of a message box drawn in `color`.
+    pub fn tint(&self, color: Color32) -> Color32 {
+        self.background.lerp_to_gamma(color, 0.15)
+    }
+
+    /// egui's built-in theme to draw widgets with.
+    pub fn egui_theme(&self) -> egui::Theme {
+        if self.dark {
+            egui::Theme::Dark
+        } else {
+            egui::Theme::Light
+        }
+    }
+}
+
+/// Relative brightness from 0 (black) to 1 (white).
+fn luminance(color: Color32) -> f32 {
+    (0.2126 * f32::from(color.r()) + 0.7152 * f32::from(color.g()) + 0.0722 * f32::from(color.b()))
+        / 255.0
+}
+
+pub fn to_color32([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+pub fn to_rgb(color: Color32) -> [u8; 3] {
+    [color.r(), color.g(), color.b()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_dark_widgets_for_dark_backgrounds() {
+        let custom = ThemeColors {
+            accent: [200, 0, 0],
+            background: [10, 10, 10],
+            text: [250, 250, 250],
+        };
+        assert!(!Theme::new(ThemeName::Light, custom).dark);
+        assert!(Theme::new(ThemeName::Dark, custom).dark);
+        assert!(Theme::new(ThemeName::HighContrast, custom).dark);
+
+        let theme = Theme::new(ThemeName::Custom, custom);
+        assert!(theme.dark);
+        assert_eq!(theme.accent, Color32::from_rgb(200, 0, 0));
+        assert_eq!(
+            Theme::new(ThemeName::Light, custom).accent,
+            to_color32(ThemeColors::LIGHT.accent)
+        );
+    }
+}
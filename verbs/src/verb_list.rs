@@ -0,0 +1,122 @@
+use crate::{DanishVerbsApp, VerbSort, View, recent_outcomes, stats};
+use eframe::egui;
+
+impl DanishVerbsApp {
+    /// Every active verb in a table to search and sort, where clicking a verb asks it next.
+    pub(crate) fn show_verb_list(&mut self, ui: &mut egui::Ui, body_font: &egui::FontId) {
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Search:")
+                    .font(body_font.clone())
+                    .color(self.theme.text),
+            );
+            ui.add_sized(
+                [ui.available_width(), 36.0],
+                egui::TextEdit::singleline(&mut self.verb_list_search)
+                    .font(body_font.clone())
+                    .hint_text("Danish or English"),
+            );
+        });
+        ui.add_space(8.0);
+
+        let mut sort = self.verb_list_sort;
+        ui.horizontal(|ui| {
+            ui.label("Sort by");
+            for (column, label) in [
+                (VerbSort::Name, "Verb"),
+                (VerbSort::English, "English"),
+                (VerbSort::Accuracy, "Accuracy"),
+                (VerbSort::LastSeen, "Last seen"),
+            ] {
+                ui.selectable_value(&mut sort, column, label);
+            }
+        });
+        self.verb_list_sort = sort;
+
+        let query = self.verb_list_search.trim().to_lowercase();
+        let rows: Vec<usize> = self
+            .verb_table_order(sort)
+            .iter()
+            .copied()
+            .filter(|&index| {
+                let verb = &self.verbs[index];
+                query.is_empty()
+                    || verb.infinitive.to_lowercase().contains(&query)
+                    || verb.english.to_lowercase().contains(&query)
+            })
+            .collect();
+        ui.label(
+            egui::RichText::new(format!(
+                "{} of {} verbs. Click a verb to practice it next.",
+                rows.len(),
+                self.active_verb_count()
+            ))
+            .small(),
+        );
+        ui.add_space(8.0);
+
+        let now = stats::now();
+        let mut practice = None;
+        // Only the rows in view are laid out, so big decks scroll smoothly
+        let row_height = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+        egui::ScrollArea::vertical().auto_shrink(false).show_rows(
+            ui,
+            row_height,
+            rows.len(),
+            |ui, visible| {
+                egui::Grid::new("verb_list_grid")
+                    .striped(true)
+                    .min_col_width(100.0)
+                    .spacing([24.0, 4.0])
+                    .show(ui, |ui| {
+                        for &index in &rows[visible] {
+                            let verb = &self.verbs[index];
+                            if ui
+                                .link(&verb.infinitive)
+                                .on_hover_text("Practice this verb next")
+                                .clicked()
+                            {
+                                practice = Some(index);
+                            }
+                            ui.label(&verb.english);
+                            let verb_stat = self
+                                .stats
+                                .verbs
+                                .get(&verb.infinitive)
+                                .filter(|verb_stat| verb_stat.attempts > 0);
+                            match verb_stat {
+                                Some(verb_stat) => ui
+                                    .label(format!(
+                                        "{:.0}% of {}",
+                                        verb_stat.correct as f32 / verb_stat.attempts as f32
+                                            * 100.0,
+                                        verb_stat.attempts
+                                    ))
+                                    .on_hover_text(recent_outcomes(&verb_stat.recent)),
+                                None => ui.label("–"),
+                            };
+                            match verb_stat.and_then(|verb_stat| verb_stat.last_seen) {
+                                Some(last_seen) => {
+                                    ui.label(stats::format_ago(now.saturating_sub(last_seen)))
+                                }
+                                None => ui.label("never"),
+                            };
+                            ui.end_row();
+                        }
+                    });
+            },
+        );
+
+        if let Some(index) = practice {
+            self.practice_verb(index);
+        }
+    }
+
+    /// Switches to practice and asks the verb at `index` as the next question.
+    fn practice_verb(&mut self, index: usize) {
+        self.requested_verb = Some(self.verbs[index].infinitive.clone());
+        self.pass_finished = false;
+        self.view = View::Practice;
+        self.next_verb();
+    }
+}